@@ -0,0 +1,3 @@
+alter table todos add column deleted_at timestamptz;
+
+create index idx_todos_deleted_at on todos(deleted_at) where deleted_at is not null;
@@ -0,0 +1,34 @@
+create type todo_history_action as enum ('created', 'updated', 'status_changed', 'deleted', 'restored', 'reverted');
+
+create table todo_history (
+    id uuid primary key default gen_random_uuid()
+    , todo_id uuid not null references todos(id) on delete cascade
+    , actor_id uuid references users(id) on delete set null
+    , version integer not null
+    , action todo_history_action not null
+    , changes jsonb not null default '{}'::jsonb
+    , snapshot jsonb not null
+    , created_at timestamptz not null default now()
+    , unique (todo_id, version)
+);
+
+create index idx_todo_history_todo_id on todo_history(todo_id);
+
+-- 既存のToDoには作成時点の履歴を登録しておく
+insert into todo_history (todo_id, actor_id, version, action, changes, snapshot, created_at)
+select
+    id
+    , user_id
+    , 1
+    , 'created'
+    , '{}'::jsonb
+    , jsonb_build_object(
+        'title', title
+        , 'description', description
+        , 'dueDate', due_date
+        , 'status', case status when 'in_progress' then 'inProgress' else status::text end
+        , 'priority', priority
+        , 'deletedAt', deleted_at
+    )
+    , created_at
+from todos;
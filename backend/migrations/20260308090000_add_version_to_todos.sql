@@ -0,0 +1 @@
+alter table todos add column version integer not null default 1;
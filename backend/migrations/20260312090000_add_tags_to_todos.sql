@@ -0,0 +1,3 @@
+alter table todos add column tags text[] not null default '{}';
+
+create index idx_todos_tags on todos using gin(tags);
@@ -0,0 +1,18 @@
+-- 手動並び替え用のランクキー（バイト順で比較するためC照合順序を使用）
+alter table todos add column position text collate "C";
+
+-- 既存のToDoは作成日時順に並べる
+-- ランクキーは末尾が '0' にならないよう '1' を付与する
+update todos
+set position = ranked.position
+from (
+    select
+        id
+        , lpad(row_number() over (partition by user_id order by created_at, id)::text, 9, '0') || '1' as position
+    from todos
+) as ranked
+where todos.id = ranked.id;
+
+alter table todos alter column position set not null;
+
+create index idx_todos_user_id_position on todos(user_id, position);
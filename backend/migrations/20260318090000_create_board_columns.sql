@@ -0,0 +1,36 @@
+-- カンバンボードの列（ステータスのカテゴリに対応付ける）
+create table board_columns (
+    id uuid primary key default gen_random_uuid()
+    , user_id uuid not null references users(id) on delete cascade
+    , name varchar(100) not null
+    , category todo_status not null
+    , position text collate "C" not null
+    , wip_limit integer check (wip_limit > 0)
+    , created_at timestamptz not null default now()
+    , updated_at timestamptz not null default now()
+    , unique (user_id, name)
+);
+
+create index idx_board_columns_user_id_position on board_columns(user_id, position);
+
+-- null の場合はステータスに対応する先頭の列に表示する
+alter table todos add column column_id uuid references board_columns(id) on delete set null;
+
+create index idx_todos_column_id on todos(column_id);
+
+-- 既存ユーザーにはステータスごとのデフォルト列を作成し、ToDoを割り当てる
+insert into board_columns (user_id, name, category, position)
+select users.id, defaults.name, defaults.category, defaults.position
+from users
+cross join (
+    values
+        ('To Do', 'pending'::todo_status, 'F')
+        , ('In Progress', 'in_progress'::todo_status, 'V')
+        , ('Done', 'completed'::todo_status, 'k')
+) as defaults(name, category, position);
+
+update todos
+set column_id = board_columns.id
+from board_columns
+where board_columns.user_id = todos.user_id
+  and board_columns.category = todos.status;
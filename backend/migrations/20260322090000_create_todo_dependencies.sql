@@ -0,0 +1,10 @@
+-- ToDoの依存関係（todo_id は blocker_id が完了するまで着手できない）
+create table todo_dependencies (
+    todo_id uuid not null references todos(id) on delete cascade
+    , blocker_id uuid not null references todos(id) on delete cascade
+    , created_at timestamptz not null default now()
+    , primary key (todo_id, blocker_id)
+    , check (todo_id <> blocker_id)
+);
+
+create index idx_todo_dependencies_blocker_id on todo_dependencies(blocker_id);
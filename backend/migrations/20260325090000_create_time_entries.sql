@@ -0,0 +1,20 @@
+-- 見積もり時間（分）
+alter table todos add column estimate_minutes integer check (estimate_minutes > 0);
+
+-- 作業時間の記録（ended_at が null の場合は計測中）
+create table time_entries (
+    id uuid primary key default gen_random_uuid()
+    , todo_id uuid not null references todos(id) on delete cascade
+    , user_id uuid not null references users(id) on delete cascade
+    , started_at timestamptz not null
+    , ended_at timestamptz
+    , note text
+    , created_at timestamptz not null default now()
+    , check (ended_at is null or ended_at >= started_at)
+);
+
+create index idx_time_entries_todo_id on time_entries(todo_id);
+create index idx_time_entries_user_id_started_at on time_entries(user_id, started_at);
+
+-- 計測中のタイマーはユーザーごとに1つまで
+create unique index idx_time_entries_running on time_entries(user_id) where ended_at is null;
@@ -0,0 +1,18 @@
+alter table todos add column start_date timestamptz;
+alter table todos add column completed_at timestamptz;
+
+-- 完了済みのToDoは、履歴上で最後に完了になった日時（履歴がなければ更新日時）を完了日時とする
+update todos
+set completed_at = coalesce(
+    (
+        select max(h.created_at)
+          from todo_history h
+         where h.todo_id = todos.id
+           and h.changes ? 'status'
+           and h.snapshot ->> 'status' = 'completed'
+    )
+    , updated_at
+)
+where status = 'completed';
+
+create index idx_todos_user_id_completed_at on todos(user_id, completed_at);
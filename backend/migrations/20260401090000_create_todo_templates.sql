@@ -0,0 +1,22 @@
+-- サブタスク（テンプレートから作成したToDoの親ToDo）
+alter table todos add column parent_id uuid references todos(id) on delete cascade;
+
+create index idx_todos_parent_id on todos(parent_id);
+
+-- ToDoテンプレート
+create table todo_templates (
+    id uuid primary key default gen_random_uuid()
+    , user_id uuid not null references users(id) on delete cascade
+    , name varchar(100) not null
+    , title varchar(255) not null
+    , description text
+    , priority todo_priority not null default 'medium'
+    , due_offset_days integer
+    , tags text[] not null default '{}'
+    , subtasks jsonb not null default '[]'::jsonb
+    , created_at timestamptz not null default now()
+    , updated_at timestamptz not null default now()
+    , unique (user_id, name)
+);
+
+create index idx_todo_templates_user_id on todo_templates(user_id);
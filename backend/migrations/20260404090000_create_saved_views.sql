@@ -0,0 +1,14 @@
+-- 保存済みビュー（一覧の絞り込み条件に名前を付けて保存する）
+-- 相対的な日付（today, +7d など）は実行時に解決するため文字列のまま保存する
+create table saved_views (
+    id uuid primary key default gen_random_uuid()
+    , user_id uuid not null references users(id) on delete cascade
+    , name varchar(100) not null
+    , filters jsonb not null default '{}'::jsonb
+    , position text collate "C" not null
+    , created_at timestamptz not null default now()
+    , updated_at timestamptz not null default now()
+    , unique (user_id, name)
+);
+
+create index idx_saved_views_user_id_position on saved_views(user_id, position);
@@ -0,0 +1,10 @@
+-- ユーザーのプロフィール（日付の解釈に使うタイムゾーン・ロケール・週の始まり）
+create type week_day as enum (
+    'monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday', 'sunday'
+);
+
+alter table users
+    add column display_name varchar(100)
+    , add column time_zone varchar(64) not null default 'UTC'
+    , add column locale varchar(35) not null default 'ja-JP'
+    , add column week_start week_day not null default 'monday';
@@ -0,0 +1,6 @@
+-- カレンダー購読用の秘密トークン（ユーザーごとに1つ、ハッシュ値を保存する）
+create table calendar_tokens (
+    user_id uuid primary key references users(id) on delete cascade
+    , token_hash varchar(64) not null unique
+    , created_at timestamptz not null default now()
+);
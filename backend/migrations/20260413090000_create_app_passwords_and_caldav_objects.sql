@@ -0,0 +1,21 @@
+-- アプリパスワード（CalDAVなどBearerトークンを使えないクライアント用、ハッシュ値を保存する）
+create table app_passwords (
+    id uuid primary key default gen_random_uuid()
+    , user_id uuid not null references users(id) on delete cascade
+    , name varchar(100) not null
+    , password_hash varchar(64) not null unique
+    , last_used_at timestamptz
+    , created_at timestamptz not null default now()
+);
+
+create index idx_app_passwords_user_id on app_passwords(user_id);
+
+-- CalDAVクライアントが作成したリソースの名前とUID
+-- 登録がないToDoは `{id}.ics` / `{id}@todo-web` として公開する
+create table caldav_objects (
+    todo_id uuid primary key references todos(id) on delete cascade
+    , user_id uuid not null references users(id) on delete cascade
+    , name varchar(255) not null
+    , uid varchar(255) not null
+    , unique (user_id, name)
+);
@@ -0,0 +1,44 @@
+-- ToDoの変更を通知するWebhook（`secret` は署名に使うため平文で保存する）
+create table webhooks (
+    id uuid primary key default gen_random_uuid()
+    , user_id uuid not null references users(id) on delete cascade
+    , url varchar(2048) not null
+    , description varchar(255)
+    , events varchar(50)[] not null
+    , secret varchar(64) not null
+    , active boolean not null default true
+    , consecutive_failures integer not null default 0
+    , disabled_at timestamptz
+    , created_at timestamptz not null default now()
+    , updated_at timestamptz not null default now()
+);
+
+create index idx_webhooks_user_id on webhooks(user_id);
+
+create type webhook_delivery_status as enum ('pending', 'succeeded', 'failed');
+
+-- 配信ログ（`pending` の間は `next_attempt_at` 以降に再試行する）
+create table webhook_deliveries (
+    id uuid primary key default gen_random_uuid()
+    , webhook_id uuid not null references webhooks(id) on delete cascade
+    , event varchar(50) not null
+    , payload jsonb not null
+    , status webhook_delivery_status not null default 'pending'
+    , attempts integer not null default 0
+    , next_attempt_at timestamptz not null default now()
+    , response_status integer
+    , response_body text
+    , error text
+    , duration_ms integer
+    , redelivery_of uuid references webhook_deliveries(id) on delete set null
+    , created_at timestamptz not null default now()
+    , delivered_at timestamptz
+);
+
+create index idx_webhook_deliveries_webhook_id on webhook_deliveries(webhook_id, created_at desc);
+create index idx_webhook_deliveries_pending on webhook_deliveries(next_attempt_at) where status = 'pending';
+
+-- 履歴をイベントの送信元として使う（配信を登録した日時、既存の履歴は対象外）
+alter table todo_history add column webhooks_dispatched_at timestamptz;
+update todo_history set webhooks_dispatched_at = now();
+create index idx_todo_history_webhooks_pending on todo_history(created_at) where webhooks_dispatched_at is null;
@@ -0,0 +1,15 @@
+-- リアルタイム通知用：履歴に通し番号（SSEのイベントID）を付ける
+alter table todo_history add column seq bigserial;
+create unique index idx_todo_history_seq on todo_history(seq);
+
+-- 履歴の追加時に、ToDoの所有者のIDを `todo_events` チャンネルに通知する（コミット時に送信される）
+create function notify_todo_event() returns trigger as $$
+begin
+    perform pg_notify('todo_events', (select user_id::text from todos where id = new.todo_id));
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger trg_todo_history_notify
+    after insert on todo_history
+    for each row execute function notify_todo_event();
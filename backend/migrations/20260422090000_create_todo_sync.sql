@@ -0,0 +1,58 @@
+-- 差分同期用：ToDoの変更ごとに通し番号（同期カーソル）を振る
+create sequence todo_sync_seq;
+alter table todos add column sync_seq bigint not null default nextval('todo_sync_seq');
+create index idx_todos_user_id_sync_seq on todos(user_id, sync_seq);
+
+-- 完全に削除したToDoの記録（ユーザーの削除時にも作られるため、外部キーは付けない）
+create table todo_tombstones (
+    todo_id uuid primary key
+    , user_id uuid not null
+    , sync_seq bigint not null
+    , deleted_at timestamp with time zone not null default now()
+);
+create index idx_todo_tombstones_user_id_sync_seq on todo_tombstones(user_id, sync_seq);
+
+-- 同じユーザーの変更はロックで直列化して採番する
+-- （コミット前の変更より大きい番号がコミットされ、カーソルが未コミットの変更を飛ばすことがないようにする）
+create function set_todo_sync_seq() returns trigger as $$
+begin
+    perform pg_advisory_xact_lock(hashtextextended(new.user_id::text, 0));
+    new.sync_seq := nextval('todo_sync_seq');
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger trg_todos_sync_seq
+    before insert or update on todos
+    for each row execute function set_todo_sync_seq();
+
+create function record_todo_tombstone() returns trigger as $$
+begin
+    perform pg_advisory_xact_lock(hashtextextended(old.user_id::text, 0));
+    insert into todo_tombstones (todo_id, user_id, sync_seq)
+    values (old.id, old.user_id, nextval('todo_sync_seq'))
+    on conflict (todo_id) do update
+        set sync_seq = excluded.sync_seq
+          , deleted_at = now();
+    return old;
+end;
+$$ language plpgsql;
+
+create trigger trg_todos_tombstone
+    after delete on todos
+    for each row execute function record_todo_tombstone();
+
+-- 競合の解決用：履歴にその時点のToDoのバージョンを記録する（既存の履歴は `null`）
+alter table todo_history add column todo_version integer;
+create index idx_todo_history_todo_id_todo_version on todo_history(todo_id, todo_version);
+
+create function set_todo_history_version() returns trigger as $$
+begin
+    new.todo_version := (select version from todos where id = new.todo_id);
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger trg_todo_history_todo_version
+    before insert on todo_history
+    for each row execute function set_todo_history_version();
@@ -0,0 +1,5 @@
+-- 親ToDoを完全に削除してもサブタスクは削除せず、親との関係だけを外す
+alter table todos drop constraint todos_parent_id_fkey;
+alter table todos
+    add constraint todos_parent_id_fkey
+    foreign key (parent_id) references todos(id) on delete set null;
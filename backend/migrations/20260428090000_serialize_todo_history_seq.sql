@@ -0,0 +1,15 @@
+-- 同じユーザーの履歴はロックで直列化して採番する
+-- （`todos.sync_seq` と同じく、コミット前の履歴より大きい番号がコミットされ、SSEの再開位置が未コミットの履歴を飛ばすことがないようにする）
+alter table todo_history alter column seq drop default;
+
+create function set_todo_history_seq() returns trigger as $$
+begin
+    perform pg_advisory_xact_lock(hashtextextended((select user_id from todos where id = new.todo_id)::text, 0));
+    new.seq := nextval(pg_get_serial_sequence('todo_history', 'seq'));
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger trg_todo_history_seq
+    before insert on todo_history
+    for each row execute function set_todo_history_seq();
@@ -9,6 +9,12 @@ pub struct Config {
     pub jwt_public_key_path: String,  // RS256公開鍵ファイルパス
     pub jwt_access_expires_in: i64,   // minutes
     pub jwt_refresh_expires_in: i64,  // days
+    pub trash_retention_days: i64,    // days
+    pub trash_purge_interval: u64,    // minutes
+    pub rank_rebalance_interval: u64, // minutes
+    pub webhook_delivery_interval: u64, // seconds
+    pub webhook_retry_base: u64,        // seconds
+    pub webhook_allow_private_urls: bool, // ローカル・プライベートアドレスへの送信を許可（開発・テスト用）
 }
 
 impl Config {
@@ -25,6 +31,29 @@ impl Config {
                 .unwrap_or_else(|_| "7".to_string())
                 .parse()
                 .unwrap_or(7),
+            trash_retention_days: env::var("TRASH_RETENTION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            trash_purge_interval: env::var("TRASH_PURGE_INTERVAL")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            rank_rebalance_interval: env::var("RANK_REBALANCE_INTERVAL")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            webhook_delivery_interval: env::var("WEBHOOK_DELIVERY_INTERVAL")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            webhook_retry_base: env::var("WEBHOOK_RETRY_BASE")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            webhook_allow_private_urls: env::var("WEBHOOK_ALLOW_PRIVATE_URLS")
+                .map(|v| v == "true")
+                .unwrap_or(false),
         })
     }
 }
@@ -0,0 +1,28 @@
+use axum::{extract::State, response::IntoResponse, Extension, Json};
+
+use crate::{
+    error::AppResult,
+    models::{agenda::AgendaResponse, auth::Claims},
+    AppState,
+};
+
+/// アジェンダ（期限切れ・今日・明日・今週・それ以降）の取得
+/// 期限で区分し、期限がなく開始日（`startDate`）が未来のToDoは開始日で区分する
+/// スヌーズは専用の項目を持たず、開始日を未来の日時に更新して表す（開始日になるまで期限切れ・今日に表示されない）
+#[utoipa::path(
+    get,
+    path = "/api/agenda",
+    responses(
+        (status = 200, description = "Non-completed todos grouped by date", body = AgendaResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "agenda"
+)]
+pub async fn get_agenda(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.agenda_service.get(claims.sub).await?;
+    Ok(Json(response))
+}
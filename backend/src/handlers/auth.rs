@@ -1,10 +1,17 @@
-use axum::{Json, Extension, extract::State, response::IntoResponse, http::StatusCode};
+use axum::{Json, Extension, extract::{Path, State}, response::IntoResponse, http::StatusCode};
 use axum_extra::extract::CookieJar;
 use axum_extra::extract::cookie::{Cookie, SameSite};
+use uuid::Uuid;
 use validator::Validate;
 
 use crate::error::{AppError, AppResult};
-use crate::models::auth::{AuthResponse, Claims, LoginRequest, RegisterRequest, UserResponse};
+use crate::models::app_password::{
+    AppPasswordResponse, CreateAppPasswordRequest, CreatedAppPasswordResponse,
+};
+use crate::models::auth::{
+    AuthResponse, Claims, LoginRequest, MeResponse, RegisterRequest, UserResponse,
+};
+use crate::models::user::{ProfileResponse, UpdateProfileRequest};
 use crate::AppState;
 
 const REFRESH_TOKEN_KEY: &str = "refresh_token";
@@ -144,17 +151,136 @@ pub async  fn logout(
     get,
     path = "/api/auth/me",
     responses(
-        (status = 200, description = "Current user info"),
+        (status = 200, description = "Current user info", body = MeResponse),
         (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found"),
     ),
     security(("bearer_auth" = [])),
     tag = "auth"
 )]
-pub async fn me(Extension(claims): Extension<Claims>) -> AppResult<impl IntoResponse> {
-    let body = serde_json::json!({
-        "id": claims.sub,
-        "email": claims.email,
-    });
-    
-    Ok(Json(body))
-}
\ No newline at end of file
+pub async fn me(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.profile_service.me(claims.sub).await?;
+
+    Ok(Json(response))
+}
+
+/// プロフィール取得
+#[utoipa::path(
+    get,
+    path = "/api/auth/me/profile",
+    responses(
+        (status = 200, description = "Profile", body = ProfileResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+pub async fn get_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.profile_service.get(claims.sub).await?;
+
+    Ok(Json(response))
+}
+
+/// プロフィール更新
+#[utoipa::path(
+    put,
+    path = "/api/auth/me/profile",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = ProfileResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+pub async fn update_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<UpdateProfileRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.profile_service.update(claims.sub, req).await?;
+
+    Ok(Json(response))
+}
+/// アプリパスワード一覧（CalDAVクライアント用）
+#[utoipa::path(
+    get,
+    path = "/api/auth/app-passwords",
+    responses(
+        (status = 200, description = "App passwords", body = Vec<AppPasswordResponse>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+pub async fn list_app_passwords(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.app_password_service.list(claims.sub).await?;
+
+    Ok(Json(response))
+}
+
+/// アプリパスワードの発行（パスワードはこのレスポンスでのみ返す）
+#[utoipa::path(
+    post,
+    path = "/api/auth/app-passwords",
+    request_body = CreateAppPasswordRequest,
+    responses(
+        (status = 201, description = "App password created", body = CreatedAppPasswordResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+pub async fn create_app_password(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<CreateAppPasswordRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.app_password_service.create(claims.sub, req).await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// アプリパスワードの無効化
+#[utoipa::path(
+    delete,
+    path = "/api/auth/app-passwords/{id}",
+    params(
+        ("id" = Uuid, Path, description = "App password ID"),
+    ),
+    responses(
+        (status = 204, description = "App password revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "App password not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+pub async fn delete_app_password(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    state.app_password_service.delete(id, claims.sub).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,161 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        auth::Claims,
+        board::{
+            BoardColumnResponse, BoardResponse, CreateBoardColumnRequest, MoveBoardColumnRequest,
+            UpdateBoardColumnRequest,
+        },
+    },
+    AppState,
+};
+
+/// カンバンボードの取得
+#[utoipa::path(
+    get,
+    path = "/api/board",
+    responses(
+        (status = 200, description = "Board columns with their todos", body = BoardResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "board"
+)]
+pub async fn get_board(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.board_service.board(claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// 列一覧の取得
+#[utoipa::path(
+    get,
+    path = "/api/board/columns",
+    responses(
+        (status = 200, description = "Board columns", body = Vec<BoardColumnResponse>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "board"
+)]
+pub async fn list_columns(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.board_service.list_columns(claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// 列の作成
+#[utoipa::path(
+    post,
+    path = "/api/board/columns",
+    request_body = CreateBoardColumnRequest,
+    responses(
+        (status = 201, description = "Column created", body = BoardColumnResponse),
+        (status = 400, description = "Validation error"),
+        (status = 409, description = "Column name already exists"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "board"
+)]
+pub async fn create_column(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<CreateBoardColumnRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.board_service.create_column(claims.sub, req).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// 列の更新
+#[utoipa::path(
+    put,
+    path = "/api/board/columns/{id}",
+    params(("id" = Uuid, Path, description = "Column ID")),
+    request_body = UpdateBoardColumnRequest,
+    responses(
+        (status = 200, description = "Column updated", body = BoardColumnResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Column name already exists"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "board"
+)]
+pub async fn update_column(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateBoardColumnRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.board_service.update_column(id, claims.sub, req).await?;
+    Ok(Json(response))
+}
+
+/// 列の並び替え
+#[utoipa::path(
+    post,
+    path = "/api/board/columns/{id}/move",
+    params(("id" = Uuid, Path, description = "Column ID")),
+    request_body = MoveBoardColumnRequest,
+    responses(
+        (status = 200, description = "Column moved", body = BoardColumnResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "board"
+)]
+pub async fn move_column(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<MoveBoardColumnRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.board_service.move_column(id, claims.sub, req).await?;
+    Ok(Json(response))
+}
+
+/// 列の削除
+#[utoipa::path(
+    delete,
+    path = "/api/board/columns/{id}",
+    params(("id" = Uuid, Path, description = "Column ID")),
+    responses(
+        (status = 204, description = "Column deleted"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Last column of a status category"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "board"
+)]
+pub async fn delete_column(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    state.board_service.delete_column(id, claims.sub).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
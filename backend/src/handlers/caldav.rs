@@ -0,0 +1,423 @@
+//! CalDAVのタスクコレクション
+//!
+//! - `/caldav/` : ルート（`current-user-principal` を返す）
+//! - `/caldav/{user_id}/` : プリンシパル兼カレンダーホーム
+//! - `/caldav/{user_id}/todos/` : ToDoのタスクコレクション（VTODOのみ）
+//! - `/caldav/{user_id}/todos/{name}` : 1件のToDo（VTODOのカレンダーオブジェクト）
+//!
+//! 認証はメールアドレスとアプリパスワードによるBasic認証
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{
+        header::{ALLOW, CONTENT_TYPE, ETAG, LOCATION},
+        HeaderMap, HeaderName, Method, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Extension,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::etag::{etag, if_match_versions, if_none_match};
+use crate::{
+    error::{AppError, AppResult},
+    models::{app_password::AppPasswordUser, caldav::CaldavResource},
+    services::{
+        caldav::{
+            self, escape, percent_decode, percent_encode, DavRequest, DavResponse, PropName,
+            CALDAV, CALENDARSERVER, DAV,
+        },
+        ical,
+    },
+    AppState,
+};
+
+const DAV_COMPLIANCE: &str = "1, 3, calendar-access";
+const ALLOWED_METHODS: &str = "OPTIONS, GET, HEAD, PUT, DELETE, PROPFIND, REPORT";
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+const COLLECTION_NAME: &str = "todos";
+
+/// `/.well-known/caldav` からルートへ誘導
+pub async fn well_known() -> impl IntoResponse {
+    (StatusCode::MOVED_PERMANENTLY, [(LOCATION, "/caldav/")])
+}
+
+/// ルート
+pub async fn root(
+    State(state): State<AppState>,
+    Extension(user): Extension<AppPasswordUser>,
+    method: Method,
+    body: String,
+) -> AppResult<Response> {
+    match method.as_str() {
+        "OPTIONS" => Ok(options()),
+        "PROPFIND" => {
+            let request = parse_request(&body)?;
+            let context = Context::load(&state, &user).await?;
+            Ok(multistatus(vec![context.response(
+                "/caldav/".to_string(),
+                &Kind::Root,
+                &request,
+            )]))
+        }
+        _ => Ok(method_not_allowed()),
+    }
+}
+
+/// プリンシパル兼カレンダーホーム
+pub async fn principal(
+    State(state): State<AppState>,
+    Extension(user): Extension<AppPasswordUser>,
+    Path(user_id): Path<Uuid>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> AppResult<Response> {
+    check_owner(user_id, &user)?;
+
+    match method.as_str() {
+        "OPTIONS" => Ok(options()),
+        "PROPFIND" => {
+            let request = parse_request(&body)?;
+            let context = Context::load(&state, &user).await?;
+            let mut responses =
+                vec![context.response(context.principal_href(), &Kind::Principal, &request)];
+            if depth(&headers) > 0 {
+                responses.push(context.response(
+                    context.collection_href(),
+                    &Kind::Collection,
+                    &request,
+                ));
+            }
+            Ok(multistatus(responses))
+        }
+        _ => Ok(method_not_allowed()),
+    }
+}
+
+/// タスクコレクション
+pub async fn collection(
+    State(state): State<AppState>,
+    Extension(user): Extension<AppPasswordUser>,
+    Path(user_id): Path<Uuid>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> AppResult<Response> {
+    check_owner(user_id, &user)?;
+
+    match method.as_str() {
+        "OPTIONS" => Ok(options()),
+        "PROPFIND" => {
+            let request = parse_request(&body)?;
+            let context = Context::load(&state, &user).await?;
+            let mut responses =
+                vec![context.response(context.collection_href(), &Kind::Collection, &request)];
+            if depth(&headers) > 0 {
+                for resource in state.caldav_service.resources(user.user_id).await? {
+                    responses.push(context.object_response(&resource, &request));
+                }
+            }
+            Ok(multistatus(responses))
+        }
+        "REPORT" => {
+            let request = parse_request(&body)?;
+            let context = Context::load(&state, &user).await?;
+            let root = request.root.clone().unwrap_or_else(|| PropName::new(DAV, ""));
+
+            if root.is(CALDAV, "calendar-multiget") {
+                let mut responses = Vec::new();
+                for href in &request.hrefs {
+                    let name = percent_decode(href.trim_end_matches('/').rsplit('/').next().unwrap_or_default());
+                    match state.caldav_service.resource(user.user_id, &name).await? {
+                        Some(resource) => responses.push(context.object_response(&resource, &request)),
+                        None => responses.push(DavResponse {
+                            href: href.clone(),
+                            status: Some("404 Not Found"),
+                            ..Default::default()
+                        }),
+                    }
+                }
+                Ok(multistatus(responses))
+            } else if root.is(CALDAV, "calendar-query") {
+                // 時間範囲などの条件は扱わず、VTODOを要求された場合は全件を返す
+                let wants_todos =
+                    request.components.is_empty() || request.components.iter().any(|c| c == "VTODO");
+                let mut responses = Vec::new();
+                if wants_todos {
+                    for resource in state.caldav_service.resources(user.user_id).await? {
+                        responses.push(context.object_response(&resource, &request));
+                    }
+                }
+                Ok(multistatus(responses))
+            } else {
+                Ok((
+                    StatusCode::FORBIDDEN,
+                    [(CONTENT_TYPE, XML_CONTENT_TYPE)],
+                    r#"<?xml version="1.0" encoding="utf-8"?><d:error xmlns:d="DAV:"><d:supported-report/></d:error>"#,
+                )
+                    .into_response())
+            }
+        }
+        _ => Ok(method_not_allowed()),
+    }
+}
+
+/// 1件のToDo
+pub async fn object(
+    State(state): State<AppState>,
+    Extension(user): Extension<AppPasswordUser>,
+    Path((user_id, name)): Path<(Uuid, String)>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> AppResult<Response> {
+    check_owner(user_id, &user)?;
+
+    match method.as_str() {
+        "OPTIONS" => Ok(options()),
+        "GET" | "HEAD" => {
+            let resource = find_resource(&state, &user, &name).await?;
+            let tag = etag(resource.todo.version);
+            if if_none_match(&headers, resource.todo.version) {
+                return Ok((StatusCode::NOT_MODIFIED, [(ETAG, tag)]).into_response());
+            }
+            let body = ical::render_todo(&resource.todo, &resource.uid, Utc::now());
+            Ok((
+                [
+                    (CONTENT_TYPE, CALENDAR_CONTENT_TYPE.parse().expect("valid header")),
+                    (ETAG, tag),
+                ],
+                body,
+            )
+                .into_response())
+        }
+        "PUT" => {
+            let create_only = headers
+                .get(axum::http::header::IF_NONE_MATCH)
+                .is_some_and(|v| v.as_bytes() == b"*");
+            let expected_versions = if_match_versions(&headers);
+            let result = state
+                .caldav_service
+                .put(
+                    user.user_id,
+                    &name,
+                    &body,
+                    expected_versions.as_deref(),
+                    create_only,
+                )
+                .await?;
+            let status = if result.created {
+                StatusCode::CREATED
+            } else {
+                StatusCode::NO_CONTENT
+            };
+            Ok((status, [(ETAG, etag(result.version))]).into_response())
+        }
+        "DELETE" => {
+            let expected_versions = if_match_versions(&headers);
+            state
+                .caldav_service
+                .delete(user.user_id, &name, expected_versions.as_deref())
+                .await?;
+            Ok(StatusCode::NO_CONTENT.into_response())
+        }
+        "PROPFIND" => {
+            let request = parse_request(&body)?;
+            let resource = find_resource(&state, &user, &name).await?;
+            let context = Context::load(&state, &user).await?;
+            Ok(multistatus(vec![context.object_response(&resource, &request)]))
+        }
+        _ => Ok(method_not_allowed()),
+    }
+}
+
+// Helpers
+
+enum Kind<'a> {
+    Root,
+    Principal,
+    Collection,
+    Object(&'a CaldavResource),
+}
+
+/// プロパティの値を組み立てるための情報
+struct Context {
+    user_id: Uuid,
+    email: String,
+    display_name: Option<String>,
+    ctag: String,
+}
+
+impl Context {
+    async fn load(state: &AppState, user: &AppPasswordUser) -> AppResult<Self> {
+        let profile = state.profile_service.user(user.user_id).await?;
+        let ctag = state.caldav_service.ctag(user.user_id).await?;
+
+        Ok(Self {
+            user_id: profile.id,
+            email: profile.email,
+            display_name: profile.display_name,
+            ctag,
+        })
+    }
+
+    fn principal_href(&self) -> String {
+        format!("/caldav/{}/", self.user_id)
+    }
+
+    fn collection_href(&self) -> String {
+        format!("{}{}/", self.principal_href(), COLLECTION_NAME)
+    }
+
+    fn object_response(&self, resource: &CaldavResource, request: &DavRequest) -> DavResponse {
+        let href = format!("{}{}", self.collection_href(), percent_encode(&resource.name));
+        self.response(href, &Kind::Object(resource), request)
+    }
+
+    /// 要求されたプロパティを返す（要求がない場合は主なプロパティ）
+    fn response(&self, href: String, kind: &Kind, request: &DavRequest) -> DavResponse {
+        let mut response = DavResponse {
+            href,
+            ..Default::default()
+        };
+
+        if request.props.is_empty() {
+            let defaults = [
+                PropName::new(DAV, "resourcetype"),
+                PropName::new(DAV, "displayname"),
+                PropName::new(DAV, "getetag"),
+                PropName::new(DAV, "getcontenttype"),
+                PropName::new(DAV, "getlastmodified"),
+                PropName::new(CALENDARSERVER, "getctag"),
+            ];
+            for name in defaults {
+                if let Some(value) = self.property(kind, &name) {
+                    response.found.push((name, value));
+                }
+            }
+            return response;
+        }
+
+        for name in &request.props {
+            match self.property(kind, name) {
+                Some(value) => response.found.push((name.clone(), value)),
+                None => response.missing.push(name.clone()),
+            }
+        }
+        response
+    }
+
+    /// プロパティの値（XML）
+    fn property(&self, kind: &Kind, name: &PropName) -> Option<String> {
+        let principal = format!("<d:href>{}</d:href>", escape(&self.principal_href()));
+
+        match (name.namespace.as_str(), name.name.as_str(), kind) {
+            (DAV, "resourcetype", Kind::Root) => Some("<d:collection/>".into()),
+            (DAV, "resourcetype", Kind::Principal) => Some("<d:collection/><d:principal/>".into()),
+            (DAV, "resourcetype", Kind::Collection) => Some("<d:collection/><c:calendar/>".into()),
+            (DAV, "resourcetype", Kind::Object(_)) => Some(String::new()),
+            (DAV, "current-user-principal" | "principal-URL" | "owner", _) => Some(principal),
+            (CALDAV, "calendar-home-set", Kind::Root | Kind::Principal) => Some(principal),
+            (CALDAV, "calendar-user-address-set", Kind::Root | Kind::Principal) => Some(format!(
+                "<d:href>mailto:{}</d:href>",
+                escape(&self.email)
+            )),
+            (DAV, "displayname", Kind::Principal) => Some(escape(
+                self.display_name.as_deref().unwrap_or(&self.email),
+            )),
+            (DAV, "displayname", Kind::Collection) => Some("ToDo".into()),
+            (CALDAV, "supported-calendar-component-set", Kind::Collection) => {
+                Some(r#"<c:comp name="VTODO"/>"#.into())
+            }
+            (CALENDARSERVER, "getctag", Kind::Collection) => Some(escape(&self.ctag)),
+            (DAV, "supported-report-set", Kind::Collection) => Some(
+                concat!(
+                    "<d:supported-report><d:report><c:calendar-query/></d:report></d:supported-report>",
+                    "<d:supported-report><d:report><c:calendar-multiget/></d:report></d:supported-report>",
+                )
+                .into(),
+            ),
+            (DAV, "current-user-privilege-set", Kind::Collection | Kind::Object(_)) => Some(
+                ["read", "write", "write-content", "write-properties", "bind", "unbind"]
+                    .iter()
+                    .map(|p| format!("<d:privilege><d:{}/></d:privilege>", p))
+                    .collect(),
+            ),
+            (DAV, "getetag", Kind::Object(resource)) => {
+                Some(escape(&format!("\"{}\"", resource.todo.version)))
+            }
+            (DAV, "getcontenttype", Kind::Object(_)) => {
+                Some("text/calendar; charset=utf-8; component=vtodo".into())
+            }
+            (DAV, "getlastmodified", Kind::Object(resource)) => Some(
+                resource
+                    .todo
+                    .updated_at
+                    .format("%a, %d %b %Y %H:%M:%S GMT")
+                    .to_string(),
+            ),
+            (CALDAV, "calendar-data", Kind::Object(resource)) => Some(escape(
+                &ical::render_todo(&resource.todo, &resource.uid, Utc::now()),
+            )),
+            _ => None,
+        }
+    }
+}
+
+fn check_owner(user_id: Uuid, user: &AppPasswordUser) -> AppResult<()> {
+    if user_id != user.user_id {
+        return Err(AppError::NotFound("Principal not found".into()));
+    }
+    Ok(())
+}
+
+async fn find_resource(
+    state: &AppState,
+    user: &AppPasswordUser,
+    name: &str,
+) -> AppResult<CaldavResource> {
+    state
+        .caldav_service
+        .resource(user.user_id, name)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Resource not found".into()))
+}
+
+fn parse_request(body: &str) -> AppResult<DavRequest> {
+    caldav::parse_request(body).map_err(AppError::Validation)
+}
+
+/// Depthヘッダー（省略時・`infinity` は1として扱う）
+fn depth(headers: &HeaderMap) -> u8 {
+    match headers.get("depth").and_then(|v| v.to_str().ok()) {
+        Some("0") => 0,
+        _ => 1,
+    }
+}
+
+fn multistatus(responses: Vec<DavResponse>) -> Response {
+    Response::builder()
+        .status(StatusCode::MULTI_STATUS)
+        .header(CONTENT_TYPE, XML_CONTENT_TYPE)
+        .body(Body::from(caldav::multistatus(&responses)))
+        .expect("valid response")
+}
+
+fn options() -> Response {
+    (
+        StatusCode::OK,
+        [
+            (HeaderName::from_static("dav"), DAV_COMPLIANCE),
+            (ALLOW, ALLOWED_METHODS),
+        ],
+    )
+        .into_response()
+}
+
+fn method_not_allowed() -> Response {
+    (StatusCode::METHOD_NOT_ALLOWED, [(ALLOW, ALLOWED_METHODS)]).into_response()
+}
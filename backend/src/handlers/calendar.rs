@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::IntoResponse,
+    Extension, Json,
+};
+
+use crate::{
+    error::AppResult,
+    models::{
+        auth::Claims,
+        calendar::{CalendarExportQuery, CalendarFeedResponse, CalendarTokenResponse},
+    },
+    AppState,
+};
+
+const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+/// iCalendar形式でエクスポート
+#[utoipa::path(
+    get,
+    path = "/api/todos/export.ics",
+    params(
+        ("component" = Option<String>, Query, description = "vtodo or vevent (default: both)"),
+    ),
+    responses(
+        (status = 200, description = "Todos with due dates as iCalendar", content_type = "text/calendar", body = String),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "calendar"
+)]
+pub async fn export(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<CalendarExportQuery>,
+) -> AppResult<impl IntoResponse> {
+    let body = state
+        .calendar_service
+        .export(claims.sub, &query.components())
+        .await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, CALENDAR_CONTENT_TYPE),
+            (CONTENT_DISPOSITION, "attachment; filename=\"todos.ics\""),
+        ],
+        body,
+    ))
+}
+
+/// 購読URLによるiCalendarの取得（Bearerトークン不要）
+#[utoipa::path(
+    get,
+    path = "/api/calendar/feed/{token}",
+    params(
+        ("token" = String, Path, description = "Subscription token (optionally followed by .ics)"),
+        ("component" = Option<String>, Query, description = "vtodo or vevent (default: both)"),
+    ),
+    responses(
+        (status = 200, description = "Todos with due dates as iCalendar", content_type = "text/calendar", body = String),
+        (status = 404, description = "Unknown or revoked token"),
+    ),
+    tag = "calendar"
+)]
+pub async fn feed(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    Query(query): Query<CalendarExportQuery>,
+) -> AppResult<impl IntoResponse> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+    let body = state
+        .calendar_service
+        .feed(token, &query.components())
+        .await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, CALENDAR_CONTENT_TYPE),
+            (CACHE_CONTROL, "private, no-cache"),
+        ],
+        body,
+    ))
+}
+
+/// 購読URLの発行状況を取得
+#[utoipa::path(
+    get,
+    path = "/api/calendar/token",
+    responses(
+        (status = 200, description = "Subscription status", body = CalendarFeedResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "calendar"
+)]
+pub async fn get_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.calendar_service.get_feed(claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// 購読トークンの発行・再発行（以前のURLは無効になる）
+#[utoipa::path(
+    post,
+    path = "/api/calendar/token",
+    responses(
+        (status = 201, description = "Subscription token issued", body = CalendarTokenResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "calendar"
+)]
+pub async fn regenerate_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.calendar_service.regenerate_token(claims.sub).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// 購読トークンの無効化
+#[utoipa::path(
+    delete,
+    path = "/api/calendar/token",
+    responses(
+        (status = 204, description = "Subscription token revoked"),
+        (status = 404, description = "No subscription token"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "calendar"
+)]
+pub async fn revoke_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    state.calendar_service.revoke_token(claims.sub).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,61 @@
+//! ETag / 条件付きリクエストのヘルパー
+
+use axum::http::{
+    header::{IF_MATCH, IF_NONE_MATCH},
+    HeaderMap, HeaderValue,
+};
+
+/// バージョンからETagを生成
+pub fn etag(version: i32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", version)).expect("ETag must be a valid header value")
+}
+
+/// If-Match ヘッダーから許可するバージョンの一覧を取得
+/// ヘッダーがない、または `*` の場合は `None`（バージョンを問わない）
+/// 弱いETagは強い比較で一致しないため無視する
+pub fn if_match_versions(headers: &HeaderMap) -> Option<Vec<i32>> {
+    let values = header_values(headers, IF_MATCH)?;
+    if values.iter().any(|v| v == "*") {
+        return None;
+    }
+
+    Some(
+        values
+            .iter()
+            .filter(|v| !v.starts_with("W/"))
+            .filter_map(|v| parse_version(v))
+            .collect(),
+    )
+}
+
+/// If-None-Match ヘッダーが現在のバージョンに一致するか（弱い比較）
+pub fn if_none_match(headers: &HeaderMap, version: i32) -> bool {
+    let Some(values) = header_values(headers, IF_NONE_MATCH) else {
+        return false;
+    };
+
+    values.iter().any(|v| {
+        v == "*" || parse_version(v.strip_prefix("W/").unwrap_or(v)) == Some(version)
+    })
+}
+
+fn header_values(headers: &HeaderMap, name: axum::http::HeaderName) -> Option<Vec<String>> {
+    let values: Vec<String> = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect();
+
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+fn parse_version(tag: &str) -> Option<i32> {
+    tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()
+}
@@ -0,0 +1,64 @@
+use std::{convert::Infallible, time::Duration};
+
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Sse,
+    },
+    Extension,
+};
+use chrono::Utc;
+use futures::StreamExt;
+
+use crate::{
+    error::AppResult, models::auth::Claims, services::event_service::EventStreamItem, AppState,
+};
+
+/// ToDoの変更イベントのストリーム（Server-Sent Events）
+/// イベント名は `todo.created` / `todo.updated` / `todo.status_changed` / `todo.deleted`、IDは履歴の通し番号
+/// 再接続時に `Last-Event-ID` を送ると、その後のイベントを再送する（多すぎる場合は `reset` を送る）
+/// アクセストークンの有効期限で切断するため、クライアントはトークンを更新して再接続する
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    params(("Last-Event-ID" = Option<i64>, Header, description = "Resume after this event ID")),
+    responses(
+        (status = 200, description = "Event stream", content_type = "text/event-stream"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "events"
+)]
+pub async fn stream(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    headers: HeaderMap,
+) -> AppResult<impl IntoResponse> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok());
+    let expires_in = (claims.exp as i64 - Utc::now().timestamp()).max(0) as u64;
+
+    let events = state
+        .event_service
+        .subscribe(claims.sub, last_event_id)
+        .await?
+        .map(|item| {
+            let event = match item {
+                EventStreamItem::Todo(event) => Event::default()
+                    .id(event.seq.to_string())
+                    .event(event.name())
+                    .data(event.payload().to_string()),
+                EventStreamItem::Reset { seq } => {
+                    Event::default().id(seq.to_string()).event("reset").data("{}")
+                }
+            };
+            Ok::<_, Infallible>(event)
+        })
+        .take_until(tokio::time::sleep(Duration::from_secs(expires_in)));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
@@ -1,3 +1,16 @@
 //! Request handlers
+pub mod agenda;
 pub mod auth;
-pub mod todo;
\ No newline at end of file
+pub mod board;
+pub mod caldav;
+pub mod calendar;
+pub mod etag;
+pub mod event;
+pub mod saved_view;
+pub mod stats;
+pub mod sync;
+pub mod template;
+pub mod time_entry;
+pub mod todo;
+pub mod transfer;
+pub mod webhook;
@@ -0,0 +1,190 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        auth::Claims,
+        saved_view::{
+            MoveSavedViewRequest, SavedViewRequest, SavedViewResponse, SavedViewTodosQuery,
+        },
+        todo::TodoListResponse,
+    },
+    AppState,
+};
+
+/// ビュー一覧の取得
+#[utoipa::path(
+    get,
+    path = "/api/views",
+    responses(
+        (status = 200, description = "Saved views", body = Vec<SavedViewResponse>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "views"
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.saved_view_service.list(claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// ビューの作成
+#[utoipa::path(
+    post,
+    path = "/api/views",
+    request_body = SavedViewRequest,
+    responses(
+        (status = 201, description = "View created", body = SavedViewResponse),
+        (status = 400, description = "Validation error"),
+        (status = 409, description = "View name already exists"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "views"
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<SavedViewRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.saved_view_service.create(claims.sub, req).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// ビュー詳細の取得
+#[utoipa::path(
+    get,
+    path = "/api/views/{id}",
+    params(("id" = Uuid, Path, description = "View ID")),
+    responses(
+        (status = 200, description = "Saved view", body = SavedViewResponse),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "views"
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.saved_view_service.get_by_id(id, claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// ビューの更新
+#[utoipa::path(
+    put,
+    path = "/api/views/{id}",
+    params(("id" = Uuid, Path, description = "View ID")),
+    request_body = SavedViewRequest,
+    responses(
+        (status = 200, description = "View updated", body = SavedViewResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "View name already exists"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "views"
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<SavedViewRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.saved_view_service.update(id, claims.sub, req).await?;
+    Ok(Json(response))
+}
+
+/// ビューの並び替え
+#[utoipa::path(
+    post,
+    path = "/api/views/{id}/move",
+    params(("id" = Uuid, Path, description = "View ID")),
+    request_body = MoveSavedViewRequest,
+    responses(
+        (status = 200, description = "View moved", body = SavedViewResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "views"
+)]
+pub async fn move_view(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<MoveSavedViewRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.saved_view_service.move_view(id, claims.sub, req).await?;
+    Ok(Json(response))
+}
+
+/// ビューの削除
+#[utoipa::path(
+    delete,
+    path = "/api/views/{id}",
+    params(("id" = Uuid, Path, description = "View ID")),
+    responses(
+        (status = 204, description = "View deleted"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "views"
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    state.saved_view_service.delete(id, claims.sub).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// ビューの条件でToDo一覧を取得
+#[utoipa::path(
+    get,
+    path = "/api/views/{id}/todos",
+    params(
+        ("id" = Uuid, Path, description = "View ID"),
+        ("page" = Option<i64>, Query, description = "Page number"),
+        ("perPage" = Option<i64>, Query, description = "Items per page"),
+    ),
+    responses(
+        (status = 200, description = "Todos matching the view", body = TodoListResponse),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "views"
+)]
+pub async fn todos(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SavedViewTodosQuery>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.saved_view_service.todos(id, claims.sub, query).await?;
+    Ok(Json(response))
+}
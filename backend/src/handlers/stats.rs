@@ -0,0 +1,40 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Extension, Json,
+};
+
+use crate::{
+    error::AppResult,
+    models::{
+        auth::Claims,
+        stats::{StatsQuery, StatsResponse},
+    },
+    AppState,
+};
+
+/// 統計の取得
+#[utoipa::path(
+    get,
+    path = "/api/stats",
+    params(
+        ("from" = Option<String>, Query, description = "Start date (YYYY-MM-DD, inclusive, default: 29 days before to)"),
+        ("to" = Option<String>, Query, description = "End date (YYYY-MM-DD, inclusive, default: today)"),
+        ("interval" = Option<String>, Query, description = "Series interval: day or week (default: day)"),
+    ),
+    responses(
+        (status = 200, description = "Todo statistics", body = StatsResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "stats"
+)]
+pub async fn get_stats(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<StatsQuery>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.stats_service.get(claims.sub, query).await?;
+    Ok(Json(response))
+}
@@ -0,0 +1,68 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Extension, Json,
+};
+
+use crate::{
+    error::AppResult,
+    models::{
+        auth::Claims,
+        sync::{SyncPushRequest, SyncPushResponse, SyncQuery, SyncResponse},
+    },
+    AppState,
+};
+
+/// カーソル以降の変更の取得（差分同期）
+/// 作成・更新されたToDoと、削除されたToDo（ゴミ箱への移動・完全な削除）を古い順に返す
+/// `hasMore` が `true` の間は、返された `cursor` を指定して続きを取得する
+#[utoipa::path(
+    get,
+    path = "/api/sync",
+    params(
+        ("since" = Option<String>, Query, description = "Cursor returned by the previous sync (omit for a full sync)"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of changes (default 500, max 1000)"),
+    ),
+    responses(
+        (status = 200, description = "Changes since the cursor", body = SyncResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sync"
+)]
+pub async fn pull(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<SyncQuery>,
+) -> AppResult<impl IntoResponse> {
+    let response = state
+        .sync_service
+        .pull(claims.sub, query.since.as_deref(), query.limit)
+        .await?;
+    Ok(Json(response))
+}
+
+/// オフライン中の変更の送信（差分同期）
+/// 変更は送信順に適用し、元のバージョン以降にサーバーで変更されたフィールドはサーバーの値を優先する
+/// `cursor` 以降の変更（送信した変更の適用結果を含む）と新しいカーソルを返す
+#[utoipa::path(
+    post,
+    path = "/api/sync",
+    request_body = SyncPushRequest,
+    responses(
+        (status = 200, description = "Mutation results and changes since the cursor", body = SyncPushResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sync"
+)]
+pub async fn push(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<SyncPushRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.sync_service.push(claims.sub, req).await?;
+    Ok(Json(response))
+}
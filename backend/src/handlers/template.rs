@@ -0,0 +1,166 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        auth::Claims,
+        template::{
+            InstantiateTemplateRequest, InstantiateTemplateResponse, TemplateRequest,
+            TemplateResponse,
+        },
+    },
+    AppState,
+};
+
+/// テンプレート一覧の取得
+#[utoipa::path(
+    get,
+    path = "/api/templates",
+    responses(
+        (status = 200, description = "Templates", body = Vec<TemplateResponse>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "templates"
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.template_service.list(claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// テンプレートの作成
+#[utoipa::path(
+    post,
+    path = "/api/templates",
+    request_body = TemplateRequest,
+    responses(
+        (status = 201, description = "Template created", body = TemplateResponse),
+        (status = 400, description = "Validation error"),
+        (status = 409, description = "Template name already exists"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "templates"
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<TemplateRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.template_service.create(claims.sub, req).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// テンプレート詳細の取得
+#[utoipa::path(
+    get,
+    path = "/api/templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 200, description = "Template", body = TemplateResponse),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "templates"
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.template_service.get_by_id(id, claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// テンプレートの更新
+#[utoipa::path(
+    put,
+    path = "/api/templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    request_body = TemplateRequest,
+    responses(
+        (status = 200, description = "Template updated", body = TemplateResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Template name already exists"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "templates"
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<TemplateRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.template_service.update(id, claims.sub, req).await?;
+    Ok(Json(response))
+}
+
+/// テンプレートの削除
+#[utoipa::path(
+    delete,
+    path = "/api/templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 204, description = "Template deleted"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "templates"
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    state.template_service.delete(id, claims.sub).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// テンプレートからToDoを作成
+#[utoipa::path(
+    post,
+    path = "/api/templates/{id}/instantiate",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    request_body = InstantiateTemplateRequest,
+    responses(
+        (status = 201, description = "Todos created from the template", body = InstantiateTemplateResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "templates"
+)]
+pub async fn instantiate(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<InstantiateTemplateRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = state
+        .template_service
+        .instantiate(id, claims.sub, req)
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
@@ -0,0 +1,167 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        auth::Claims,
+        time_entry::{
+            CreateTimeEntryRequest, TimeEntryResponse, TimeReportQuery, TimeReportResponse,
+        },
+    },
+    AppState,
+};
+
+/// タイマーの開始
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/timer/start",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 201, description = "Timer started", body = TimeEntryResponse),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Another timer is already running"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "time"
+)]
+pub async fn start_timer(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.time_entry_service.start_timer(id, claims.sub).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// タイマーの停止
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/timer/stop",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "Timer stopped", body = TimeEntryResponse),
+        (status = 404, description = "Not found or no running timer"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "time"
+)]
+pub async fn stop_timer(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.time_entry_service.stop_timer(id, claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// 作業時間の記録一覧の取得
+#[utoipa::path(
+    get,
+    path = "/api/todos/{id}/time-entries",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "Time entries", body = Vec<TimeEntryResponse>),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "time"
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.time_entry_service.list(id, claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// 作業時間の手動登録
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/time-entries",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    request_body = CreateTimeEntryRequest,
+    responses(
+        (status = 201, description = "Time entry created", body = TimeEntryResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "time"
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateTimeEntryRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.time_entry_service.create(id, claims.sub, req).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// 作業時間の記録の削除
+#[utoipa::path(
+    delete,
+    path = "/api/todos/{id}/time-entries/{entry_id}",
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("entry_id" = Uuid, Path, description = "Time entry ID"),
+    ),
+    responses(
+        (status = 204, description = "Time entry deleted"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "time"
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path((id, entry_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<impl IntoResponse> {
+    state
+        .time_entry_service
+        .delete(entry_id, id, claims.sub)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 作業時間レポートの取得
+#[utoipa::path(
+    get,
+    path = "/api/time/report",
+    params(
+        ("from" = String, Query, description = "Start date (YYYY-MM-DD, inclusive)"),
+        ("to" = String, Query, description = "End date (YYYY-MM-DD, inclusive)"),
+    ),
+    responses(
+        (status = 200, description = "Time report by day, todo and tag", body = TimeReportResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "time"
+)]
+pub async fn report(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<TimeReportQuery>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.time_entry_service.report(claims.sub, query).await?;
+    Ok(Json(response))
+}
@@ -1,20 +1,28 @@
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{
+        header::{CONTENT_TYPE, ETAG},
+        HeaderMap, StatusCode,
+    },
     response::IntoResponse,
     Extension, Json,
 };
 use uuid::Uuid;
 use validator::Validate;
 
+use super::etag::{etag, if_match_versions, if_none_match};
 use crate::{
     error::{AppError, AppResult},
     models::{
         auth::Claims,
         todo::{
-            CreateTodoRequest, TodoListResponse, TodoQuery, TodoResponse, UpdateTodoRequest,
-            UpdateTodoStatusRequest,
+            BulkTodoRequest, BulkTodoResponse, CreateTodoRequest, MoveTodoRequest,
+            QuickAddTodoRequest, QuickAddTodoResponse, TodoListResponse, TodoPatch, TodoQuery,
+            TodoResponse, TrashQuery, UpdateTodoRequest, UpdateTodoStatusRequest,
         },
+        todo_dependency::AddBlockerRequest,
+        todo_history::TodoHistoryResponse,
     },
     AppState,
 };
@@ -26,8 +34,13 @@ use crate::{
     params(
         ("status" = Option<String>, Query, description = "Filter by status"),
         ("priority" = Option<String>, Query, description = "Filter by priority"),
-        ("sort" = Option<String>, Query, description = "Sort field"),
-        ("order" = Option<String>, Query, description = "Sort order"),
+        ("completedAfter" = Option<String>, Query, description = "Filter by completion date (inclusive)"),
+        ("completedBefore" = Option<String>, Query, description = "Filter by completion date (inclusive)"),
+        ("tag" = Option<String>, Query, description = "Filter by tag"),
+        ("q" = Option<String>, Query, description = "Search title and description (case-insensitive)"),
+        ("filter" = Option<String>, Query, description = "Filter expression, e.g. `priority:high and (status:pending or due:<today) and not tag:someday`"),
+        ("sort" = Option<String>, Query, description = "Sort field (created_at, due_date, priority, completed_at, position)"),
+        ("order" = Option<String>, Query, description = "Sort order (default: asc for position, desc otherwise)"),
         ("page" = Option<i64>, Query, description = "Page number"),
         ("per_page" = Option<i64>, Query, description = "Items per page"),
     ),
@@ -72,6 +85,31 @@ pub async fn create(
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+/// 1行の入力からToDoを登録
+#[utoipa::path(
+    post,
+    path = "/api/todos/quick",
+    request_body = QuickAddTodoRequest,
+    responses(
+        (status = 201, description = "Todo created with the parsed fields", body = QuickAddTodoResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn quick_add(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<QuickAddTodoRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.todo_service.quick_add(claims.sub, req).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
 /// ToDoの詳細を取得
 #[utoipa::path(
     get,
@@ -79,6 +117,7 @@ pub async fn create(
     params(("id" = Uuid, Path, description = "Todo ID")),
     responses(
         (status = 200, description = "Todo detail", body = TodoResponse),
+        (status = 304, description = "Not modified (If-None-Match)"),
         (status = 404, description = "Not found"),
         (status = 401, description = "Unauthorized"),
     ),
@@ -89,9 +128,15 @@ pub async fn get_by_id(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
 ) -> AppResult<impl IntoResponse> {
     let response = state.todo_service.get_by_id(id, claims.sub).await?;
-    Ok(Json(response))
+    let etag = etag(response.version);
+
+    if if_none_match(&headers, response.version) {
+        return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response());
+    }
+    Ok(([(ETAG, etag)], Json(response)).into_response())
 }
 
 /// ToDoの更新
@@ -103,6 +148,8 @@ pub async fn get_by_id(
     responses(
         (status = 200, description = "Todo updated", body = TodoResponse),
         (status = 404, description = "Not found"),
+        (status = 409, description = "Blocked by incomplete todos or WIP limit of the column reached"),
+        (status = 412, description = "If-Match precondition failed"),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer_auth" = [])),
@@ -112,28 +159,91 @@ pub async fn update(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(req): Json<UpdateTodoRequest>,
 ) -> AppResult<impl IntoResponse> {
-    if let Some(ref title) = req.title {
-        if title.is_empty() || title.len() > 255 {
-            return Err(AppError::Validation(
-                "Title must be between 1 and 255 characters".into(),
-            ));
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let expected_versions = if_match_versions(&headers);
+    let response = state
+        .todo_service
+        .update(id, claims.sub, req, expected_versions.as_deref())
+        .await?;
+    Ok(([(ETAG, etag(response.version))], Json(response)))
+}
+
+/// ToDoの部分更新（JSON Merge Patch / JSON Patch）
+#[utoipa::path(
+    patch,
+    path = "/api/todos/{id}",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    request_body(
+        content = Object,
+        content_type = "application/merge-patch+json",
+        description = "RFC 7396 merge patch (`null` clears a field). RFC 6902 JSON Patch is accepted with `application/json-patch+json`."
+    ),
+    responses(
+        (status = 200, description = "Todo patched", body = TodoResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "JSON Patch test operation failed, blocked by incomplete todos or WIP limit of the column reached"),
+        (status = 412, description = "If-Match precondition failed"),
+        (status = 415, description = "Unsupported patch format"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn patch(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<impl IntoResponse> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let patch = match content_type.as_str() {
+        TodoPatch::JSON_PATCH_CONTENT_TYPE => TodoPatch::Json(
+            serde_json::from_slice(&body)
+                .map_err(|e| AppError::Validation(format!("Invalid JSON Patch: {}", e)))?,
+        ),
+        TodoPatch::MERGE_PATCH_CONTENT_TYPE | "application/json" => TodoPatch::Merge(
+            serde_json::from_slice(&body)
+                .map_err(|e| AppError::Validation(format!("Invalid merge patch: {}", e)))?,
+        ),
+        _ => {
+            return Err(AppError::UnsupportedMediaType(format!(
+                "Expected {} or {}",
+                TodoPatch::MERGE_PATCH_CONTENT_TYPE,
+                TodoPatch::JSON_PATCH_CONTENT_TYPE
+            )))
         }
-    }
+    };
 
-    let response = state.todo_service.update(id, claims.sub, req).await?;
-    Ok(Json(response))
+    let expected_versions = if_match_versions(&headers);
+    let response = state
+        .todo_service
+        .patch(id, claims.sub, patch, expected_versions.as_deref())
+        .await?;
+    Ok(([(ETAG, etag(response.version))], Json(response)))
 }
 
-/// ToDoを削除
+/// ToDoを削除（ゴミ箱へ移動）
 #[utoipa::path(
     delete,
     path = "/api/todos/{id}",
     params(("id" = Uuid, Path, description = "Todo ID")),
     responses(
-        (status = 204, description = "Todo deleted"),
+        (status = 204, description = "Todo moved to trash"),
         (status = 404, description = "Not found"),
+        (status = 412, description = "If-Match precondition failed"),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer_auth" = [])),
@@ -143,8 +253,13 @@ pub async fn delete(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
 ) -> AppResult<impl IntoResponse> {
-    state.todo_service.delete(id, claims.sub).await?;
+    let expected_versions = if_match_versions(&headers);
+    state
+        .todo_service
+        .delete(id, claims.sub, expected_versions.as_deref())
+        .await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -157,6 +272,8 @@ pub async fn delete(
     responses(
         (status = 200, description = "Status updated", body = TodoResponse),
         (status = 404, description = "Not found"),
+        (status = 409, description = "Blocked by incomplete todos or WIP limit of the column reached"),
+        (status = 412, description = "If-Match precondition failed"),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer_auth" = [])),
@@ -166,11 +283,263 @@ pub async fn update_status(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(req): Json<UpdateTodoStatusRequest>,
 ) -> AppResult<impl IntoResponse> {
+    let expected_versions = if_match_versions(&headers);
     let response = state
         .todo_service
-        .update_status(id, claims.sub, req)
+        .update_status(id, claims.sub, req, expected_versions.as_deref())
         .await?;
+    Ok(([(ETAG, etag(response.version))], Json(response)))
+}
+
+/// ToDoを並び替える
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/move",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    request_body = MoveTodoRequest,
+    responses(
+        (status = 200, description = "Todo moved", body = TodoResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Blocked by incomplete todos or WIP limit of the column reached"),
+        (status = 412, description = "If-Match precondition failed"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn move_todo(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<MoveTodoRequest>,
+) -> AppResult<impl IntoResponse> {
+    let expected_versions = if_match_versions(&headers);
+    let response = state
+        .todo_service
+        .move_todo(id, claims.sub, req, expected_versions.as_deref())
+        .await?;
+    Ok(([(ETAG, etag(response.version))], Json(response)))
+}
+
+/// 依存先（ブロッカー）の追加
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/blockers",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    request_body = AddBlockerRequest,
+    responses(
+        (status = 200, description = "Blocker added", body = TodoResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Dependency would create a cycle"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn add_blocker(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<AddBlockerRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.todo_service.add_blocker(id, claims.sub, req).await?;
+    Ok(Json(response))
+}
+
+/// 依存先（ブロッカー）の削除
+#[utoipa::path(
+    delete,
+    path = "/api/todos/{id}/blockers/{blocker_id}",
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("blocker_id" = Uuid, Path, description = "Blocker todo ID"),
+    ),
+    responses(
+        (status = 204, description = "Blocker removed"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn remove_blocker(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path((id, blocker_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<impl IntoResponse> {
+    state
+        .todo_service
+        .remove_blocker(id, claims.sub, blocker_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// ToDoの一括操作
+#[utoipa::path(
+    post,
+    path = "/api/todos/bulk",
+    request_body = BulkTodoRequest,
+    responses(
+        (status = 200, description = "Per-item results", body = BulkTodoResponse),
+        (status = 400, description = "Validation error"),
+        (status = 409, description = "Blocked by incomplete todos or WIP limit of the column reached"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn bulk(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<BulkTodoRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.todo_service.bulk(claims.sub, req).await?;
     Ok(Json(response))
 }
+
+/// ゴミ箱内のToDo一覧の取得
+#[utoipa::path(
+    get,
+    path = "/api/todos/trash",
+    params(
+        ("page" = Option<i64>, Query, description = "Page number"),
+        ("per_page" = Option<i64>, Query, description = "Items per page"),
+    ),
+    responses(
+        (status = 200, description = "Trashed todo list", body = TodoListResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn list_trash(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<TrashQuery>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.todo_service.list_trash(claims.sub, query).await?;
+    Ok(Json(response))
+}
+
+/// ゴミ箱からToDoを復元
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/restore",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "Todo restored", body = TodoResponse),
+        (status = 404, description = "Not found in trash"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn restore(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.todo_service.restore(id, claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// ゴミ箱内のToDoを完全に削除
+#[utoipa::path(
+    delete,
+    path = "/api/todos/trash/{id}",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 204, description = "Todo permanently deleted"),
+        (status = 404, description = "Not found in trash"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn purge(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    state.todo_service.purge(id, claims.sub).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// ゴミ箱を空にする
+#[utoipa::path(
+    delete,
+    path = "/api/todos/trash",
+    responses(
+        (status = 204, description = "Trash emptied"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn empty_trash(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    state.todo_service.empty_trash(claims.sub).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// ToDoの変更履歴を取得
+#[utoipa::path(
+    get,
+    path = "/api/todos/{id}/history",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "Todo history (newest first)", body = [TodoHistoryResponse]),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn history(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.todo_service.history(id, claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// ToDoを過去のバージョンに戻す
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/history/{version}/revert",
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("version" = i32, Path, description = "History version to revert to"),
+    ),
+    responses(
+        (status = 200, description = "Todo reverted", body = TodoResponse),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Blocked by incomplete todos or WIP limit of the column reached"),
+        (status = 412, description = "If-Match precondition failed"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn revert(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path((id, version)): Path<(Uuid, i32)>,
+    headers: HeaderMap,
+) -> AppResult<impl IntoResponse> {
+    let expected_versions = if_match_versions(&headers);
+    let response = state
+        .todo_service
+        .revert(id, claims.sub, version, expected_versions.as_deref())
+        .await?;
+    Ok(([(ETAG, etag(response.version))], Json(response)))
+}
@@ -0,0 +1,144 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::IntoResponse,
+    Extension, Json,
+};
+
+use crate::{
+    error::AppResult,
+    models::{
+        auth::Claims,
+        todo::{TodoQuery, TodoResponse},
+        transfer::{
+            ExportQuery, ExternalImportRequest, ExternalImportResponse, ImportSource,
+            ImportTodosRequest, ImportTodosResponse, TransferFormat,
+        },
+    },
+    AppState,
+};
+
+/// ToDoのエクスポート（一覧と同じ絞り込み条件に一致する全件）
+#[utoipa::path(
+    get,
+    path = "/api/todos/export",
+    params(
+        ("format" = Option<String>, Query, description = "csv, json or todotxt (default: json)"),
+        ("status" = Option<String>, Query, description = "Filter by status"),
+        ("priority" = Option<String>, Query, description = "Filter by priority"),
+        ("tag" = Option<String>, Query, description = "Filter by tag"),
+        ("q" = Option<String>, Query, description = "Search title and description (case-insensitive)"),
+        ("filter" = Option<String>, Query, description = "Filter expression"),
+        ("sort" = Option<String>, Query, description = "Sort field (created_at, due_date, priority, completed_at, position)"),
+        ("order" = Option<String>, Query, description = "Sort order (default: asc for position, desc otherwise)"),
+    ),
+    responses(
+        (status = 200, description = "Todos as CSV, a JSON array or todo.txt lines", body = Vec<TodoResponse>),
+        (status = 400, description = "Invalid filter"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn export(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(export): Query<ExportQuery>,
+    Query(query): Query<TodoQuery>,
+) -> AppResult<impl IntoResponse> {
+    let (content_type, file_name) = match export.format {
+        TransferFormat::Csv => ("text/csv; charset=utf-8", "todos.csv"),
+        TransferFormat::Json => ("application/json", "todos.json"),
+        TransferFormat::Todotxt => ("text/plain; charset=utf-8", "todo.txt"),
+    };
+    let stream = state
+        .transfer_service
+        .export(claims.sub, query, export.format)
+        .await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        Body::from_stream(stream),
+    ))
+}
+
+/// ToDoのインポート（CSV / JSON / todo.txt）
+/// 1行でもエラーがある場合は何も保存せず、422で行ごとのエラーを返す
+#[utoipa::path(
+    post,
+    path = "/api/todos/import",
+    request_body = ImportTodosRequest,
+    responses(
+        (status = 201, description = "Todos imported", body = ImportTodosResponse),
+        (status = 200, description = "Dry run result", body = ImportTodosResponse),
+        (status = 400, description = "Invalid file or mapping"),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Some rows are invalid (nothing was imported)", body = ImportTodosResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn import(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<ImportTodosRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.transfer_service.import(claims.sub, req).await?;
+
+    let status = if !response.errors.is_empty() && !response.dry_run {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else if response.imported > 0 {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(response)))
+}
+
+/// 他のツールからのインポート（Todoist / Taskwarrior / Microsoft To Do）
+/// 取り込まなかった・変換した項目は `notes` に記録し、エラーがある場合は何も保存しない
+#[utoipa::path(
+    post,
+    path = "/api/todos/import/{source}",
+    params(("source" = ImportSource, Path, description = "todoist, taskwarrior or microsoft-todo")),
+    request_body = ExternalImportRequest,
+    responses(
+        (status = 201, description = "Todos imported", body = ExternalImportResponse),
+        (status = 200, description = "Dry run result", body = ExternalImportResponse),
+        (status = 400, description = "Invalid file"),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Some tasks are invalid (nothing was imported)", body = ExternalImportResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "todos"
+)]
+pub async fn import_external(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(source): Path<ImportSource>,
+    Json(req): Json<ExternalImportRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = state
+        .transfer_service
+        .import_external(claims.sub, source, req)
+        .await?;
+
+    let status = if !response.errors.is_empty() && !response.dry_run {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else if response.imported > 0 {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(response)))
+}
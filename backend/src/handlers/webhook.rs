@@ -0,0 +1,186 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        auth::Claims,
+        webhook::{
+            CreatedWebhookResponse, WebhookDeliveryResponse, WebhookRequest, WebhookResponse,
+        },
+    },
+    AppState,
+};
+
+/// Webhook一覧の取得
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    responses(
+        (status = 200, description = "Webhooks", body = Vec<WebhookResponse>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.webhook_service.list(claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// Webhookの登録（署名の秘密鍵は登録時にのみ返す）
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    request_body = WebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created", body = CreatedWebhookResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<WebhookRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.webhook_service.create(claims.sub, req).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Webhook詳細の取得
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Webhook", body = WebhookResponse),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.webhook_service.get_by_id(id, claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// Webhookの更新（有効にすると連続失敗回数をリセットする）
+#[utoipa::path(
+    put,
+    path = "/api/webhooks/{id}",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    request_body = WebhookRequest,
+    responses(
+        (status = 200, description = "Webhook updated", body = WebhookResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<WebhookRequest>,
+) -> AppResult<impl IntoResponse> {
+    req.validate()
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let response = state.webhook_service.update(id, claims.sub, req).await?;
+    Ok(Json(response))
+}
+
+/// Webhookの削除
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    responses(
+        (status = 204, description = "Webhook deleted"),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    state.webhook_service.delete(id, claims.sub).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 配信ログの取得（新しい順に100件）
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}/deliveries",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Deliveries", body = Vec<WebhookDeliveryResponse>),
+        (status = 404, description = "Not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn deliveries(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let response = state.webhook_service.deliveries(id, claims.sub).await?;
+    Ok(Json(response))
+}
+
+/// 配信のやり直し（同じ内容を新しい配信として送信する）
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{id}/deliveries/{delivery_id}/redeliver",
+    params(
+        ("id" = Uuid, Path, description = "Webhook ID"),
+        ("delivery_id" = Uuid, Path, description = "Delivery ID"),
+    ),
+    responses(
+        (status = 202, description = "Redelivery queued", body = WebhookDeliveryResponse),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Webhook is disabled"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn redeliver(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<impl IntoResponse> {
+    let response = state
+        .webhook_service
+        .redeliver(id, claims.sub, delivery_id)
+        .await?;
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
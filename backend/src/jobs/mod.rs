@@ -0,0 +1,4 @@
+//! Background jobs
+pub mod rank_rebalance;
+pub mod trash_purge;
+pub mod webhook_delivery;
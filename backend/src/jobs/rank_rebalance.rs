@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::services::todo_service::TodoService;
+
+/// 長くなりすぎたランクキーを定期的に振り直すジョブを起動
+pub fn spawn(todo_service: TodoService, interval_minutes: u64) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_minutes.max(1) * 60));
+
+        loop {
+            interval.tick().await;
+
+            match todo_service.rebalance_positions().await {
+                Ok(0) => {}
+                Ok(users) => tracing::info!("Rebalanced todo positions for {} users", users),
+                Err(e) => tracing::error!("Failed to rebalance todo positions: {}", e),
+            }
+        }
+    })
+}
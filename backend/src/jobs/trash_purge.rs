@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::services::todo_service::TodoService;
+
+/// 保持期間を過ぎたゴミ箱内のToDoを定期的に完全削除するジョブを起動
+pub fn spawn(todo_service: TodoService, retention_days: i64, interval_minutes: u64) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_minutes.max(1) * 60));
+
+        loop {
+            interval.tick().await;
+
+            match todo_service.purge_expired_trash(retention_days).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!("Purged {} expired todos from trash", purged),
+                Err(e) => tracing::error!("Failed to purge expired trash: {}", e),
+            }
+        }
+    })
+}
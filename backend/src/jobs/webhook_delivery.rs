@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::services::webhook_service::WebhookService;
+
+/// ToDoの変更をWebhookの配信に登録し、送信・再試行するジョブを起動
+pub fn spawn(webhook_service: WebhookService, interval_seconds: u64) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds.max(1)));
+
+        loop {
+            interval.tick().await;
+
+            match webhook_service.process().await {
+                Ok(0) => {}
+                Ok(sent) => tracing::debug!("Sent {} webhook deliveries", sent),
+                Err(e) => tracing::error!("Failed to process webhook deliveries: {}", e),
+            }
+        }
+    })
+}
@@ -5,6 +5,7 @@
 pub mod config;
 pub mod error;
 pub mod handlers;
+pub mod jobs;
 pub mod middleware;
 pub mod models;
 pub mod repositories;
@@ -12,13 +13,41 @@ pub mod routes;
 pub mod services;
 
 use jsonwebtoken::DecodingKey;
+use services::agenda_service::AgendaService;
+use services::app_password_service::AppPasswordService;
 use services::auth_service::AuthService;
+use services::board_service::BoardService;
+use services::caldav_service::CaldavService;
+use services::calendar_service::CalendarService;
+use services::event_service::EventService;
+use services::profile_service::ProfileService;
+use services::saved_view_service::SavedViewService;
+use services::stats_service::StatsService;
+use services::sync_service::SyncService;
+use services::template_service::TemplateService;
+use services::time_entry_service::TimeEntryService;
 use services::todo_service::TodoService;
+use services::transfer_service::TransferService;
+use services::webhook_service::WebhookService;
 
 #[derive(Clone)]
 pub struct AppState {
     pub auth_service: AuthService,
+    pub profile_service: ProfileService,
     pub todo_service: TodoService,
+    pub board_service: BoardService,
+    pub time_entry_service: TimeEntryService,
+    pub template_service: TemplateService,
+    pub saved_view_service: SavedViewService,
+    pub agenda_service: AgendaService,
+    pub stats_service: StatsService,
+    pub calendar_service: CalendarService,
+    pub app_password_service: AppPasswordService,
+    pub caldav_service: CaldavService,
+    pub transfer_service: TransferService,
+    pub webhook_service: WebhookService,
+    pub event_service: EventService,
+    pub sync_service: SyncService,
     pub decoding_key: DecodingKey,
 }
 
@@ -26,11 +55,69 @@ pub struct AppState {
 pub fn build_app_state(pool: sqlx::PgPool, config: config::Config) -> AppState {
     let user_repo = repositories::user_repository::UserRepository::new(pool.clone());
     let token_repo = repositories::token_repository::TokenRepository::new(pool.clone());
-    let todo_repo = repositories::todo_repository::TodoRepository::new(pool);
+    let todo_repo = repositories::todo_repository::TodoRepository::new(pool.clone());
+    let todo_history_repo =
+        repositories::todo_history_repository::TodoHistoryRepository::new(pool.clone());
+    let board_column_repo =
+        repositories::board_column_repository::BoardColumnRepository::new(pool.clone());
+    let todo_dependency_repo =
+        repositories::todo_dependency_repository::TodoDependencyRepository::new(pool.clone());
+    let time_entry_repo =
+        repositories::time_entry_repository::TimeEntryRepository::new(pool.clone());
+    let template_repo = repositories::template_repository::TemplateRepository::new(pool.clone());
+    let saved_view_repo =
+        repositories::saved_view_repository::SavedViewRepository::new(pool.clone());
+    let stats_repo = repositories::stats_repository::StatsRepository::new(pool.clone());
+    let calendar_token_repo =
+        repositories::calendar_token_repository::CalendarTokenRepository::new(pool.clone());
+    let app_password_repo =
+        repositories::app_password_repository::AppPasswordRepository::new(pool.clone());
+    let caldav_object_repo =
+        repositories::caldav_object_repository::CaldavObjectRepository::new(pool.clone());
+    let webhook_repo = repositories::webhook_repository::WebhookRepository::new(pool.clone());
+    let sync_repo = repositories::sync_repository::SyncRepository::new(pool);
 
+    let profile_service = ProfileService::new(user_repo.clone());
+    let app_password_service = AppPasswordService::new(app_password_repo, user_repo.clone());
     let auth_service = AuthService::new(user_repo, token_repo, config.clone())
         .expect("Failed to init AuthService");
-    let todo_service = TodoService::new(todo_repo);
+    let event_service = EventService::new(todo_history_repo.clone());
+    let todo_service = TodoService::new(
+        todo_repo.clone(),
+        todo_history_repo.clone(),
+        todo_dependency_repo,
+        time_entry_repo.clone(),
+        profile_service.clone(),
+    );
+    let board_service = BoardService::new(board_column_repo, todo_repo.clone(), todo_service.clone());
+    let time_entry_service =
+        TimeEntryService::new(time_entry_repo, todo_repo.clone(), profile_service.clone());
+    let template_service = TemplateService::new(
+        template_repo,
+        todo_repo.clone(),
+        todo_service.clone(),
+        profile_service.clone(),
+    );
+    let saved_view_service =
+        SavedViewService::new(saved_view_repo, todo_service.clone(), profile_service.clone());
+    let agenda_service =
+        AgendaService::new(todo_repo.clone(), todo_service.clone(), profile_service.clone());
+    let stats_service = StatsService::new(stats_repo, profile_service.clone());
+    let calendar_service = CalendarService::new(calendar_token_repo, todo_repo.clone());
+    let caldav_service = CaldavService::new(
+        caldav_object_repo,
+        todo_repo.clone(),
+        todo_service.clone(),
+        profile_service.clone(),
+    );
+    let transfer_service =
+        TransferService::new(todo_repo, todo_service.clone(), profile_service.clone());
+    let webhook_service = WebhookService::new(
+        webhook_repo,
+        config.webhook_retry_base,
+        config.webhook_allow_private_urls,
+    );
+    let sync_service = SyncService::new(sync_repo, todo_history_repo, todo_service.clone());
 
     let public_key_data =
         std::fs::read(&config.jwt_public_key_path).expect("Failed to read public key");
@@ -38,21 +125,36 @@ pub fn build_app_state(pool: sqlx::PgPool, config: config::Config) -> AppState {
 
     AppState {
         auth_service,
+        profile_service,
         todo_service,
+        board_service,
+        time_entry_service,
+        template_service,
+        saved_view_service,
+        agenda_service,
+        stats_service,
+        calendar_service,
+        app_password_service,
+        caldav_service,
+        transfer_service,
+        webhook_service,
+        event_service,
+        sync_service,
         decoding_key,
     }
 }
 
 /// テスト・統合テスト用：Routerを構築
 pub fn build_router(state: AppState) -> axum::Router {
-    use axum::routing::get;
+    use axum::routing::{any, get};
     use axum::Router;
     use tower_http::cors::{Any, CorsLayer};
 
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([axum::http::header::ETAG]);
 
     Router::new()
         .route(
@@ -61,6 +163,18 @@ pub fn build_router(state: AppState) -> axum::Router {
         )
         .nest("/api/auth", routes::auth_routes(state.clone()))
         .nest("/api/todos", routes::todo_routes(state.clone()))
+        .nest("/api/board", routes::board_routes(state.clone()))
+        .nest("/api/time", routes::time_routes(state.clone()))
+        .nest("/api/templates", routes::template_routes(state.clone()))
+        .nest("/api/views", routes::view_routes(state.clone()))
+        .nest("/api/agenda", routes::agenda_routes(state.clone()))
+        .nest("/api/stats", routes::stats_routes(state.clone()))
+        .nest("/api/calendar", routes::calendar_routes(state.clone()))
+        .nest("/api/webhooks", routes::webhook_routes(state.clone()))
+        .nest("/api/events", routes::event_routes(state.clone()))
+        .nest("/api/sync", routes::sync_routes(state.clone()))
+        .route("/.well-known/caldav", any(handlers::caldav::well_known))
+        .merge(routes::caldav_routes(state.clone()))
         .with_state(state)
         .layer(cors)
 }
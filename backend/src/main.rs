@@ -1,4 +1,4 @@
-use axum::{routing::get, Json, Router};
+use axum::{routing::{any, get}, Json, Router};
 use jsonwebtoken::DecodingKey;
 use serde::Serialize;
 use sqlx::postgres::PgPoolOptions;
@@ -10,18 +10,62 @@ use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::config::Config;
-use crate::models::auth::{AuthResponse, LoginRequest, RegisterRequest, UserResponse};
-use crate::models::todo::{CreateTodoRequest, TodoListResponse, TodoPriority, TodoResponse, TodoStatus, UpdateTodoRequest, UpdateTodoStatusRequest};
+use crate::models::agenda::{AgendaBucket, AgendaResponse};
+use crate::models::app_password::{AppPasswordResponse, CreateAppPasswordRequest, CreatedAppPasswordResponse};
+use crate::models::auth::{AuthResponse, LoginRequest, MeResponse, RegisterRequest, UserResponse};
+use crate::models::board::{BoardColumnResponse, BoardColumnWithTodos, BoardResponse, CreateBoardColumnRequest, MoveBoardColumnRequest, UpdateBoardColumnRequest};
+use crate::models::calendar::{CalendarComponent, CalendarFeedResponse, CalendarTokenResponse};
+use crate::models::saved_view::{MoveSavedViewRequest, SavedViewFilters, SavedViewRequest, SavedViewResponse};
+use crate::models::stats::{CompletionStreak, PriorityCount, StatsInterval, StatsPeriod, StatsResponse, StatusCount};
+use crate::models::template::{InstantiateTemplateRequest, InstantiateTemplateResponse, TemplateRequest, TemplateResponse, TemplateSubtask};
+use crate::models::todo::{BulkTodoAction, BulkTodoRequest, BulkTodoResponse, BulkTodoResult, CreateTodoRequest, MoveTodoRequest, QuickAddParsed, QuickAddTodoRequest, QuickAddTodoResponse, TodoListResponse, TodoPriority, TodoQuery, TodoResponse, TodoStatus, UpdateTodoRequest, UpdateTodoStatusRequest};
+use crate::models::time_entry::{CreateTimeEntryRequest, TimeEntryResponse, TimeReportDay, TimeReportQuery, TimeReportResponse, TimeReportTag, TimeReportTodo};
+use crate::models::todo_dependency::AddBlockerRequest;
+use crate::models::transfer::{
+    ExternalImportRequest, ExternalImportResponse, ImportNote, ImportNoteKind, ImportRowError,
+    ImportSource, ImportTodosRequest, ImportTodosResponse, TransferFormat,
+};
+use crate::models::todo_history::{TodoHistoryAction, TodoHistoryResponse};
+use crate::models::sync::{SyncConflict, SyncDeletion, SyncMutation, SyncMutationResult, SyncMutationStatus, SyncOperation, SyncPushRequest, SyncPushResponse, SyncResponse};
+use crate::models::user::{ProfileResponse, UpdateProfileRequest, WeekDay};
+use crate::models::webhook::{CreatedWebhookResponse, WebhookDeliveryResponse, WebhookDeliveryStatus, WebhookEvent, WebhookRequest, WebhookResponse};
+use crate::repositories::app_password_repository::AppPasswordRepository;
+use crate::repositories::board_column_repository::BoardColumnRepository;
+use crate::repositories::caldav_object_repository::CaldavObjectRepository;
+use crate::repositories::calendar_token_repository::CalendarTokenRepository;
+use crate::repositories::saved_view_repository::SavedViewRepository;
+use crate::repositories::stats_repository::StatsRepository;
+use crate::repositories::template_repository::TemplateRepository;
+use crate::repositories::time_entry_repository::TimeEntryRepository;
+use crate::repositories::todo_dependency_repository::TodoDependencyRepository;
+use crate::repositories::todo_history_repository::TodoHistoryRepository;
 use crate::repositories::todo_repository::TodoRepository;
 use crate::repositories::token_repository::TokenRepository;
+use crate::repositories::sync_repository::SyncRepository;
 use crate::repositories::user_repository::UserRepository;
+use crate::repositories::webhook_repository::WebhookRepository;
+use crate::services::agenda_service::AgendaService;
+use crate::services::app_password_service::AppPasswordService;
 use crate::services::auth_service::AuthService;
+use crate::services::board_service::BoardService;
+use crate::services::caldav_service::CaldavService;
+use crate::services::calendar_service::CalendarService;
+use crate::services::event_service::EventService;
+use crate::services::profile_service::ProfileService;
+use crate::services::saved_view_service::SavedViewService;
+use crate::services::stats_service::StatsService;
+use crate::services::sync_service::SyncService;
+use crate::services::template_service::TemplateService;
+use crate::services::time_entry_service::TimeEntryService;
 use crate::error::ErrorResponse;
 use crate::services::todo_service::TodoService;
+use crate::services::transfer_service::TransferService;
+use crate::services::webhook_service::WebhookService;
 
 mod config;
 mod error;
 mod handlers;
+mod jobs;
 mod middleware;
 mod models;
 mod repositories;
@@ -31,7 +75,21 @@ mod services;
 #[derive(Clone)]
 pub struct AppState {
     pub auth_service: AuthService,
+    pub profile_service: ProfileService,
     pub todo_service: TodoService,
+    pub board_service: BoardService,
+    pub time_entry_service: TimeEntryService,
+    pub template_service: TemplateService,
+    pub saved_view_service: SavedViewService,
+    pub agenda_service: AgendaService,
+    pub stats_service: StatsService,
+    pub calendar_service: CalendarService,
+    pub app_password_service: AppPasswordService,
+    pub caldav_service: CaldavService,
+    pub transfer_service: TransferService,
+    pub webhook_service: WebhookService,
+    pub event_service: EventService,
+    pub sync_service: SyncService,
     pub decoding_key: DecodingKey,
 }
 
@@ -56,31 +114,179 @@ async fn health_check() -> Json<HealthResponse> {
         handlers::auth::refresh,
         handlers::auth::logout,
         handlers::auth::me,
+        handlers::auth::get_profile,
+        handlers::auth::update_profile,
+        handlers::auth::list_app_passwords,
+        handlers::auth::create_app_password,
+        handlers::auth::delete_app_password,
+        handlers::agenda::get_agenda,
+        handlers::stats::get_stats,
+        handlers::calendar::export,
+        handlers::transfer::export,
+        handlers::transfer::import,
+        handlers::transfer::import_external,
+        handlers::webhook::list,
+        handlers::webhook::create,
+        handlers::webhook::get_by_id,
+        handlers::webhook::update,
+        handlers::webhook::delete,
+        handlers::webhook::deliveries,
+        handlers::webhook::redeliver,
+        handlers::event::stream,
+        handlers::sync::pull,
+        handlers::sync::push,
+        handlers::calendar::feed,
+        handlers::calendar::get_token,
+        handlers::calendar::regenerate_token,
+        handlers::calendar::revoke_token,
         handlers::todo::list,
         handlers::todo::create,
+        handlers::todo::quick_add,
         handlers::todo::get_by_id,
         handlers::todo::update,
+        handlers::todo::patch,
         handlers::todo::delete,
         handlers::todo::update_status,
+        handlers::todo::move_todo,
+        handlers::todo::add_blocker,
+        handlers::todo::remove_blocker,
+        handlers::todo::bulk,
+        handlers::todo::list_trash,
+        handlers::todo::restore,
+        handlers::todo::purge,
+        handlers::todo::empty_trash,
+        handlers::todo::history,
+        handlers::todo::revert,
+        handlers::board::get_board,
+        handlers::board::list_columns,
+        handlers::board::create_column,
+        handlers::board::update_column,
+        handlers::board::move_column,
+        handlers::board::delete_column,
+        handlers::time_entry::start_timer,
+        handlers::time_entry::stop_timer,
+        handlers::time_entry::list,
+        handlers::time_entry::create,
+        handlers::time_entry::delete,
+        handlers::time_entry::report,
+        handlers::template::list,
+        handlers::template::create,
+        handlers::template::get_by_id,
+        handlers::template::update,
+        handlers::template::delete,
+        handlers::template::instantiate,
+        handlers::saved_view::list,
+        handlers::saved_view::create,
+        handlers::saved_view::get_by_id,
+        handlers::saved_view::update,
+        handlers::saved_view::move_view,
+        handlers::saved_view::delete,
+        handlers::saved_view::todos,
     ),
     components(schemas(
         RegisterRequest,
         LoginRequest,
         AuthResponse,
         UserResponse,
+        MeResponse,
+        ProfileResponse,
+        UpdateProfileRequest,
+        CreateAppPasswordRequest,
+        AppPasswordResponse,
+        CreatedAppPasswordResponse,
+        WeekDay,
+        AgendaBucket,
+        AgendaResponse,
+        StatsInterval,
+        StatusCount,
+        PriorityCount,
+        StatsPeriod,
+        CompletionStreak,
+        StatsResponse,
+        CalendarComponent,
+        TransferFormat,
+        ImportTodosRequest,
+        ImportRowError,
+        ImportTodosResponse,
+        ImportSource,
+        ExternalImportRequest,
+        ImportNoteKind,
+        ImportNote,
+        ExternalImportResponse,
+        CalendarFeedResponse,
+        CalendarTokenResponse,
         ErrorResponse,
         CreateTodoRequest,
+        QuickAddTodoRequest,
+        QuickAddParsed,
+        QuickAddTodoResponse,
         UpdateTodoRequest,
         UpdateTodoStatusRequest,
+        MoveTodoRequest,
+        AddBlockerRequest,
         TodoResponse,
         TodoListResponse,
         TodoStatus,
         TodoPriority,
+        TodoHistoryResponse,
+        TodoHistoryAction,
+        TodoQuery,
+        BulkTodoRequest,
+        BulkTodoAction,
+        BulkTodoResult,
+        BulkTodoResponse,
+        BoardResponse,
+        BoardColumnWithTodos,
+        BoardColumnResponse,
+        CreateBoardColumnRequest,
+        UpdateBoardColumnRequest,
+        MoveBoardColumnRequest,
+        CreateTimeEntryRequest,
+        TimeEntryResponse,
+        TimeReportQuery,
+        TimeReportResponse,
+        TimeReportDay,
+        TimeReportTodo,
+        TimeReportTag,
+        TemplateRequest,
+        TemplateSubtask,
+        TemplateResponse,
+        InstantiateTemplateRequest,
+        InstantiateTemplateResponse,
+        SavedViewFilters,
+        SavedViewRequest,
+        MoveSavedViewRequest,
+        SavedViewResponse,
+        WebhookEvent,
+        WebhookRequest,
+        WebhookResponse,
+        CreatedWebhookResponse,
+        WebhookDeliveryStatus,
+        WebhookDeliveryResponse,
+        SyncOperation,
+        SyncMutation,
+        SyncPushRequest,
+        SyncDeletion,
+        SyncResponse,
+        SyncConflict,
+        SyncMutationStatus,
+        SyncMutationResult,
+        SyncPushResponse,
     )),
     modifiers(&SecurityAddon),
     tags(
         (name = "auth", description = "Authentication API"),
-        (name = "todos", description = "ToDo CRUD API")
+        (name = "todos", description = "ToDo CRUD API"),
+        (name = "board", description = "Kanban board API"),
+        (name = "time", description = "Time tracking API"),
+        (name = "templates", description = "Todo template API"),
+        (name = "views", description = "Saved view API"),
+        (name = "agenda", description = "Agenda API"),
+        (name = "stats", description = "Statistics API"),
+        (name = "calendar", description = "iCalendar export and subscription API"),
+        (name = "webhooks", description = "Outgoing webhook API"),
+        (name = "events", description = "Real-time event stream (Server-Sent Events)"),
+        (name = "sync", description = "Offline delta sync API")
     )
 )]
 struct ApiDoc;
@@ -139,9 +345,72 @@ async fn main() {
     let user_repo = UserRepository::new(pool.clone());
     let token_repo = TokenRepository::new(pool.clone());
     let todo_repo = TodoRepository::new(pool.clone());
+    let todo_history_repo = TodoHistoryRepository::new(pool.clone());
+    let board_column_repo = BoardColumnRepository::new(pool.clone());
+    let todo_dependency_repo = TodoDependencyRepository::new(pool.clone());
+    let time_entry_repo = TimeEntryRepository::new(pool.clone());
+    let template_repo = TemplateRepository::new(pool.clone());
+    let saved_view_repo = SavedViewRepository::new(pool.clone());
+    let stats_repo = StatsRepository::new(pool.clone());
+    let calendar_token_repo = CalendarTokenRepository::new(pool.clone());
+    let app_password_repo = AppPasswordRepository::new(pool.clone());
+    let caldav_object_repo = CaldavObjectRepository::new(pool.clone());
+    let webhook_repo = WebhookRepository::new(pool.clone());
+    let sync_repo = SyncRepository::new(pool.clone());
+    let profile_service = ProfileService::new(user_repo.clone());
+    let app_password_service = AppPasswordService::new(app_password_repo, user_repo.clone());
     let auth_service =
         AuthService::new(user_repo, token_repo, config.clone()).expect("Failed to initialize AuthService");
-    let todo_service = TodoService::new(todo_repo);
+    let event_service = EventService::new(todo_history_repo.clone());
+    let todo_service = TodoService::new(
+        todo_repo.clone(),
+        todo_history_repo.clone(),
+        todo_dependency_repo,
+        time_entry_repo.clone(),
+        profile_service.clone(),
+    );
+    let board_service = BoardService::new(board_column_repo, todo_repo.clone(), todo_service.clone());
+    let time_entry_service =
+        TimeEntryService::new(time_entry_repo, todo_repo.clone(), profile_service.clone());
+    let template_service = TemplateService::new(
+        template_repo,
+        todo_repo.clone(),
+        todo_service.clone(),
+        profile_service.clone(),
+    );
+    let saved_view_service =
+        SavedViewService::new(saved_view_repo, todo_service.clone(), profile_service.clone());
+    let agenda_service =
+        AgendaService::new(todo_repo.clone(), todo_service.clone(), profile_service.clone());
+    let stats_service = StatsService::new(stats_repo, profile_service.clone());
+    let calendar_service = CalendarService::new(calendar_token_repo, todo_repo.clone());
+    let caldav_service = CaldavService::new(
+        caldav_object_repo,
+        todo_repo.clone(),
+        todo_service.clone(),
+        profile_service.clone(),
+    );
+    let transfer_service =
+        TransferService::new(todo_repo, todo_service.clone(), profile_service.clone());
+    let webhook_service = WebhookService::new(
+        webhook_repo,
+        config.webhook_retry_base,
+        config.webhook_allow_private_urls,
+    );
+    let sync_service = SyncService::new(sync_repo, todo_history_repo, todo_service.clone());
+
+    // ゴミ箱の定期削除ジョブを起動
+    jobs::trash_purge::spawn(
+        todo_service.clone(),
+        config.trash_retention_days,
+        config.trash_purge_interval,
+    );
+
+    // ランクキーの定期振り直しジョブを起動
+    jobs::rank_rebalance::spawn(todo_service.clone(), config.rank_rebalance_interval);
+
+    // Webhookの配信ジョブを起動
+    jobs::webhook_delivery::spawn(webhook_service.clone(), config.webhook_delivery_interval);
 
     // 公開鍵の読み込み（JWTの検証用）
     let public_key_data =
@@ -149,7 +418,21 @@ async fn main() {
     let decoding_key = DecodingKey::from_rsa_pem(&public_key_data).expect("Invalid public key");
     let state = AppState {
         auth_service,
+        profile_service,
         todo_service,
+        board_service,
+        time_entry_service,
+        template_service,
+        saved_view_service,
+        agenda_service,
+        stats_service,
+        calendar_service,
+        app_password_service,
+        caldav_service,
+        transfer_service,
+        webhook_service,
+        event_service,
+        sync_service,
         decoding_key,
     };
 
@@ -157,7 +440,8 @@ async fn main() {
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([axum::http::header::ETAG]);
 
     // Build router
     let app = Router::new()
@@ -165,6 +449,18 @@ async fn main() {
         .route("/health", get(health_check))
         .nest("/api/auth", routes::auth_routes(state.clone()))
         .nest("/api/todos", routes::todo_routes(state.clone()))
+        .nest("/api/board", routes::board_routes(state.clone()))
+        .nest("/api/time", routes::time_routes(state.clone()))
+        .nest("/api/templates", routes::template_routes(state.clone()))
+        .nest("/api/views", routes::view_routes(state.clone()))
+        .nest("/api/agenda", routes::agenda_routes(state.clone()))
+        .nest("/api/stats", routes::stats_routes(state.clone()))
+        .nest("/api/calendar", routes::calendar_routes(state.clone()))
+        .nest("/api/webhooks", routes::webhook_routes(state.clone()))
+        .nest("/api/events", routes::event_routes(state.clone()))
+        .nest("/api/sync", routes::sync_routes(state.clone()))
+        .route("/.well-known/caldav", any(handlers::caldav::well_known))
+        .merge(routes::caldav_routes(state.clone()))
         .with_state(state)
         .layer(TraceLayer::new_for_http())
         .layer(cors);
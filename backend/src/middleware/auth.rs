@@ -1,12 +1,20 @@
 use axum::{
     extract::{Request, State},
-    http::header::AUTHORIZATION,
+    http::{
+        header::{AUTHORIZATION, WWW_AUTHENTICATE},
+        StatusCode,
+    },
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use base64::{engine::general_purpose::STANDARD, Engine};
 use jsonwebtoken::{Algorithm, Validation, decode};
 
-use crate::{AppState, error::AppError, models::auth::Claims};
+use crate::{
+    AppState,
+    error::AppError,
+    models::{app_password::AppPasswordUser, auth::Claims},
+};
 
 pub async fn require_auth(
     State(state): State<AppState>,
@@ -37,3 +45,40 @@ pub async fn require_auth(
     
     Ok(next.run(req).await)
 }
+
+/// CalDAVクライアント用：メールアドレスとアプリパスワードによるBasic認証
+/// 失敗した場合はクライアントが認証情報を求められるよう `WWW-Authenticate` を返す
+pub async fn require_app_password(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let credentials = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Basic "))
+        .and_then(|v| STANDARD.decode(v.trim()).ok())
+        .and_then(|v| String::from_utf8(v).ok());
+    let Some((email, password)) = credentials.as_deref().and_then(|c| c.split_once(':')) else {
+        return Ok(unauthorized());
+    };
+
+    let user_id = match state.app_password_service.authenticate(email, password).await {
+        Ok(user_id) => user_id,
+        Err(AppError::Auth(_)) => return Ok(unauthorized()),
+        Err(e) => return Err(e),
+    };
+
+    req.extensions_mut().insert(AppPasswordUser { user_id });
+
+    Ok(next.run(req).await)
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(WWW_AUTHENTICATE, r#"Basic realm="todo-web", charset="UTF-8""#)],
+    )
+        .into_response()
+}
@@ -0,0 +1,32 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::todo::TodoResponse;
+
+// Response DTO
+
+/// アジェンダの区分ごとのToDo
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaBucket {
+    pub count: usize,
+    pub todos: Vec<TodoResponse>,
+}
+
+/// 未完了のToDoを日付で区分したアジェンダ
+/// 日付はプロフィールのタイムゾーンで判定し、期限のないToDoは開始日が未来の場合のみ開始日に表示する（スヌーズ）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaResponse {
+    /// 基準日（プロフィールのタイムゾーンでの今日）
+    pub date: NaiveDate,
+    pub time_zone: String,
+    /// 期限が今日より前
+    pub overdue: AgendaBucket,
+    pub today: AgendaBucket,
+    pub tomorrow: AgendaBucket,
+    /// 明後日から今週の終わり（プロフィールの週の始まりで判定）まで
+    pub this_week: AgendaBucket,
+    pub later: AgendaBucket,
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+// Entity
+
+#[derive(Debug, Clone, FromRow)]
+pub struct AppPassword {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub password_hash: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// アプリパスワードで認証したユーザー（Basic認証のミドルウェアが注入する）
+#[derive(Debug, Clone)]
+pub struct AppPasswordUser {
+    pub user_id: Uuid,
+}
+
+// Request DTO
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAppPasswordRequest {
+    /// 用途（例: `iPhone リマインダー`）
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+}
+
+// Response DTO
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppPasswordResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AppPassword> for AppPasswordResponse {
+    fn from(password: AppPassword) -> Self {
+        Self {
+            id: password.id,
+            name: password.name,
+            last_used_at: password.last_used_at,
+            created_at: password.created_at,
+        }
+    }
+}
+
+/// 発行したアプリパスワード（パスワードは発行時にのみ返す）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedAppPasswordResponse {
+    #[serde(flatten)]
+    pub app_password: AppPasswordResponse,
+    pub password: String,
+}
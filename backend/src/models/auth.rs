@@ -42,4 +42,14 @@ pub struct UserResponse {
     pub id: uuid::Uuid,
     pub email: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 現在のユーザー情報（プロフィールを含む）
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MeResponse {
+    pub id: uuid::Uuid,
+    pub email: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub profile: super::user::ProfileResponse,
 }
\ No newline at end of file
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::todo::{TodoResponse, TodoStatus};
+
+// Entity
+
+/// カンバンボードの列
+/// `category` はToDoのステータスに対応し、列へ移動したToDoのステータスになる
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BoardColumn {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub category: TodoStatus,
+    pub position: String,
+    pub wip_limit: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Request DTOs
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBoardColumnRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    pub category: TodoStatus,
+    #[validate(range(min = 1, message = "WIP limit must be at least 1"))]
+    pub wip_limit: Option<i32>,
+}
+
+/// 列の更新（`wipLimit` を省略または `null` にすると上限なし）
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBoardColumnRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(range(min = 1, message = "WIP limit must be at least 1"))]
+    pub wip_limit: Option<i32>,
+}
+
+/// 列の並び替え（`after` の直後かつ `before` の直前に移動する）
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveBoardColumnRequest {
+    pub before: Option<Uuid>,
+    pub after: Option<Uuid>,
+}
+
+// Response DTO
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardColumnResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub category: TodoStatus,
+    pub position: String,
+    pub wip_limit: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<BoardColumn> for BoardColumnResponse {
+    fn from(column: BoardColumn) -> Self {
+        Self {
+            id: column.id,
+            name: column.name,
+            category: column.category,
+            position: column.position,
+            wip_limit: column.wip_limit,
+            created_at: column.created_at,
+            updated_at: column.updated_at,
+        }
+    }
+}
+
+/// ボード上の列と、その列に表示するToDo（並び順）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardColumnWithTodos {
+    #[serde(flatten)]
+    pub column: BoardColumnResponse,
+    pub todos: Vec<TodoResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardResponse {
+    pub columns: Vec<BoardColumnWithTodos>,
+}
@@ -0,0 +1,41 @@
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+use super::todo::Todo;
+
+// Entity
+
+/// CalDAVクライアントが作成したリソースの名前とUID
+#[derive(Debug, Clone, FromRow)]
+pub struct CaldavObject {
+    pub todo_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub uid: String,
+}
+
+/// タスクコレクション内のリソース
+#[derive(Debug, Clone)]
+pub struct CaldavResource {
+    /// リソース名（`{id}.ics` またはクライアントが指定した名前）
+    pub name: String,
+    pub uid: String,
+    pub todo: Todo,
+}
+
+impl CaldavResource {
+    pub fn new(todo: Todo, object: Option<&CaldavObject>) -> Self {
+        match object {
+            Some(object) => Self {
+                name: object.name.clone(),
+                uid: object.uid.clone(),
+                todo,
+            },
+            None => Self {
+                name: format!("{}.ics", todo.id),
+                uid: format!("{}@todo-web", todo.id),
+                todo,
+            },
+        }
+    }
+}
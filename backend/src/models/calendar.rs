@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Enum
+
+/// 出力するiCalendarのコンポーネント
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum CalendarComponent {
+    /// タスク（リマインダー・タスクアプリ向け）
+    Vtodo,
+    /// 期限日時の予定（カレンダーアプリ向け）
+    Vevent,
+}
+
+// Entity
+
+#[derive(Debug, Clone, FromRow)]
+pub struct CalendarToken {
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Query DTO
+
+/// 省略時はVTODOとVEVENTの両方を出力する
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarExportQuery {
+    pub component: Option<CalendarComponent>,
+}
+
+impl CalendarExportQuery {
+    pub fn components(&self) -> Vec<CalendarComponent> {
+        match self.component {
+            Some(component) => vec![component],
+            None => vec![CalendarComponent::Vtodo, CalendarComponent::Vevent],
+        }
+    }
+}
+
+// Response DTO
+
+/// 購読URLの発行状況（トークン自体は発行時にのみ返す）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarFeedResponse {
+    pub enabled: bool,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<Option<CalendarToken>> for CalendarFeedResponse {
+    fn from(token: Option<CalendarToken>) -> Self {
+        Self {
+            enabled: token.is_some(),
+            created_at: token.map(|t| t.created_at),
+        }
+    }
+}
+
+/// 発行した購読トークンとURL（APIのオリジンからの相対パス）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarTokenResponse {
+    pub token: String,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+}
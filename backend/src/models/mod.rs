@@ -1,5 +1,19 @@
 //! Domain models
+pub mod agenda;
+pub mod app_password;
 pub mod auth;
+pub mod board;
+pub mod caldav;
+pub mod calendar;
+pub mod saved_view;
+pub mod stats;
+pub mod sync;
+pub mod template;
+pub mod time_entry;
 pub mod todo;
+pub mod todo_dependency;
+pub mod todo_history;
 pub mod token;
-pub mod user;
\ No newline at end of file
+pub mod transfer;
+pub mod user;
+pub mod webhook;
\ No newline at end of file
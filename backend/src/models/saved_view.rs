@@ -0,0 +1,216 @@
+use chrono::{DateTime, Duration, Months, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use sqlx::{prelude::FromRow, types::Json};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use super::todo::{TodoPriority, TodoQuery, TodoStatus};
+
+// Entity
+
+#[derive(Debug, Clone, FromRow)]
+pub struct SavedView {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub filters: Json<SavedViewFilters>,
+    pub position: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 保存する絞り込み条件（`GET /api/todos` のクエリと同じ項目）
+/// 日付は日時・日付のほか `today` / `tomorrow` / `yesterday` / `+7d` / `-2w` / `+1m` を指定でき、実行時に解決する
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedViewFilters {
+    pub status: Option<TodoStatus>,
+    pub priority: Option<TodoPriority>,
+    #[validate(custom(function = "validate_date_token"))]
+    pub due_before: Option<String>,
+    #[validate(custom(function = "validate_date_token"))]
+    pub due_after: Option<String>,
+    #[validate(custom(function = "validate_date_token"))]
+    pub completed_before: Option<String>,
+    #[validate(custom(function = "validate_date_token"))]
+    pub completed_after: Option<String>,
+    pub tag: Option<String>,
+    #[validate(length(max = 255, message = "Search text must be at most 255 characters"))]
+    pub q: Option<String>,
+    /// 絞り込み式（相対的な日付は実行時に解決）
+    pub filter: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+}
+
+impl SavedViewFilters {
+    /// 基準日時で相対的な日付を解決し、一覧取得のクエリに変換する
+    /// `*Before` はその日の終わり、`*After` はその日の始まりとして扱う
+    pub fn to_query(&self, now: DateTime<Tz>, page: i64, per_page: i64) -> TodoQuery {
+        let before = |token: &Option<String>| {
+            token.as_deref().and_then(|t| resolve_date(t, &now, true))
+        };
+        let after = |token: &Option<String>| {
+            token.as_deref().and_then(|t| resolve_date(t, &now, false))
+        };
+
+        TodoQuery {
+            status: self.status.clone(),
+            priority: self.priority.clone(),
+            due_before: before(&self.due_before),
+            due_after: after(&self.due_after),
+            completed_before: before(&self.completed_before),
+            completed_after: after(&self.completed_after),
+            tag: self.tag.clone(),
+            q: self.q.clone(),
+            filter: self.filter.clone(),
+            sort: self.sort.clone().unwrap_or_else(|| "created_at".to_string()),
+            order: self.order.clone(),
+            page,
+            per_page,
+        }
+    }
+}
+
+// Request DTOs
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedViewRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[serde(default)]
+    #[validate(nested)]
+    pub filters: SavedViewFilters,
+}
+
+/// ビューの並び替え（`after` の直後かつ `before` の直前に移動する）
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveSavedViewRequest {
+    pub before: Option<Uuid>,
+    pub after: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedViewTodosQuery {
+    #[serde(default = "default_page")]
+    pub page: i64,
+    #[serde(default = "default_per_page")]
+    pub per_page: i64,
+}
+
+fn default_page() -> i64 {
+    1
+}
+fn default_per_page() -> i64 {
+    20
+}
+
+// Response DTO
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedViewResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub filters: SavedViewFilters,
+    pub position: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<SavedView> for SavedViewResponse {
+    fn from(view: SavedView) -> Self {
+        Self {
+            id: view.id,
+            name: view.name,
+            filters: view.filters.0,
+            position: view.position,
+            created_at: view.created_at,
+            updated_at: view.updated_at,
+        }
+    }
+}
+
+// Date token
+
+fn validate_date_token(token: &str) -> Result<(), ValidationError> {
+    if resolve_date(token, &Utc::now().with_timezone(&Tz::UTC), false).is_none() {
+        return Err(ValidationError::new("date").with_message(
+            format!(
+                "Invalid date '{}': use a date, a datetime, today, tomorrow, yesterday or an offset like +7d",
+                token
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// 日付の指定を基準日時のタイムゾーンで解決する
+/// 日時はそのまま、日付と相対指定は `end_of_day` に応じてその日の始まりか終わりを返す
+pub fn resolve_date(token: &str, now: &DateTime<Tz>, end_of_day: bool) -> Option<DateTime<Utc>> {
+    let token = token.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(token) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+
+    let today = now.date_naive();
+    let date = match token {
+        "today" => Some(today),
+        "tomorrow" => today.succ_opt(),
+        "yesterday" => today.pred_opt(),
+        _ => NaiveDate::parse_from_str(token, "%Y-%m-%d")
+            .ok()
+            .or_else(|| offset_date(token, today)),
+    }?;
+
+    let tz = now.timezone();
+    if end_of_day {
+        let next = start_of_day(date.succ_opt()?, &tz)?;
+        Some(next - Duration::microseconds(1))
+    } else {
+        start_of_day(date, &tz)
+    }
+}
+
+/// タイムゾーンでの日付の始まり（0時が存在しない場合はその日の最初の時刻）
+pub fn start_of_day(date: NaiveDate, tz: &Tz) -> Option<DateTime<Utc>> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    (0..=2)
+        .find_map(|hour| {
+            (midnight + Duration::hours(hour))
+                .and_local_timezone(*tz)
+                .earliest()
+        })
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+/// `+7d` / `-2w` / `+1m` 形式の相対指定を解決する
+fn offset_date(token: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (sign, rest) = match token.chars().next()? {
+        '+' => (1, &token[1..]),
+        '-' => (-1, &token[1..]),
+        _ => return None,
+    };
+    let unit = rest.chars().last()?;
+    let amount: u32 = rest[..rest.len() - unit.len_utf8()].parse().ok()?;
+
+    match unit {
+        'd' | 'w' => {
+            let days = i64::from(amount) * if unit == 'w' { 7 } else { 1 };
+            today.checked_add_signed(Duration::days(sign * days))
+        }
+        'm' if sign > 0 => today.checked_add_months(Months::new(amount)),
+        'm' => today.checked_sub_months(Months::new(amount)),
+        _ => None,
+    }
+}
@@ -0,0 +1,81 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+
+use super::todo::{TodoPriority, TodoStatus};
+
+// Query DTO
+
+/// 推移の集計単位
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum StatsInterval {
+    #[default]
+    Day,
+    /// プロフィールの週の始まりで区切る
+    Week,
+}
+
+/// 推移の集計期間（両端の日付を含む、プロフィールのタイムゾーン）
+/// 省略時は今日までの30日間
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    #[serde(default)]
+    pub interval: StatsInterval,
+}
+
+// Response DTO
+
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusCount {
+    pub status: TodoStatus,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityCount {
+    pub priority: TodoPriority,
+    pub count: i64,
+}
+
+/// 期間ごとの作成数・完了数（`date` は期間の初日）
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsPeriod {
+    pub date: NaiveDate,
+    pub created: i64,
+    pub completed: i64,
+}
+
+/// 1件以上完了した日の連続日数
+/// 今日まだ完了していなくても昨日まで続いていれば継続中とする
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionStreak {
+    pub current_days: i64,
+    pub longest_days: i64,
+}
+
+/// ToDoの統計（件数はゴミ箱のToDoを除く）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub interval: StatsInterval,
+    pub time_zone: String,
+    pub by_status: Vec<StatusCount>,
+    pub by_priority: Vec<PriorityCount>,
+    /// 期限を過ぎた未完了のToDoの件数
+    pub overdue: i64,
+    pub series: Vec<StatsPeriod>,
+    /// 期間内に完了したToDoの作成から完了までの平均時間（秒）
+    pub average_lead_time_seconds: Option<i64>,
+    pub streak: CompletionStreak,
+}
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::todo::{Todo, TodoResponse};
+
+// Enum
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncOperation {
+    Create,
+    Update,
+    Delete,
+}
+
+/// 変更の適用結果
+#[derive(Debug, Clone, Copy, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncMutationStatus {
+    /// すべて適用した（作成・削除の再送も含む）
+    Applied,
+    /// サーバー側の変更と競合した（競合したフィールドはサーバーの値を優先する）
+    Conflict,
+    /// 検証エラーなどで適用できなかった
+    Rejected,
+}
+
+// Entity
+
+/// 同期カーソル付きのToDo
+#[derive(Debug, Clone, FromRow)]
+pub struct SyncTodo {
+    #[sqlx(flatten)]
+    pub todo: Todo,
+    pub sync_seq: i64,
+}
+
+/// 完全に削除したToDoの記録
+#[derive(Debug, Clone, FromRow)]
+pub struct TodoTombstone {
+    pub todo_id: Uuid,
+    pub user_id: Uuid,
+    pub sync_seq: i64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+// Query DTO
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncQuery {
+    /// 前回の同期で受け取ったカーソル（省略時は全件）
+    pub since: Option<String>,
+    pub limit: Option<i64>,
+}
+
+// Request DTO
+
+/// クライアントでの変更
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncMutation {
+    /// クライアントが結果と対応付けるためのID（そのまま返す）
+    pub mutation_id: Option<String>,
+    pub op: SyncOperation,
+    /// ToDoのID（作成時はクライアントで生成する）
+    pub id: Uuid,
+    /// 変更の元にしたバージョン（更新・削除では必須）
+    pub base_version: Option<i32>,
+    /// 作成・更新する項目（更新では変更した項目のみ、`null` で削除）
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub fields: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPushRequest {
+    /// 前回の同期で受け取ったカーソル（省略時は全件を返す）
+    pub cursor: Option<String>,
+    pub mutations: Vec<SyncMutation>,
+}
+
+// Response DTO
+
+/// 削除されたToDo（ゴミ箱への移動を含む）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDeletion {
+    pub id: Uuid,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// カーソル以降の変更
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncResponse {
+    /// 次回の同期に使うカーソル
+    pub cursor: String,
+    /// 続きがある場合は `true`（`cursor` を指定して再取得する）
+    pub has_more: bool,
+    /// 作成・更新されたToDo
+    pub todos: Vec<TodoResponse>,
+    pub deleted: Vec<SyncDeletion>,
+}
+
+/// フィールド単位の競合
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    pub field: String,
+    pub client_value: Value,
+    pub server_value: Value,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncMutationResult {
+    pub mutation_id: Option<String>,
+    pub id: Uuid,
+    pub op: SyncOperation,
+    pub status: SyncMutationStatus,
+    /// 適用後のサーバーのToDo（削除済みの場合は `null`）
+    pub todo: Option<TodoResponse>,
+    /// サーバーの値を優先したフィールド（`field` が `deletedAt` の場合はサーバーで削除済み）
+    pub conflicts: Vec<SyncConflict>,
+    pub error: Option<String>,
+}
+
+/// 変更の送信結果（カーソル以降の変更を含む）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPushResponse {
+    #[serde(flatten)]
+    pub changes: SyncResponse,
+    pub results: Vec<SyncMutationResult>,
+}
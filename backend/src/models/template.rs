@@ -0,0 +1,141 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{prelude::FromRow, types::Json};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::todo::{validate_tags, TodoPriority, TodoResponse};
+
+// Entity
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TodoTemplate {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: TodoPriority,
+    pub due_offset_days: Option<i32>,
+    pub tags: Vec<String>,
+    pub subtasks: Json<Vec<TemplateSubtask>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// テンプレートのサブタスク（作成時は親ToDoのサブタスクになる）
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateSubtask {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Title must be between 1 and 255 characters"
+    ))]
+    pub title: String,
+    pub description: Option<String>,
+    /// 作成日からの期限（日数）
+    #[validate(range(
+        min = -3650,
+        max = 3650,
+        message = "Due offset must be between -3650 and 3650 days"
+    ))]
+    pub due_offset_days: Option<i32>,
+}
+
+// Request DTOs
+
+/// テンプレートの作成・更新
+/// タイトルと説明には `{{date}}` などのプレースホルダーを使用できる
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Title must be between 1 and 255 characters"
+    ))]
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<TodoPriority>,
+    /// 作成日からの期限（日数）
+    #[validate(range(
+        min = -3650,
+        max = 3650,
+        message = "Due offset must be between -3650 and 3650 days"
+    ))]
+    pub due_offset_days: Option<i32>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(length(max = 100, message = "At most 100 subtasks are allowed"), nested)]
+    pub subtasks: Option<Vec<TemplateSubtask>>,
+}
+
+/// テンプレートからToDoを作成
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateRequest {
+    /// 基準日（省略時は当日）。期限とプレースホルダーの計算に使用する
+    pub date: Option<NaiveDate>,
+}
+
+// Response DTO
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: TodoPriority,
+    pub due_offset_days: Option<i32>,
+    pub tags: Vec<String>,
+    pub subtasks: Vec<TemplateSubtask>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<TodoTemplate> for TemplateResponse {
+    fn from(template: TodoTemplate) -> Self {
+        Self {
+            id: template.id,
+            name: template.name,
+            title: template.title,
+            description: template.description,
+            priority: template.priority,
+            due_offset_days: template.due_offset_days,
+            tags: template.tags,
+            subtasks: template.subtasks.0,
+            created_at: template.created_at,
+            updated_at: template.updated_at,
+        }
+    }
+}
+
+/// テンプレートから作成したToDo（先頭が親ToDo、以降がサブタスク）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateResponse {
+    pub todos: Vec<TodoResponse>,
+}
+
+// Placeholder
+
+/// プレースホルダーを基準日の値で置換する
+/// - `{{date}}`: 2026-03-16
+/// - `{{week}}`: 2026-W12（ISO週番号）
+/// - `{{month}}`: 2026-03
+pub fn render_placeholders(text: &str, date: NaiveDate) -> String {
+    let week = date.iso_week();
+    text.replace("{{date}}", &date.format("%Y-%m-%d").to_string())
+        .replace("{{week}}", &format!("{}-W{:02}", week.year(), week.week()))
+        .replace("{{month}}", &date.format("%Y-%m").to_string())
+}
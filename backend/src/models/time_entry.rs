@@ -0,0 +1,106 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+// Entity
+
+/// 作業時間の記録（`ended_at` が `None` の場合は計測中）
+#[derive(Debug, Clone, FromRow)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    pub user_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Request DTOs
+
+/// 作業時間の手動登録
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTimeEntryRequest {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    #[validate(length(max = 1000, message = "Note must be at most 1000 characters"))]
+    pub note: Option<String>,
+}
+
+// Query DTO
+
+/// 集計期間（両端の日付を含む、プロフィールのタイムゾーン）
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeReportQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+// Response DTO
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntryResponse {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    /// 作業時間（秒、計測中の場合は現在までの時間）
+    pub duration_seconds: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TimeEntry> for TimeEntryResponse {
+    fn from(entry: TimeEntry) -> Self {
+        let ended_at = entry.ended_at.unwrap_or_else(Utc::now);
+        Self {
+            id: entry.id,
+            todo_id: entry.todo_id,
+            started_at: entry.started_at,
+            ended_at: entry.ended_at,
+            note: entry.note,
+            duration_seconds: (ended_at - entry.started_at).num_seconds(),
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeReportDay {
+    pub date: NaiveDate,
+    pub seconds: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeReportTodo {
+    pub todo_id: Uuid,
+    pub title: String,
+    pub seconds: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeReportTag {
+    pub tag: String,
+    pub seconds: i64,
+}
+
+/// 作業時間レポート（日別・ToDo別・タグ別）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeReportResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total_seconds: i64,
+    pub by_day: Vec<TimeReportDay>,
+    pub by_todo: Vec<TimeReportTodo>,
+    pub by_tag: Vec<TimeReportTag>,
+}
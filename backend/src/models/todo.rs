@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
 use utoipa::ToSchema;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+use super::todo_dependency::TodoDependency;
 
 // Enum
 
@@ -38,6 +40,15 @@ pub struct Todo {
     pub priority: TodoPriority,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i32,
+    pub tags: Vec<String>,
+    pub position: String,
+    pub column_id: Option<Uuid>,
+    pub estimate_minutes: Option<i32>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub parent_id: Option<Uuid>,
 }
 
 // Request DTOs
@@ -52,9 +63,28 @@ pub struct CreateTodoRequest {
     ))]
     pub title: String,
     pub description: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
     pub due_date: Option<DateTime<Utc>>,
     pub status: Option<TodoStatus>,
     pub priority: Option<TodoPriority>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(range(min = 1, message = "Estimate must be at least 1 minute"))]
+    pub estimate_minutes: Option<i32>,
+}
+
+/// 1行の入力からToDoを作成（例: `Pay rent tomorrow 9am !high #finance`, `来週金曜 歯医者 #健康`）
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddTodoRequest {
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "Text must be between 1 and 500 characters"
+    ))]
+    pub text: String,
+    /// 日付・時刻の解釈に使うタイムゾーン（IANA名、省略時はプロフィールのタイムゾーン）
+    pub time_zone: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
@@ -67,30 +97,150 @@ pub struct UpdateTodoRequest {
     ))]
     pub title: Option<String>,
     pub description: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
     pub due_date: Option<DateTime<Utc>>,
     pub status: Option<TodoStatus>,
     pub priority: Option<TodoPriority>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(range(min = 1, message = "Estimate must be at least 1 minute"))]
+    pub estimate_minutes: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTodoStatusRequest {
     pub status: TodoStatus,
+    /// 未完了の依存先があっても着手・完了にする
+    #[serde(default)]
+    pub force: bool,
 }
 
-// Query DTO
+/// 並び替えリクエスト
+/// `after` の直後かつ `before` の直前に移動する（どちらか一方のみでも可）
+/// `columnId` を指定するとボードの列へ移動し、ステータスは列のカテゴリになる
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveTodoRequest {
+    pub before: Option<Uuid>,
+    pub after: Option<Uuid>,
+    pub column_id: Option<Uuid>,
+}
+
+/// PATCH リクエストの内容
+/// 適用後のドキュメントは `CreateTodoRequest` と同じルールで検証する
+#[derive(Debug)]
+pub enum TodoPatch {
+    /// RFC 7396 JSON Merge Patch（`null` でフィールドを削除）
+    Merge(serde_json::Value),
+    /// RFC 6902 JSON Patch
+    Json(json_patch::Patch),
+}
 
+impl TodoPatch {
+    pub const MERGE_PATCH_CONTENT_TYPE: &'static str = "application/merge-patch+json";
+    pub const JSON_PATCH_CONTENT_TYPE: &'static str = "application/json-patch+json";
+
+    /// パッチで編集可能なフィールド
+    pub const FIELDS: [&'static str; 8] = [
+        "title",
+        "description",
+        "startDate",
+        "dueDate",
+        "status",
+        "priority",
+        "tags",
+        "estimateMinutes",
+    ];
+}
+
+/// 一括操作の内容
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum BulkTodoAction {
+    SetStatus { status: TodoStatus },
+    SetPriority { priority: TodoPriority },
+    /// `null` で期限を削除
+    SetDueDate { due_date: Option<DateTime<Utc>> },
+    AddTag { tag: String },
+    RemoveTag { tag: String },
+    Delete,
+}
+
+/// 一括操作リクエスト
+/// 対象は `ids` か `filter`（一覧取得と同じ条件）のどちらか一方で指定する
 #[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
+pub struct BulkTodoRequest {
+    pub ids: Option<Vec<Uuid>>,
+    pub filter: Option<TodoQuery>,
+    pub action: BulkTodoAction,
+}
+
+// Tag
+
+pub const MAX_TAGS: usize = 20;
+pub const MAX_TAG_LENGTH: usize = 50;
+
+pub fn validate_tags(tags: &[String]) -> Result<(), ValidationError> {
+    if tags.len() > MAX_TAGS {
+        return Err(ValidationError::new("tags")
+            .with_message(format!("At most {} tags are allowed", MAX_TAGS).into()));
+    }
+    if tags.iter().any(|t| !is_valid_tag(t)) {
+        return Err(ValidationError::new("tags").with_message(
+            format!("Tags must be between 1 and {} characters", MAX_TAG_LENGTH).into(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn is_valid_tag(tag: &str) -> bool {
+    let len = tag.trim().chars().count();
+    (1..=MAX_TAG_LENGTH).contains(&len)
+}
+
+/// タグの前後の空白を除去し、重複を取り除く（順序は維持）
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+// Query DTO
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct TodoQuery {
     pub status: Option<TodoStatus>,
     pub priority: Option<TodoPriority>,
     pub due_before: Option<DateTime<Utc>>,
     pub due_after: Option<DateTime<Utc>>,
+    pub completed_before: Option<DateTime<Utc>>,
+    pub completed_after: Option<DateTime<Utc>>,
+    pub tag: Option<String>,
+    /// タイトル・説明の部分一致検索（大文字小文字を区別しない）
+    pub q: Option<String>,
+    /// 絞り込み式（例: `priority:high and (status:pending or due:<today) and not tag:someday`）
+    pub filter: Option<String>,
     #[serde(default = "default_sort")]
     pub sort: String,
-    #[serde(default = "default_order")]
-    pub order: String,
+    /// 省略時は `sort=position` なら昇順、それ以外は降順
+    pub order: Option<String>,
+    #[serde(default = "default_page")]
+    pub page: i64,
+    #[serde(default = "default_per_page")]
+    pub per_page: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashQuery {
     #[serde(default = "default_page")]
     pub page: i64,
     #[serde(default = "default_per_page")]
@@ -100,9 +250,6 @@ pub struct TodoQuery {
 fn default_sort() -> String {
     "created_at".to_string()
 }
-fn default_order() -> String {
-    "desc".to_string()
-}
 fn default_page() -> i64 {
     1
 }
@@ -118,11 +265,48 @@ pub struct TodoResponse {
     pub id: Uuid,
     pub title: String,
     pub description: Option<String>,
+    /// 開始日（未来の日時にするとアジェンダではスヌーズとして扱う）
+    pub start_date: Option<DateTime<Utc>>,
     pub due_date: Option<DateTime<Utc>>,
     pub status: TodoStatus,
     pub priority: TodoPriority,
+    /// 完了日時（完了にした時点でサーバーが設定し、未完了に戻すと `null`）
+    pub completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i32,
+    pub tags: Vec<String>,
+    pub position: String,
+    /// ボードの列（`null` の場合はステータスに対応する先頭の列）
+    pub column_id: Option<Uuid>,
+    /// 親ToDo（サブタスクの場合）
+    pub parent_id: Option<Uuid>,
+    /// このToDoの依存先（完了するまで着手できない）
+    pub blocked_by: Vec<Uuid>,
+    /// このToDoに依存しているToDo
+    pub blocking: Vec<Uuid>,
+    pub estimate_minutes: Option<i32>,
+    /// 記録済みの作業時間（秒、計測中のタイマーを含む）
+    pub tracked_seconds: i64,
+}
+
+/// クイック追加の解析結果
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddParsed {
+    pub title: String,
+    pub due_date: Option<DateTime<Utc>>,
+    pub priority: Option<TodoPriority>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddTodoResponse {
+    pub parsed: QuickAddParsed,
+    pub todo: TodoResponse,
 }
 
 impl From<Todo> for TodoResponse {
@@ -131,15 +315,42 @@ impl From<Todo> for TodoResponse {
             id: todo.id,
             title: todo.title,
             description: todo.description,
+            start_date: todo.start_date,
             due_date: todo.due_date,
             status: todo.status,
             priority: todo.priority,
+            completed_at: todo.completed_at,
             created_at: todo.created_at,
             updated_at: todo.updated_at,
+            deleted_at: todo.deleted_at,
+            version: todo.version,
+            tags: todo.tags,
+            position: todo.position,
+            column_id: todo.column_id,
+            parent_id: todo.parent_id,
+            blocked_by: Vec::new(),
+            blocking: Vec::new(),
+            estimate_minutes: todo.estimate_minutes,
+            tracked_seconds: 0,
         }
     }
 }
 
+impl TodoResponse {
+    /// 依存関係の一覧から `blockedBy` / `blocking` を設定
+    pub fn with_dependencies(mut self, dependencies: &[TodoDependency]) -> Self {
+        for dependency in dependencies {
+            if dependency.todo_id == self.id {
+                self.blocked_by.push(dependency.blocker_id);
+            }
+            if dependency.blocker_id == self.id {
+                self.blocking.push(dependency.todo_id);
+            }
+        }
+        self
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TodoListResponse {
@@ -148,3 +359,22 @@ pub struct TodoListResponse {
     pub page: i64,
     pub per_page: i64,
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTodoResult {
+    pub id: Uuid,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub todo: Option<TodoResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTodoResponse {
+    pub results: Vec<BulkTodoResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Entity
+
+/// ToDoの依存関係（`todo_id` は `blocker_id` が完了するまで着手できない）
+#[derive(Debug, Clone, FromRow)]
+pub struct TodoDependency {
+    pub todo_id: Uuid,
+    pub blocker_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+// Request DTO
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AddBlockerRequest {
+    pub blocker_id: Uuid,
+}
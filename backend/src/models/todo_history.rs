@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    todo::{Todo, TodoPriority, TodoStatus},
+    webhook::WebhookEvent,
+};
+
+// Enum
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "todo_history_action", rename_all = "snake_case")]
+#[serde(rename_all = "camelCase")]
+pub enum TodoHistoryAction {
+    Created,
+    Updated,
+    StatusChanged,
+    Deleted,
+    Restored,
+    Reverted,
+}
+
+// Entity
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TodoHistory {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub version: i32,
+    pub action: TodoHistoryAction,
+    pub changes: Value,
+    pub snapshot: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// ToDoの変更イベント（履歴とToDoの所有者）
+/// Webhookの配信とリアルタイム通知で使用する
+#[derive(Debug, Clone, FromRow)]
+pub struct TodoEvent {
+    /// 履歴の通し番号（SSEのイベントID）
+    pub seq: i64,
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    pub user_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub version: i32,
+    pub action: TodoHistoryAction,
+    pub changes: Value,
+    pub snapshot: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TodoEvent {
+    /// イベント名（`todo.created` など）
+    pub fn name(&self) -> &'static str {
+        WebhookEvent::from_action(self.action).as_str()
+    }
+
+    /// 通知する内容
+    pub fn payload(&self) -> Value {
+        json!({
+            "id": self.id,
+            "event": self.name(),
+            "createdAt": self.created_at,
+            "data": {
+                "todoId": self.todo_id,
+                "version": self.version,
+                "actorId": self.actor_id,
+                "changes": self.changes,
+                "todo": self.snapshot,
+            },
+        })
+    }
+}
+
+/// 履歴に保存するToDoの状態
+/// 差分の算出と、過去バージョンへの復帰に使用する
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoSnapshot {
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<DateTime<Utc>>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub status: TodoStatus,
+    pub priority: TodoPriority,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub estimate_minutes: Option<i32>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl From<&Todo> for TodoSnapshot {
+    fn from(todo: &Todo) -> Self {
+        Self {
+            title: todo.title.clone(),
+            description: todo.description.clone(),
+            start_date: todo.start_date,
+            due_date: todo.due_date,
+            status: todo.status.clone(),
+            priority: todo.priority.clone(),
+            tags: todo.tags.clone(),
+            estimate_minutes: todo.estimate_minutes,
+            deleted_at: todo.deleted_at,
+        }
+    }
+}
+
+impl TodoSnapshot {
+    /// フィールド単位の差分を `{"field": {"old": ..., "new": ...}}` 形式で返す
+    /// 変更前が存在しない（作成時）場合は全フィールドを差分とする
+    pub fn diff(before: Option<&Self>, after: &Self) -> Value {
+        let before = before
+            .map(|b| serde_json::to_value(b).unwrap_or(Value::Null))
+            .unwrap_or(Value::Null);
+        let after = serde_json::to_value(after).unwrap_or(Value::Null);
+
+        let mut changes = Map::new();
+        if let Value::Object(fields) = after {
+            for (field, new) in fields {
+                let old = before.get(&field).cloned().unwrap_or(Value::Null);
+                if old != new {
+                    changes.insert(field, json!({ "old": old, "new": new }));
+                }
+            }
+        }
+
+        Value::Object(changes)
+    }
+}
+
+// Response DTO
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoHistoryResponse {
+    pub version: i32,
+    pub action: TodoHistoryAction,
+    pub actor_id: Option<Uuid>,
+    #[schema(value_type = Object)]
+    pub changes: Value,
+    #[schema(value_type = Object)]
+    pub snapshot: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TodoHistory> for TodoHistoryResponse {
+    fn from(history: TodoHistory) -> Self {
+        Self {
+            version: history.version,
+            action: history.action,
+            actor_id: history.actor_id,
+            changes: history.changes,
+            snapshot: history.snapshot,
+            created_at: history.created_at,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use super::todo::TodoResponse;
+
+// Enum
+
+/// インポート・エクスポートのファイル形式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TransferFormat {
+    Csv,
+    #[default]
+    Json,
+    /// todo.txt 形式（1行1タスク）
+    Todotxt,
+}
+
+// Query DTO
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: TransferFormat,
+}
+
+// Request DTO
+
+/// インポートリクエスト
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportTodosRequest {
+    pub format: TransferFormat,
+    /// CSV・todo.txtの場合は本文の文字列、JSONの場合はオブジェクトの配列
+    pub content: Value,
+    /// 取り込み先のフィールド名（`title`, `dueDate` など）から、CSVの列名・JSONのキーへの対応
+    /// 省略したフィールドは同名の列・キーから読み取る（todo.txtでは使用しない）
+    #[serde(default)]
+    pub mapping: HashMap<String, String>,
+    /// `true` の場合は検証のみ行い、保存しない
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// Response DTO
+
+/// 検証エラーになった行（`row` はデータ行の1始まりの番号）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowError {
+    pub row: usize,
+    pub message: String,
+}
+
+/// インポート結果
+/// エラーが1件でもある場合は何も保存しない
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportTodosResponse {
+    pub dry_run: bool,
+    pub total: usize,
+    pub imported: usize,
+    pub errors: Vec<ImportRowError>,
+    pub todos: Vec<TodoResponse>,
+}
+
+// External import
+
+/// 他のツールのエクスポートファイルの種類
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportSource {
+    /// TodoistのバックアップCSV、またはAPIのJSON
+    Todoist,
+    /// Taskwarriorの `task export` のJSON
+    Taskwarrior,
+    /// Microsoft To Do（Microsoft Graph の `todoTask`）のJSON
+    MicrosoftTodo,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalImportRequest {
+    /// CSVの場合は本文の文字列、JSONの場合はそのままの値
+    pub content: Value,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportNoteKind {
+    /// 対応する項目がないため取り込まなかった
+    Skipped,
+    /// 値を変換して取り込んだ
+    Transformed,
+}
+
+/// 取り込まなかった・変換した項目
+#[derive(Debug, Clone, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportNote {
+    /// 対象のタスク名（ファイル全体に関する場合は空）
+    pub item: String,
+    /// 元のファイルでのフィールド名
+    pub field: String,
+    pub kind: ImportNoteKind,
+    pub detail: String,
+}
+
+/// 他のツールからのインポート結果
+/// エラーが1件でもある場合は何も保存しない
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalImportResponse {
+    pub source: ImportSource,
+    pub dry_run: bool,
+    /// サブタスクを含む件数
+    pub total: usize,
+    pub imported: usize,
+    pub errors: Vec<ImportRowError>,
+    pub notes: Vec<ImportNote>,
+    pub todos: Vec<TodoResponse>,
+}
@@ -1,7 +1,41 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Utc, Weekday};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+// Enum
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "week_day", rename_all = "snake_case")]
+#[serde(rename_all = "camelCase")]
+pub enum WeekDay {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<WeekDay> for Weekday {
+    fn from(day: WeekDay) -> Self {
+        match day {
+            WeekDay::Monday => Weekday::Mon,
+            WeekDay::Tuesday => Weekday::Tue,
+            WeekDay::Wednesday => Weekday::Wed,
+            WeekDay::Thursday => Weekday::Thu,
+            WeekDay::Friday => Weekday::Fri,
+            WeekDay::Saturday => Weekday::Sat,
+            WeekDay::Sunday => Weekday::Sun,
+        }
+    }
+}
+
+// Entity
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct User {
@@ -11,4 +45,82 @@ pub struct User {
     pub password_hash: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub display_name: Option<String>,
+    pub time_zone: String,
+    pub locale: String,
+    pub week_start: WeekDay,
+}
+
+impl User {
+    /// プロフィールのタイムゾーン（不正な値の場合はUTC）
+    pub fn tz(&self) -> Tz {
+        self.time_zone.parse().unwrap_or(Tz::UTC)
+    }
+
+    /// ユーザーのタイムゾーンでの現在日時
+    pub fn now(&self) -> DateTime<Tz> {
+        Utc::now().with_timezone(&self.tz())
+    }
+}
+
+// Request DTO
+
+/// プロフィールの更新（全項目を置き換える）
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProfileRequest {
+    #[validate(length(max = 100, message = "Display name must be at most 100 characters"))]
+    pub display_name: Option<String>,
+    /// IANAタイムゾーン名（例: `Asia/Tokyo`）
+    #[validate(custom(function = "validate_time_zone"))]
+    pub time_zone: String,
+    /// BCP 47 の言語タグ（例: `ja-JP`, `en-US`）
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: String,
+    pub week_start: WeekDay,
+}
+
+fn validate_time_zone(time_zone: &str) -> Result<(), ValidationError> {
+    if time_zone.parse::<Tz>().is_err() {
+        return Err(ValidationError::new("time_zone")
+            .with_message(format!("Unknown time zone '{}'", time_zone).into()));
+    }
+    Ok(())
+}
+
+fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    let mut subtags = locale.split('-');
+    let language_valid = subtags
+        .next()
+        .is_some_and(|l| (2..=3).contains(&l.len()) && l.chars().all(|c| c.is_ascii_alphabetic()));
+    let rest_valid =
+        subtags.all(|s| (1..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    if !language_valid || !rest_valid || locale.len() > 35 {
+        return Err(ValidationError::new("locale")
+            .with_message(format!("Invalid locale '{}'", locale).into()));
+    }
+    Ok(())
+}
+
+// Response DTO
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileResponse {
+    pub display_name: Option<String>,
+    pub time_zone: String,
+    pub locale: String,
+    pub week_start: WeekDay,
+}
+
+impl From<User> for ProfileResponse {
+    fn from(user: User) -> Self {
+        Self {
+            display_name: user.display_name,
+            time_zone: user.time_zone,
+            locale: user.locale,
+            week_start: user.week_start,
+        }
+    }
 }
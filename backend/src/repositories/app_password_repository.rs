@@ -0,0 +1,93 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{error::AppResult, models::app_password::AppPassword};
+
+#[derive(Clone)]
+pub struct AppPasswordRepository {
+    pool: PgPool,
+}
+
+impl AppPasswordRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        password_hash: &str,
+    ) -> AppResult<AppPassword> {
+        let password = sqlx::query_as::<_, AppPassword>(
+            r#"
+            insert into app_passwords (user_id, name, password_hash)
+            values ($1, $2, $3)
+            returning *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(password_hash)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(password)
+    }
+
+    /// アプリパスワード一覧（作成日時順）
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<AppPassword>> {
+        let passwords = sqlx::query_as::<_, AppPassword>(
+            r#"
+            select *
+              from app_passwords
+             where user_id = $1
+             order by created_at, id
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(passwords)
+    }
+
+    /// パスワードのハッシュで検索し、最終使用日時を更新
+    pub async fn touch_by_hash(
+        &self,
+        user_id: Uuid,
+        password_hash: &str,
+    ) -> AppResult<Option<AppPassword>> {
+        let password = sqlx::query_as::<_, AppPassword>(
+            r#"
+            update app_passwords
+               set last_used_at = now()
+             where user_id = $1
+               and password_hash = $2
+            returning *
+            "#,
+        )
+        .bind(user_id)
+        .bind(password_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(password)
+    }
+
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            delete from app_passwords
+             where id = $1
+               and user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
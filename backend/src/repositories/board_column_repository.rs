@@ -0,0 +1,364 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::{board::BoardColumn, todo::TodoStatus},
+    repositories::todo_repository::TodoRepository,
+    services::rank,
+};
+
+#[derive(Clone)]
+pub struct BoardColumnRepository {
+    pool: PgPool,
+}
+
+impl BoardColumnRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 列の作成
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        category: &TodoStatus,
+        position: &str,
+        wip_limit: Option<i32>,
+    ) -> AppResult<BoardColumn> {
+        let column = sqlx::query_as::<_, BoardColumn>(
+            r#"
+            insert into board_columns (user_id, name, category, position, wip_limit)
+            values ($1, $2, $3, $4, $5)
+            returning *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(category)
+        .bind(position)
+        .bind(wip_limit)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(column)
+    }
+
+    /// ステータスごとのデフォルト列を作成（同名の列が既にある場合は作成しない）
+    pub async fn create_defaults(&self, user_id: Uuid) -> AppResult<()> {
+        let names = ["To Do", "In Progress", "Done"];
+        let categories = [TodoStatus::Pending, TodoStatus::InProgress, TodoStatus::Completed];
+        let positions = rank::spread(names.len());
+
+        sqlx::query(
+            r#"
+            insert into board_columns (user_id, name, category, position)
+            select $1, defaults.name, defaults.category, defaults.position
+              from unnest($2::text[], $3::todo_status[], $4::text[])
+                as defaults(name, category, position)
+            on conflict (user_id, name) do nothing
+            "#,
+        )
+        .bind(user_id)
+        .bind(&names[..])
+        .bind(&categories[..])
+        .bind(&positions)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// ユーザーの列一覧を取得（並び順）
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<BoardColumn>> {
+        let columns = sqlx::query_as::<_, BoardColumn>(
+            "select * from board_columns where user_id = $1 order by position, id",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(columns)
+    }
+
+    /// ID + ユーザーIDで取得
+    pub async fn find_by_id_and_user_id(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<BoardColumn>> {
+        let column = sqlx::query_as::<_, BoardColumn>(
+            "select * from board_columns where id = $1 and user_id = $2",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(column)
+    }
+
+    /// 名前で取得
+    pub async fn find_by_name(&self, user_id: Uuid, name: &str) -> AppResult<Option<BoardColumn>> {
+        let column = sqlx::query_as::<_, BoardColumn>(
+            "select * from board_columns where user_id = $1 and name = $2",
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(column)
+    }
+
+    /// 列の更新
+    pub async fn update(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        name: &str,
+        wip_limit: Option<i32>,
+    ) -> AppResult<Option<BoardColumn>> {
+        let column = sqlx::query_as::<_, BoardColumn>(
+            r#"
+            update board_columns
+            set name = $3, wip_limit = $4, updated_at = now()
+            where id = $1 and user_id = $2
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(name)
+        .bind(wip_limit)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(column)
+    }
+
+    /// ランクキーを更新（並び替え）
+    pub async fn update_position(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        position: &str,
+    ) -> AppResult<Option<BoardColumn>> {
+        let column = sqlx::query_as::<_, BoardColumn>(
+            r#"
+            update board_columns
+            set position = $3, updated_at = now()
+            where id = $1 and user_id = $2
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(position)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(column)
+    }
+
+    /// 列の削除（列内のToDoはステータスに対応する先頭の列に表示される）
+    /// 列内のToDoの更新（`column_id` の解除）より先にユーザーのロックを取得する
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+        TodoRepository::lock_user(&mut tx, user_id).await?;
+        let result = sqlx::query("delete from board_columns where id = $1 and user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// ユーザーの末尾のランクキーを取得
+    pub async fn last_position(&self, user_id: Uuid) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, Option<String>>(
+            "select max(position) from board_columns where user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// 指定したキーの直後にある列のランクキーを取得
+    pub async fn next_position(
+        &self,
+        user_id: Uuid,
+        position: &str,
+        exclude_id: Uuid,
+    ) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, String>(
+            r#"
+            select position
+              from board_columns
+             where user_id = $1
+               and position > $2
+               and id <> $3
+             order by position
+             limit 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(position)
+        .bind(exclude_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// 指定したキーの直前にある列のランクキーを取得
+    pub async fn prev_position(
+        &self,
+        user_id: Uuid,
+        position: &str,
+        exclude_id: Uuid,
+    ) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, String>(
+            r#"
+            select position
+              from board_columns
+             where user_id = $1
+               and position < $2
+               and id <> $3
+             order by position desc
+             limit 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(position)
+        .bind(exclude_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// ユーザーの全列のランクキーを現在の順序のまま等間隔に振り直す
+    pub async fn rebalance_positions(&self, user_id: Uuid) -> AppResult<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            "select id from board_columns where user_id = $1 order by position, id for update",
+        )
+        .bind(user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let positions = rank::spread(ids.len());
+
+        let result = sqlx::query(
+            r#"
+            update board_columns
+            set position = ranked.position
+            from unnest($1::uuid[], $2::text[]) as ranked(id, position)
+            where board_columns.id = ranked.id
+            "#,
+        )
+        .bind(&ids)
+        .bind(&positions)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// カテゴリの列数を取得
+    pub async fn count_by_category(&self, user_id: Uuid, category: &TodoStatus) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "select count(*) from board_columns where user_id = $1 and category = $2",
+        )
+        .bind(user_id)
+        .bind(category)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// 列を行ロック付きで取得（WIP制限の確認用）
+    pub async fn lock_by_id(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<BoardColumn>> {
+        let column = sqlx::query_as::<_, BoardColumn>(
+            "select * from board_columns where id = $1 and user_id = $2 for update",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(column)
+    }
+
+    /// カテゴリの先頭の列のIDを取得（列未指定のToDoの表示先）
+    pub async fn find_default_id(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        category: &TodoStatus,
+    ) -> AppResult<Option<Uuid>> {
+        let id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            select id
+              from board_columns
+             where user_id = $1
+               and category = $2
+             order by position, id
+             limit 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(category)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// 列に表示されるToDoの件数を取得（指定したToDoを除く）
+    /// 列未指定のToDoは、ステータスに対応する先頭の列に含める
+    pub async fn count_todos(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        column_id: Uuid,
+        exclude_id: Uuid,
+    ) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            select count(*)
+              from todos
+             where user_id = $1
+               and deleted_at is null
+               and id <> $3
+               and coalesce(
+                   column_id,
+                   (select c.id
+                      from board_columns c
+                     where c.user_id = todos.user_id
+                       and c.category = todos.status
+                     order by c.position, c.id
+                     limit 1)
+               ) = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(column_id)
+        .bind(exclude_id)
+        .fetch_one(conn)
+        .await?;
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,87 @@
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::{error::AppResult, models::caldav::CaldavObject};
+
+#[derive(Clone)]
+pub struct CaldavObjectRepository {
+    pool: PgPool,
+}
+
+impl CaldavObjectRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 指定したコネクション（トランザクション）でリソースを追加
+    pub async fn create_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        todo_id: Uuid,
+        user_id: Uuid,
+        name: &str,
+        uid: &str,
+    ) -> AppResult<CaldavObject> {
+        let object = sqlx::query_as::<_, CaldavObject>(
+            r#"
+            insert into caldav_objects (todo_id, user_id, name, uid)
+            values ($1, $2, $3, $4)
+            returning *
+            "#,
+        )
+        .bind(todo_id)
+        .bind(user_id)
+        .bind(name)
+        .bind(uid)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(object)
+    }
+
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<CaldavObject>> {
+        let objects = sqlx::query_as::<_, CaldavObject>(
+            r#"
+            select *
+              from caldav_objects
+             where user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(objects)
+    }
+
+    pub async fn find_by_name(&self, user_id: Uuid, name: &str) -> AppResult<Option<CaldavObject>> {
+        let object = sqlx::query_as::<_, CaldavObject>(
+            r#"
+            select *
+              from caldav_objects
+             where user_id = $1
+               and name = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(object)
+    }
+
+    pub async fn find_by_todo_id(&self, todo_id: Uuid) -> AppResult<Option<CaldavObject>> {
+        let object = sqlx::query_as::<_, CaldavObject>(
+            r#"
+            select *
+              from caldav_objects
+             where todo_id = $1
+            "#,
+        )
+        .bind(todo_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(object)
+    }
+}
@@ -0,0 +1,80 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{error::AppResult, models::calendar::CalendarToken};
+
+#[derive(Clone)]
+pub struct CalendarTokenRepository {
+    pool: PgPool,
+}
+
+impl CalendarTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 購読トークンを発行（既存のトークンは置き換える）
+    pub async fn upsert(&self, user_id: Uuid, token_hash: &str) -> AppResult<CalendarToken> {
+        let token = sqlx::query_as::<_, CalendarToken>(
+            r#"
+            insert into calendar_tokens (user_id, token_hash)
+            values ($1, $2)
+            on conflict (user_id)
+            do update set token_hash = excluded.token_hash, created_at = now()
+            returning *
+            "#,
+        )
+        .bind(user_id)
+        .bind(token_hash)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> AppResult<Option<CalendarToken>> {
+        let token = sqlx::query_as::<_, CalendarToken>(
+            r#"
+            select *
+              from calendar_tokens
+             where user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// トークンハッシュで購読トークンを検索
+    pub async fn find_by_token_hash(&self, token_hash: &str) -> AppResult<Option<CalendarToken>> {
+        let token = sqlx::query_as::<_, CalendarToken>(
+            r#"
+            select *
+              from calendar_tokens
+             where token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// 購読トークンを無効化
+    pub async fn delete(&self, user_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            delete from calendar_tokens
+             where user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,4 +1,16 @@
 //! Data access layer
+pub mod app_password_repository;
+pub mod board_column_repository;
+pub mod caldav_object_repository;
+pub mod calendar_token_repository;
+pub mod saved_view_repository;
+pub mod stats_repository;
+pub mod sync_repository;
+pub mod template_repository;
+pub mod time_entry_repository;
+pub mod todo_dependency_repository;
+pub mod todo_history_repository;
 pub mod todo_repository;
 pub mod token_repository;
-pub mod user_repository;
\ No newline at end of file
+pub mod user_repository;
+pub mod webhook_repository;
\ No newline at end of file
@@ -0,0 +1,243 @@
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::saved_view::{SavedView, SavedViewFilters},
+    services::rank,
+};
+
+#[derive(Clone)]
+pub struct SavedViewRepository {
+    pool: PgPool,
+}
+
+impl SavedViewRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// ビューの作成
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        filters: &SavedViewFilters,
+        position: &str,
+    ) -> AppResult<SavedView> {
+        let view = sqlx::query_as::<_, SavedView>(
+            r#"
+            insert into saved_views (user_id, name, filters, position)
+            values ($1, $2, $3, $4)
+            returning *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(Json(filters))
+        .bind(position)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(view)
+    }
+
+    /// ユーザーのビュー一覧を取得（並び順）
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<SavedView>> {
+        let views = sqlx::query_as::<_, SavedView>(
+            "select * from saved_views where user_id = $1 order by position, id",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(views)
+    }
+
+    /// ID + ユーザーIDで取得
+    pub async fn find_by_id_and_user_id(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<SavedView>> {
+        let view = sqlx::query_as::<_, SavedView>(
+            "select * from saved_views where id = $1 and user_id = $2",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(view)
+    }
+
+    /// 名前で取得
+    pub async fn find_by_name(&self, user_id: Uuid, name: &str) -> AppResult<Option<SavedView>> {
+        let view = sqlx::query_as::<_, SavedView>(
+            "select * from saved_views where user_id = $1 and name = $2",
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(view)
+    }
+
+    /// ビューの更新（名前と条件を置き換える）
+    pub async fn update(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        name: &str,
+        filters: &SavedViewFilters,
+    ) -> AppResult<Option<SavedView>> {
+        let view = sqlx::query_as::<_, SavedView>(
+            r#"
+            update saved_views
+            set name = $3, filters = $4, updated_at = now()
+            where id = $1 and user_id = $2
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(name)
+        .bind(Json(filters))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(view)
+    }
+
+    /// ランクキーを更新（並び替え）
+    pub async fn update_position(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        position: &str,
+    ) -> AppResult<Option<SavedView>> {
+        let view = sqlx::query_as::<_, SavedView>(
+            r#"
+            update saved_views
+            set position = $3, updated_at = now()
+            where id = $1 and user_id = $2
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(position)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(view)
+    }
+
+    /// ビューの削除
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("delete from saved_views where id = $1 and user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// ユーザーの末尾のランクキーを取得
+    pub async fn last_position(&self, user_id: Uuid) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, Option<String>>(
+            "select max(position) from saved_views where user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// 指定したキーの直後にあるビューのランクキーを取得
+    pub async fn next_position(
+        &self,
+        user_id: Uuid,
+        position: &str,
+        exclude_id: Uuid,
+    ) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, String>(
+            r#"
+            select position
+              from saved_views
+             where user_id = $1
+               and position > $2
+               and id <> $3
+             order by position
+             limit 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(position)
+        .bind(exclude_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// 指定したキーの直前にあるビューのランクキーを取得
+    pub async fn prev_position(
+        &self,
+        user_id: Uuid,
+        position: &str,
+        exclude_id: Uuid,
+    ) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, String>(
+            r#"
+            select position
+              from saved_views
+             where user_id = $1
+               and position < $2
+               and id <> $3
+             order by position desc
+             limit 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(position)
+        .bind(exclude_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// ユーザーの全ビューのランクキーを現在の順序のまま等間隔に振り直す
+    pub async fn rebalance_positions(&self, user_id: Uuid) -> AppResult<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            "select id from saved_views where user_id = $1 order by position, id for update",
+        )
+        .bind(user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let positions = rank::spread(ids.len());
+
+        let result = sqlx::query(
+            r#"
+            update saved_views
+            set position = ranked.position
+            from unnest($1::uuid[], $2::text[]) as ranked(id, position)
+            where saved_views.id = ranked.id
+            "#,
+        )
+        .bind(&ids)
+        .bind(&positions)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+}
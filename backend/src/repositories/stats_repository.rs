@@ -0,0 +1,206 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::stats::{CompletionStreak, PriorityCount, StatsPeriod, StatusCount},
+};
+
+#[derive(Clone)]
+pub struct StatsRepository {
+    pool: PgPool,
+}
+
+impl StatsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// ステータス別の件数（0件のステータスも含む）
+    pub async fn count_by_status(&self, user_id: Uuid) -> AppResult<Vec<StatusCount>> {
+        let rows = sqlx::query_as::<_, StatusCount>(
+            r#"
+            select s.status
+                 , count(t.id) as count
+              from unnest(enum_range(null::todo_status)) as s(status)
+              left join todos t
+                on t.status = s.status
+               and t.user_id = $1
+               and t.deleted_at is null
+             group by s.status
+             order by s.status
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// 優先度別の件数（0件の優先度も含む）
+    pub async fn count_by_priority(&self, user_id: Uuid) -> AppResult<Vec<PriorityCount>> {
+        let rows = sqlx::query_as::<_, PriorityCount>(
+            r#"
+            select p.priority
+                 , count(t.id) as count
+              from unnest(enum_range(null::todo_priority)) as p(priority)
+              left join todos t
+                on t.priority = p.priority
+               and t.user_id = $1
+               and t.deleted_at is null
+             group by p.priority
+             order by p.priority
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// 期限を過ぎた未完了のToDoの件数
+    pub async fn count_overdue(&self, user_id: Uuid, now: DateTime<Utc>) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            select count(*)
+              from todos
+             where user_id = $1
+               and deleted_at is null
+               and status <> 'completed'
+               and due_date < $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// 期間ごとの作成数・完了数
+    /// `from` は最初の期間の初日、期間は `from` から `step_days` 日ごとに区切る
+    pub async fn series(
+        &self,
+        user_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        step_days: i32,
+        time_zone: &str,
+    ) -> AppResult<Vec<StatsPeriod>> {
+        let rows = sqlx::query_as::<_, StatsPeriod>(
+            r#"
+            with periods as (
+                select d::date as date
+                  from generate_series($2::date, $3::date, make_interval(days => $4)) as d
+            ), created as (
+                select local - (local - $2::date) % $4 as date
+                     , count(*) as count
+                  from (
+                      select (created_at at time zone $5)::date as local
+                        from todos
+                       where user_id = $1
+                         and deleted_at is null
+                  ) t
+                 where local between $2 and $3
+                 group by 1
+            ), completed as (
+                select local - (local - $2::date) % $4 as date
+                     , count(*) as count
+                  from (
+                      select (completed_at at time zone $5)::date as local
+                        from todos
+                       where user_id = $1
+                         and deleted_at is null
+                         and completed_at is not null
+                  ) t
+                 where local between $2 and $3
+                 group by 1
+            )
+            select p.date
+                 , coalesce(c.count, 0) as created
+                 , coalesce(x.count, 0) as completed
+              from periods p
+              left join created c on c.date = p.date
+              left join completed x on x.date = p.date
+             order by p.date
+            "#,
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .bind(step_days)
+        .bind(time_zone)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// 期間内に完了したToDoの作成から完了までの平均時間（秒）
+    pub async fn average_lead_time(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AppResult<Option<i64>> {
+        let seconds = sqlx::query_scalar::<_, Option<i64>>(
+            r#"
+            select round(avg(extract(epoch from completed_at - created_at)))::bigint
+              from todos
+             where user_id = $1
+               and deleted_at is null
+               and completed_at >= $2
+               and completed_at < $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(seconds)
+    }
+
+    /// 完了した日の連続日数（現在と最長）
+    pub async fn completion_streak(
+        &self,
+        user_id: Uuid,
+        today: NaiveDate,
+        time_zone: &str,
+    ) -> AppResult<CompletionStreak> {
+        let streak = sqlx::query_as::<_, CompletionStreak>(
+            r#"
+            with days as (
+                select distinct (completed_at at time zone $3)::date as date
+                  from todos
+                 where user_id = $1
+                   and deleted_at is null
+                   and completed_at is not null
+            ), streaks as (
+                select max(date) as last_date
+                     , count(*) as days
+                  from (
+                      select date, date - (row_number() over (order by date))::int as grp
+                        from days
+                  ) d
+                 group by grp
+            )
+            select coalesce(max(days) filter (where last_date >= $2::date - 1), 0)::bigint as current_days
+                 , coalesce(max(days), 0)::bigint as longest_days
+              from streaks
+            "#,
+        )
+        .bind(user_id)
+        .bind(today)
+        .bind(time_zone)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(streak)
+    }
+}
@@ -0,0 +1,98 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::{
+        sync::{SyncTodo, TodoTombstone},
+        todo::Todo,
+    },
+};
+
+#[derive(Clone)]
+pub struct SyncRepository {
+    pool: PgPool,
+}
+
+impl SyncRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// カーソルより後に作成・更新されたToDo（古い順）
+    /// `include_deleted` が `false` の場合はゴミ箱内のToDoを除く
+    pub async fn find_todos_since(
+        &self,
+        user_id: Uuid,
+        after: i64,
+        limit: i64,
+        include_deleted: bool,
+    ) -> AppResult<Vec<SyncTodo>> {
+        let todos = sqlx::query_as::<_, SyncTodo>(
+            r#"
+            select *
+              from todos
+             where user_id = $1
+               and sync_seq > $2
+               and ($4 or deleted_at is null)
+             order by sync_seq
+             limit $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(after)
+        .bind(limit)
+        .bind(include_deleted)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(todos)
+    }
+
+    /// カーソルより後に完全に削除されたToDo（古い順）
+    pub async fn find_tombstones_since(
+        &self,
+        user_id: Uuid,
+        after: i64,
+        limit: i64,
+    ) -> AppResult<Vec<TodoTombstone>> {
+        let tombstones = sqlx::query_as::<_, TodoTombstone>(
+            r#"
+            select *
+              from todo_tombstones
+             where user_id = $1
+               and sync_seq > $2
+             order by sync_seq
+             limit $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tombstones)
+    }
+
+    /// ユーザーを問わず、ゴミ箱内も含めてIDで取得（クライアントが生成したIDの重複確認に使う）
+    pub async fn find_todo(&self, id: Uuid) -> AppResult<Option<Todo>> {
+        let todo = sqlx::query_as::<_, Todo>("select * from todos where id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(todo)
+    }
+
+    pub async fn find_tombstone(&self, todo_id: Uuid) -> AppResult<Option<TodoTombstone>> {
+        let tombstone = sqlx::query_as::<_, TodoTombstone>(
+            "select * from todo_tombstones where todo_id = $1",
+        )
+        .bind(todo_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tombstone)
+    }
+}
@@ -0,0 +1,148 @@
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::{
+        template::{TemplateSubtask, TodoTemplate},
+        todo::TodoPriority,
+    },
+};
+
+#[derive(Clone)]
+pub struct TemplateRepository {
+    pool: PgPool,
+}
+
+impl TemplateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// テンプレート作成
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        title: &str,
+        description: Option<&str>,
+        priority: &TodoPriority,
+        due_offset_days: Option<i32>,
+        tags: &[String],
+        subtasks: &[TemplateSubtask],
+    ) -> AppResult<TodoTemplate> {
+        let template = sqlx::query_as::<_, TodoTemplate>(
+            r#"
+            insert into todo_templates (
+                user_id, name, title, description, priority, due_offset_days, tags, subtasks
+            )
+            values ($1, $2, $3, $4, $5, $6, $7, $8)
+            returning *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(title)
+        .bind(description)
+        .bind(priority)
+        .bind(due_offset_days)
+        .bind(tags)
+        .bind(Json(subtasks))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    /// ユーザーのテンプレート一覧を取得（名前順）
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<TodoTemplate>> {
+        let templates = sqlx::query_as::<_, TodoTemplate>(
+            "select * from todo_templates where user_id = $1 order by name",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(templates)
+    }
+
+    /// ID + ユーザーIDで取得
+    pub async fn find_by_id_and_user_id(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<TodoTemplate>> {
+        let template = sqlx::query_as::<_, TodoTemplate>(
+            "select * from todo_templates where id = $1 and user_id = $2",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    /// 名前で取得
+    pub async fn find_by_name(&self, user_id: Uuid, name: &str) -> AppResult<Option<TodoTemplate>> {
+        let template = sqlx::query_as::<_, TodoTemplate>(
+            "select * from todo_templates where user_id = $1 and name = $2",
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    /// テンプレートの更新（全項目を置き換える）
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        name: &str,
+        title: &str,
+        description: Option<&str>,
+        priority: &TodoPriority,
+        due_offset_days: Option<i32>,
+        tags: &[String],
+        subtasks: &[TemplateSubtask],
+    ) -> AppResult<Option<TodoTemplate>> {
+        let template = sqlx::query_as::<_, TodoTemplate>(
+            r#"
+            update todo_templates
+            set name = $3, title = $4, description = $5, priority = $6, due_offset_days = $7,
+                tags = $8, subtasks = $9, updated_at = now()
+            where id = $1 and user_id = $2
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(name)
+        .bind(title)
+        .bind(description)
+        .bind(priority)
+        .bind(due_offset_days)
+        .bind(tags)
+        .bind(Json(subtasks))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    /// テンプレートの削除
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("delete from todo_templates where id = $1 and user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
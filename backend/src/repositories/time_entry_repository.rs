@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::time_entry::{TimeEntry, TimeReportDay, TimeReportTag, TimeReportTodo},
+};
+
+/// 作業時間（秒）の算出式（計測中のタイマーは現在時刻までとする）
+const DURATION_SECONDS: &str =
+    "extract(epoch from coalesce(e.ended_at, now()) - e.started_at)::bigint";
+
+#[derive(Clone)]
+pub struct TimeEntryRepository {
+    pool: PgPool,
+}
+
+impl TimeEntryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 作業時間の記録を追加（`ended_at` が `None` の場合はタイマー開始）
+    pub async fn create(
+        &self,
+        todo_id: Uuid,
+        user_id: Uuid,
+        started_at: DateTime<Utc>,
+        ended_at: Option<DateTime<Utc>>,
+        note: Option<&str>,
+    ) -> AppResult<TimeEntry> {
+        let entry = sqlx::query_as::<_, TimeEntry>(
+            r#"
+            insert into time_entries (todo_id, user_id, started_at, ended_at, note)
+            values ($1, $2, $3, $4, $5)
+            returning *
+            "#,
+        )
+        .bind(todo_id)
+        .bind(user_id)
+        .bind(started_at)
+        .bind(ended_at)
+        .bind(note)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    /// ユーザーの計測中のタイマーを取得
+    pub async fn find_running_by_user_id(&self, user_id: Uuid) -> AppResult<Option<TimeEntry>> {
+        let entry = sqlx::query_as::<_, TimeEntry>(
+            "select * from time_entries where user_id = $1 and ended_at is null",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    /// ToDoの計測中のタイマーを停止
+    pub async fn stop(&self, todo_id: Uuid, user_id: Uuid) -> AppResult<Option<TimeEntry>> {
+        let entry = sqlx::query_as::<_, TimeEntry>(
+            r#"
+            update time_entries
+            set ended_at = now()
+            where todo_id = $1 and user_id = $2 and ended_at is null
+            returning *
+            "#,
+        )
+        .bind(todo_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    /// ToDoの作業時間の記録一覧を取得（新しい順）
+    pub async fn find_by_todo_id(&self, todo_id: Uuid) -> AppResult<Vec<TimeEntry>> {
+        let entries = sqlx::query_as::<_, TimeEntry>(
+            "select * from time_entries where todo_id = $1 order by started_at desc",
+        )
+        .bind(todo_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// 作業時間の記録を削除
+    pub async fn delete(&self, id: Uuid, todo_id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query(
+            "delete from time_entries where id = $1 and todo_id = $2 and user_id = $3",
+        )
+        .bind(id)
+        .bind(todo_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// ToDoごとの作業時間の合計（秒）を取得
+    pub async fn total_seconds_by_todo_ids(&self, ids: &[Uuid]) -> AppResult<Vec<(Uuid, i64)>> {
+        let totals = sqlx::query_as::<_, (Uuid, i64)>(&format!(
+            r#"
+            select e.todo_id, sum({})::bigint
+              from time_entries e
+             where e.todo_id = any($1)
+             group by e.todo_id
+            "#,
+            DURATION_SECONDS
+        ))
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    /// 日別の作業時間を集計（開始日時で期間を判定し、`time_zone` の日付で区切る）
+    pub async fn report_by_day(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        time_zone: &str,
+    ) -> AppResult<Vec<TimeReportDay>> {
+        let rows = sqlx::query_as::<_, TimeReportDay>(&format!(
+            r#"
+            select (e.started_at at time zone $4)::date as date
+                 , sum({})::bigint as seconds
+              from time_entries e
+             where e.user_id = $1
+               and e.started_at >= $2
+               and e.started_at < $3
+             group by 1
+             order by 1
+            "#,
+            DURATION_SECONDS
+        ))
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .bind(time_zone)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// ToDo別の作業時間を集計（作業時間の長い順）
+    pub async fn report_by_todo(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AppResult<Vec<TimeReportTodo>> {
+        let rows = sqlx::query_as::<_, TimeReportTodo>(&format!(
+            r#"
+            select t.id as todo_id
+                 , t.title
+                 , sum({})::bigint as seconds
+              from time_entries e
+              join todos t on t.id = e.todo_id
+             where e.user_id = $1
+               and e.started_at >= $2
+               and e.started_at < $3
+             group by t.id, t.title
+             order by seconds desc, t.title
+            "#,
+            DURATION_SECONDS
+        ))
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// タグ別の作業時間を集計（タグのないToDoは含めない）
+    pub async fn report_by_tag(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AppResult<Vec<TimeReportTag>> {
+        let rows = sqlx::query_as::<_, TimeReportTag>(&format!(
+            r#"
+            select tag
+                 , sum({})::bigint as seconds
+              from time_entries e
+              join todos t on t.id = e.todo_id
+             cross join unnest(t.tags) as tag
+             where e.user_id = $1
+               and e.started_at >= $2
+               and e.started_at < $3
+             group by tag
+             order by seconds desc, tag
+            "#,
+            DURATION_SECONDS
+        ))
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
@@ -0,0 +1,115 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{error::AppResult, models::todo_dependency::TodoDependency};
+
+#[derive(Clone)]
+pub struct TodoDependencyRepository {
+    pool: PgPool,
+}
+
+impl TodoDependencyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 依存関係を追加（既に存在する場合は何もしない）
+    pub async fn create(conn: &mut PgConnection, todo_id: Uuid, blocker_id: Uuid) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            insert into todo_dependencies (todo_id, blocker_id)
+            values ($1, $2)
+            on conflict do nothing
+            "#,
+        )
+        .bind(todo_id)
+        .bind(blocker_id)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 依存関係を削除
+    pub async fn delete(&self, todo_id: Uuid, blocker_id: Uuid) -> AppResult<bool> {
+        let result =
+            sqlx::query("delete from todo_dependencies where todo_id = $1 and blocker_id = $2")
+                .bind(todo_id)
+                .bind(blocker_id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 依存関係を追加すると循環するか
+    /// `blocker_id` の依存先を辿って `todo_id` に到達する場合は循環とみなす
+    pub async fn creates_cycle(
+        conn: &mut PgConnection,
+        todo_id: Uuid,
+        blocker_id: Uuid,
+    ) -> AppResult<bool> {
+        let cycle = sqlx::query_scalar::<_, bool>(
+            r#"
+            with recursive chain(id) as (
+                select blocker_id from todo_dependencies where todo_id = $2
+                union
+                select d.blocker_id
+                  from todo_dependencies d
+                  join chain on d.todo_id = chain.id
+            )
+            select exists(select 1 from chain where id = $1)
+            "#,
+        )
+        .bind(todo_id)
+        .bind(blocker_id)
+        .fetch_one(conn)
+        .await?;
+
+        Ok(cycle)
+    }
+
+    /// 指定したToDoに関係する依存関係を取得（ゴミ箱内のToDoとの関係は除く）
+    pub async fn find_by_todo_ids(&self, ids: &[Uuid]) -> AppResult<Vec<TodoDependency>> {
+        let dependencies = sqlx::query_as::<_, TodoDependency>(
+            r#"
+            select d.*
+              from todo_dependencies d
+              join todos t on t.id = d.todo_id and t.deleted_at is null
+              join todos b on b.id = d.blocker_id and b.deleted_at is null
+             where d.todo_id = any($1)
+                or d.blocker_id = any($1)
+             order by d.created_at
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(dependencies)
+    }
+
+    /// 未完了の依存先ToDoのIDを取得
+    pub async fn find_incomplete_blocker_ids(
+        conn: &mut PgConnection,
+        todo_ids: &[Uuid],
+    ) -> AppResult<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            select b.id
+              from todo_dependencies d
+              join todos b on b.id = d.blocker_id
+             where d.todo_id = any($1)
+               and b.deleted_at is null
+               and b.status <> 'completed'
+             group by b.id
+             order by min(d.created_at)
+            "#,
+        )
+        .bind(todo_ids)
+        .fetch_all(conn)
+        .await?;
+
+        Ok(ids)
+    }
+}
@@ -0,0 +1,162 @@
+use serde_json::Value;
+use sqlx::{postgres::PgListener, PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::todo_history::{TodoEvent, TodoHistory, TodoHistoryAction},
+};
+
+/// 履歴の追加を通知するチャンネル（ペイロードはToDoの所有者のID）
+pub const TODO_EVENTS_CHANNEL: &str = "todo_events";
+
+#[derive(Clone)]
+pub struct TodoHistoryRepository {
+    pool: PgPool,
+}
+
+impl TodoHistoryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 指定したコネクション（トランザクション）で履歴を追加（バージョンはToDoごとの連番）
+    pub async fn create_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        todo_id: Uuid,
+        actor_id: Uuid,
+        action: TodoHistoryAction,
+        changes: &Value,
+        snapshot: &Value,
+    ) -> AppResult<TodoHistory> {
+        let history = sqlx::query_as::<_, TodoHistory>(
+            r#"
+            insert into todo_history (todo_id, actor_id, version, action, changes, snapshot)
+            select $1, $2, coalesce(max(version), 0) + 1, $3, $4, $5
+              from todo_history
+             where todo_id = $1
+            returning *
+            "#,
+        )
+        .bind(todo_id)
+        .bind(actor_id)
+        .bind(action)
+        .bind(changes)
+        .bind(snapshot)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// ToDoの履歴一覧を取得（新しい順）
+    pub async fn find_by_todo_id(&self, todo_id: Uuid) -> AppResult<Vec<TodoHistory>> {
+        let histories = sqlx::query_as::<_, TodoHistory>(
+            r#"
+            select *
+              from todo_history
+             where todo_id = $1
+             order by version desc
+            "#,
+        )
+        .bind(todo_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(histories)
+    }
+
+    /// ToDoの特定バージョンの履歴を取得
+    pub async fn find_by_todo_id_and_version(
+        &self,
+        todo_id: Uuid,
+        version: i32,
+    ) -> AppResult<Option<TodoHistory>> {
+        let history = sqlx::query_as::<_, TodoHistory>(
+            "select * from todo_history where todo_id = $1 and version = $2",
+        )
+        .bind(todo_id)
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// ToDoのバージョンが `todo_version` の時点のスナップショット（記録がなければ `None`）
+    pub async fn find_snapshot_at_todo_version(
+        &self,
+        todo_id: Uuid,
+        todo_version: i32,
+    ) -> AppResult<Option<Value>> {
+        let snapshot = sqlx::query_scalar::<_, Value>(
+            r#"
+            select snapshot
+              from todo_history
+             where todo_id = $1
+               and todo_version <= $2
+             order by todo_version desc, version desc
+             limit 1
+            "#,
+        )
+        .bind(todo_id)
+        .bind(todo_version)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+
+    /// ユーザーのToDoの履歴のうち、通し番号が `after` より後のものを古い順に取得
+    pub async fn find_events_since(
+        &self,
+        user_id: Uuid,
+        after: i64,
+        limit: i64,
+    ) -> AppResult<Vec<TodoEvent>> {
+        let events = sqlx::query_as::<_, TodoEvent>(
+            r#"
+            select h.seq, h.id, h.todo_id, t.user_id, h.actor_id, h.version, h.action
+                 , h.changes, h.snapshot, h.created_at
+              from todo_history h
+              join todos t on t.id = h.todo_id
+             where t.user_id = $1
+               and h.seq > $2
+             order by h.seq
+             limit $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    /// ユーザーのToDoの履歴の最新の通し番号（履歴がなければ0）
+    pub async fn latest_seq(&self, user_id: Uuid) -> AppResult<i64> {
+        let seq: Option<i64> = sqlx::query_scalar(
+            r#"
+            select max(h.seq)
+              from todo_history h
+              join todos t on t.id = h.todo_id
+             where t.user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(seq.unwrap_or(0))
+    }
+
+    /// 履歴の追加の通知を受け取るコネクション
+    pub async fn listen(&self) -> AppResult<PgListener> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(TODO_EVENTS_CHANNEL).await?;
+
+        Ok(listener)
+    }
+}
@@ -1,10 +1,16 @@
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgExecutor, PgPool, Postgres, QueryBuilder, Transaction};
 use uuid::Uuid;
 
 use crate::{
     error::AppResult,
     models::{
-        todo::{Todo, TodoQuery},
+        todo::{BulkTodoAction, Todo, TodoPriority, TodoQuery, TodoStatus},
+        todo_history::TodoSnapshot,
+    },
+    services::{
+        filter::{Comparison, Condition, FilterExpr},
+        rank,
     },
 };
 
@@ -18,20 +24,33 @@ impl TodoRepository {
         Self { pool }
     }
 
-    /// ToDo作成
-    pub async fn create(
-        &self,
+    /// 指定したコネクション（トランザクション）でToDo作成
+    /// `parent_id` を指定するとサブタスクとして作成する
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_with<'e, E: PgExecutor<'e>>(
+        executor: E,
         user_id: Uuid,
         title: &str,
         description: Option<&str>,
-        due_date: Option<chrono::DateTime<chrono::Utc>>,
-        status: &crate::models::todo::TodoStatus,
-        priority: &crate::models::todo::TodoPriority,
+        due_date: Option<DateTime<Utc>>,
+        status: &TodoStatus,
+        priority: &TodoPriority,
+        tags: &[String],
+        position: &str,
+        estimate_minutes: Option<i32>,
+        start_date: Option<DateTime<Utc>>,
+        parent_id: Option<Uuid>,
     ) -> AppResult<Todo> {
         let todo = sqlx::query_as::<_, Todo>(
             r#"
-            insert into todos (user_id, title, description, due_date, status, priority)
-            values ($1, $2, $3, $4, $5, $6)
+            insert into todos (
+                user_id, title, description, due_date, status, priority, tags, position,
+                estimate_minutes, start_date, parent_id, completed_at
+            )
+            values (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+                case when $5 = 'completed'::todo_status then now() end
+            )
             returning *
             "#,
         )
@@ -41,116 +60,323 @@ impl TodoRepository {
         .bind(due_date)
         .bind(status)
         .bind(priority)
-        .fetch_one(&self.pool)
+        .bind(tags)
+        .bind(position)
+        .bind(estimate_minutes)
+        .bind(start_date)
+        .bind(parent_id)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(todo)
+    }
+
+    /// クライアントが生成したIDでToDo作成（同期用）
+    /// IDが既に使われている場合は `None`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_with_id<'e, E: PgExecutor<'e>>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+        title: &str,
+        description: Option<&str>,
+        due_date: Option<DateTime<Utc>>,
+        status: &TodoStatus,
+        priority: &TodoPriority,
+        tags: &[String],
+        position: &str,
+        estimate_minutes: Option<i32>,
+        start_date: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<Todo>> {
+        let todo = sqlx::query_as::<_, Todo>(
+            r#"
+            insert into todos (
+                id, user_id, title, description, due_date, status, priority, tags, position,
+                estimate_minutes, start_date, completed_at
+            )
+            values (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+                case when $6 = 'completed'::todo_status then now() end
+            )
+            on conflict (id) do nothing
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(title)
+        .bind(description)
+        .bind(due_date)
+        .bind(status)
+        .bind(priority)
+        .bind(tags)
+        .bind(position)
+        .bind(estimate_minutes)
+        .bind(start_date)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(todo)
+    }
+
+    /// インポート元の作成日時・完了日時を反映（完了日時は完了したToDoのみ）
+    pub async fn set_imported_dates_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        id: Uuid,
+        created_at: Option<DateTime<Utc>>,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> AppResult<Todo> {
+        let todo = sqlx::query_as::<_, Todo>(
+            r#"
+            update todos
+            set created_at = coalesce($2, created_at),
+                completed_at = case
+                    when status = 'completed'::todo_status then coalesce($3, completed_at)
+                end
+            where id = $1
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(created_at)
+        .bind(completed_at)
+        .fetch_one(executor)
         .await?;
 
         Ok(todo)
     }
 
     /// ID + ユーザーIDで取得
-    /// 認可チェックも行う
+    /// 認可チェックも行う（ゴミ箱内のToDoは対象外）
     pub async fn find_by_id_and_user_id(&self, id: Uuid, user_id: Uuid) -> AppResult<Option<Todo>> {
-        let todo = sqlx::query_as::<_, Todo>("select * from todos where id = $1 and user_id = $2")
-            .bind(id)
-            .bind(user_id)
-            .fetch_optional(&self.pool)
-            .await?;
+        let todo = sqlx::query_as::<_, Todo>(
+            "select * from todos where id = $1 and user_id = $2 and deleted_at is null",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
 
         Ok(todo)
     }
 
     /// フィルタ・ソート・ページネーション付き一覧取得
+    /// `filter` は `query.filter` を解析した絞り込み式
     pub async fn find_by_user_id(
         &self,
         user_id: Uuid,
         query: &TodoQuery,
+        filter: Option<&FilterExpr>,
     ) -> AppResult<(Vec<Todo>, i64)> {
-        // where句の構築
-        let mut where_clauses: Vec<String> = vec!["user_id = $1".to_string()];
-        let mut param_index = 2u32;
+        // ページネーション
+        let per_page = query.per_page.clamp(1, 100);
+        let offset = (query.page.max(1) - 1) * per_page;
 
-        if query.status.is_some() {
-            where_clauses.push(format!("status = ${}", param_index));
-            param_index += 1;
-        }
-        if query.priority.is_some() {
-            where_clauses.push(format!("priority = ${}", param_index));
-            param_index += 1;
-        }
-        if query.due_before.is_some() {
-            where_clauses.push(format!("due_date <= ${}", param_index));
-            param_index += 1;
-        }
-        if query.due_after.is_some() {
-            where_clauses.push(format!("due_date >= ${}", param_index));
-            param_index += 1;
-        }
+        // count クエリ
+        let mut count_query = QueryBuilder::<Postgres>::new("select count(*) from todos");
+        Self::push_filters(&mut count_query, user_id, query, filter);
+        let total = count_query
+            .build_query_scalar::<i64>()
+            .fetch_one(&self.pool)
+            .await?;
 
-        let where_clause = where_clauses.join(" and ");
+        // データ取得クエリ
+        let todos = self
+            .find_page_by_user_id(user_id, query, filter, per_page, offset)
+            .await?;
 
+        Ok((todos, total))
+    }
+
+    /// 一覧と同じ条件・並び順で `offset` から `limit` 件を取得（ページサイズの上限なし）
+    pub async fn find_page_by_user_id(
+        &self,
+        user_id: Uuid,
+        query: &TodoQuery,
+        filter: Option<&FilterExpr>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<Todo>> {
         // ソート
         let sort_column = match query.sort.as_str() {
             "due_date" | "dueDate" => "due_date",
             "priority" => "priority",
             "created_at" | "createdAt" => "created_at",
+            "completed_at" | "completedAt" => "completed_at",
+            "position" => "position",
             _ => "created_at",
         };
-        let sort_order = match query.order.as_str() {
-            "asc" => "asc",
-            _ => "desc",
+        let sort_order = match query.order.as_deref() {
+            Some("asc") => "asc",
+            Some(_) => "desc",
+            None if sort_column == "position" => "asc",
+            None => "desc",
         };
 
-        // ページネーション
-        let per_page = query.per_page.clamp(1, 100);
-        let offset = (query.page.max(1) - 1) * per_page;
+        let mut data_query = QueryBuilder::<Postgres>::new("select * from todos");
+        Self::push_filters(&mut data_query, user_id, query, filter);
+        data_query.push(format!(
+            " order by {0} {1}, id {1} limit {2} offset {3}",
+            sort_column, sort_order, limit, offset
+        ));
+        let todos = data_query
+            .build_query_as::<Todo>()
+            .fetch_all(&self.pool)
+            .await?;
 
-        // count クエリ
-        let count_sql = format!("select count(*) from todos where {}", where_clause);
-        let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql).bind(user_id);
+        Ok(todos)
+    }
+
+    /// 一覧取得用のwhere句を構築する
+    fn push_filters<'a>(
+        builder: &mut QueryBuilder<'a, Postgres>,
+        user_id: Uuid,
+        query: &'a TodoQuery,
+        filter: Option<&FilterExpr>,
+    ) {
+        builder
+            .push(" where user_id = ")
+            .push_bind(user_id)
+            .push(" and deleted_at is null");
 
         if let Some(ref status) = query.status {
-            count_query = count_query.bind(status);
+            builder.push(" and status = ").push_bind(status);
         }
         if let Some(ref priority) = query.priority {
-            count_query = count_query.bind(priority);
+            builder.push(" and priority = ").push_bind(priority);
         }
-        if let Some(ref due_before) = query.due_before {
-            count_query = count_query.bind(due_before);
+        if let Some(due_before) = query.due_before {
+            builder.push(" and due_date <= ").push_bind(due_before);
         }
-        if let Some(ref due_after) = query.due_after {
-            count_query = count_query.bind(due_after);
+        if let Some(due_after) = query.due_after {
+            builder.push(" and due_date >= ").push_bind(due_after);
         }
-
-        let total = count_query.fetch_one(&self.pool).await?;
-
-        // データ取得クエリ
-        let data_sql = format!(
-            "select * from todos where {} order by {} {} limit {} offset {}",
-            where_clause, sort_column, sort_order, per_page, offset
-        );
-        let mut data_query = sqlx::query_as::<_, Todo>(&data_sql).bind(user_id);
-
-        if let Some(ref status) = query.status {
-            data_query = data_query.bind(status);
+        if let Some(completed_before) = query.completed_before {
+            builder.push(" and completed_at <= ").push_bind(completed_before);
         }
-        if let Some(ref priority) = query.priority {
-            data_query = data_query.bind(priority);
+        if let Some(completed_after) = query.completed_after {
+            builder.push(" and completed_at >= ").push_bind(completed_after);
         }
-        if let Some(ref due_before) = query.due_before {
-            data_query = data_query.bind(due_before);
+        if let Some(ref tag) = query.tag {
+            builder.push(" and ").push_bind(tag).push(" = any(tags)");
         }
-        if let Some(ref due_after) = query.due_after {
-            data_query = data_query.bind(due_after);
+        if let Some(q) = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+            let pattern = format!("%{}%", Self::escape_like(q));
+            builder
+                .push(" and (title ilike ")
+                .push_bind(pattern.clone())
+                .push(" or description ilike ")
+                .push_bind(pattern)
+                .push(")");
         }
+        if let Some(filter) = filter {
+            builder.push(" and ");
+            Self::push_filter_expr(builder, filter);
+        }
+    }
 
-        let todos = data_query.fetch_all(&self.pool).await?;
+    /// 絞り込み式を条件式に変換する
+    /// `not` で未設定の日付が除外されないよう、各条件は `null` にならないようにする
+    fn push_filter_expr(builder: &mut QueryBuilder<'_, Postgres>, expr: &FilterExpr) {
+        match expr {
+            FilterExpr::And(left, right) | FilterExpr::Or(left, right) => {
+                let operator = if matches!(expr, FilterExpr::And(..)) {
+                    " and "
+                } else {
+                    " or "
+                };
+                builder.push("(");
+                Self::push_filter_expr(builder, left);
+                builder.push(operator);
+                Self::push_filter_expr(builder, right);
+                builder.push(")");
+            }
+            FilterExpr::Not(inner) => {
+                builder.push("not ");
+                Self::push_filter_expr(builder, inner);
+            }
+            FilterExpr::Condition(condition) => Self::push_filter_condition(builder, condition),
+        }
+    }
 
-        Ok((todos, total))
+    fn push_filter_condition(builder: &mut QueryBuilder<'_, Postgres>, condition: &Condition) {
+        match condition {
+            Condition::Status(status) => {
+                builder.push("(status = ").push_bind(status.clone()).push(")");
+            }
+            Condition::Priority(comparison, priority) => {
+                let operator = match comparison {
+                    Comparison::Eq => "=",
+                    Comparison::Lt => "<",
+                    Comparison::Le => "<=",
+                    Comparison::Gt => ">",
+                    Comparison::Ge => ">=",
+                };
+                builder
+                    .push(format!("(priority {} ", operator))
+                    .push_bind(priority.clone())
+                    .push(")");
+            }
+            Condition::Date {
+                field,
+                comparison,
+                start,
+                end,
+            } => {
+                let column = field.column();
+                builder.push(format!("coalesce({} ", column));
+                match comparison {
+                    Comparison::Eq => {
+                        builder
+                            .push(">= ")
+                            .push_bind(*start)
+                            .push(format!(" and {} <= ", column))
+                            .push_bind(*end);
+                    }
+                    Comparison::Lt => {
+                        builder.push("< ").push_bind(*start);
+                    }
+                    Comparison::Le => {
+                        builder.push("<= ").push_bind(*end);
+                    }
+                    Comparison::Gt => {
+                        builder.push("> ").push_bind(*end);
+                    }
+                    Comparison::Ge => {
+                        builder.push(">= ").push_bind(*start);
+                    }
+                }
+                builder.push(", false)");
+            }
+            Condition::NoDate(field) => {
+                builder.push(format!("({} is null)", field.column()));
+            }
+            Condition::Tag(tag) => {
+                builder.push("(").push_bind(tag.clone()).push(" = any(tags))");
+            }
+            Condition::Text(text) => {
+                let pattern = format!("%{}%", Self::escape_like(text));
+                builder
+                    .push("coalesce(title ilike ")
+                    .push_bind(pattern.clone())
+                    .push(" or description ilike ")
+                    .push_bind(pattern)
+                    .push(", false)");
+            }
+        }
+    }
+
+    /// LIKE のワイルドカードをエスケープする
+    fn escape_like(text: &str) -> String {
+        text.replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
     }
 
     /// ToDoの更新
-    pub async fn update(
-        &self,
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_with<'e, E: PgExecutor<'e>>(
+        executor: E,
         id: Uuid,
         user_id: Uuid,
         title: Option<&str>,
@@ -158,100 +384,680 @@ impl TodoRepository {
         due_date: Option<chrono::DateTime<chrono::Utc>>,
         status: Option<&crate::models::todo::TodoStatus>,
         priority: Option<&crate::models::todo::TodoPriority>,
+        tags: Option<&[String]>,
+        estimate_minutes: Option<i32>,
+        start_date: Option<DateTime<Utc>>,
+        expected_versions: Option<&[i32]>,
     ) -> AppResult<Option<Todo>> {
-        // set句を動的に構築
-        let mut set_clauses: Vec<String> = Vec::new();
-        let mut param_index = 3u32; // $1 = id, $2 = user_id
-
-        if title.is_some() {
-            set_clauses.push(format!("title = ${}", param_index));
-            param_index += 1;
-        }
-        if description.is_some() {
-            set_clauses.push(format!("description = ${}", param_index));
-            param_index += 1;
-        }
-        if due_date.is_some() {
-            set_clauses.push(format!("due_date = ${}", param_index));
-            param_index += 1;
-        }
-        if status.is_some() {
-            set_clauses.push(format!("status = ${}", param_index));
-            param_index += 1;
-        }
-        if priority.is_some() {
-            set_clauses.push(format!("priority = ${}", param_index));
-            param_index += 1;
-        }
-
-        if set_clauses.is_empty() {
+        if title.is_none()
+            && description.is_none()
+            && start_date.is_none()
+            && due_date.is_none()
+            && status.is_none()
+            && priority.is_none()
+            && tags.is_none()
+            && estimate_minutes.is_none()
+        {
             // 更新するフィールドがない場合は現在の値を返す
-            return self.find_by_id_and_user_id(id, user_id).await;
+            let todo = sqlx::query_as::<_, Todo>(
+                "select * from todos where id = $1 and user_id = $2 and deleted_at is null",
+            )
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(executor)
+            .await?;
+            return Ok(todo);
         }
 
-        set_clauses.push("updated_at = now()".to_string());
-
-        let sql = format!(
-            "update todos set {} where id = $1 and user_id = $2 returning *",
-            set_clauses.join(", ")
-        );
-
-        let mut query = sqlx::query_as::<_, Todo>(&sql).bind(id).bind(user_id);
+        // set句を動的に構築
+        let mut builder = QueryBuilder::<Postgres>::new("update todos set ");
+        let mut set_clauses = builder.separated(", ");
 
         if let Some(title) = title {
-            query = query.bind(title);
+            set_clauses.push("title = ").push_bind_unseparated(title);
         }
         if let Some(description) = description {
-            query = query.bind(description);
+            set_clauses.push("description = ").push_bind_unseparated(description);
+        }
+        if let Some(start_date) = start_date {
+            set_clauses.push("start_date = ").push_bind_unseparated(start_date);
         }
         if let Some(due_date) = due_date {
-            query = query.bind(due_date);
+            set_clauses.push("due_date = ").push_bind_unseparated(due_date);
         }
         if let Some(status) = status {
-            query = query.bind(status);
+            set_clauses.push("status = ").push_bind_unseparated(status);
+            // ステータスが変わった場合は対応する先頭の列に表示する
+            set_clauses
+                .push("column_id = case when status = ")
+                .push_bind_unseparated(status)
+                .push_unseparated(" then column_id end");
+            set_clauses
+                .push("completed_at = case when ")
+                .push_bind_unseparated(status)
+                .push_unseparated(" = 'completed'::todo_status then coalesce(completed_at, now()) end");
         }
         if let Some(priority) = priority {
-            query = query.bind(priority);
+            set_clauses.push("priority = ").push_bind_unseparated(priority);
+        }
+        if let Some(tags) = tags {
+            set_clauses.push("tags = ").push_bind_unseparated(tags);
         }
-        
-        let todo = query.fetch_optional(&self.pool).await?;
+        if let Some(estimate_minutes) = estimate_minutes {
+            set_clauses
+                .push("estimate_minutes = ")
+                .push_bind_unseparated(estimate_minutes);
+        }
+        set_clauses.push("updated_at = now()");
+        set_clauses.push("version = version + 1");
+
+        builder
+            .push(" where id = ")
+            .push_bind(id)
+            .push(" and user_id = ")
+            .push_bind(user_id)
+            .push(" and deleted_at is null");
+        if let Some(expected_versions) = expected_versions {
+            builder.push(" and version = any(").push_bind(expected_versions).push(")");
+        }
+        builder.push(" returning *");
+
+        let todo = builder
+            .build_query_as::<Todo>()
+            .fetch_optional(executor)
+            .await?;
         Ok(todo)
     }
 
     /// ステータス更新
-    pub async fn update_status(
-        &self,
+    pub async fn update_status_with<'e, E: PgExecutor<'e>>(
+        executor: E,
         id: Uuid,
         user_id: Uuid,
         status: &crate::models::todo::TodoStatus,
+        expected_versions: Option<&[i32]>,
     ) -> AppResult<Option<Todo>> {
         let todo = sqlx::query_as::<_, Todo>(
             r#"
-            update todos 
-            set status = $3, updated_at = now() 
-            where id = $1 and user_id = $2 
+            update todos
+            set status = $3, column_id = case when status = $3 then column_id end,
+                completed_at = case when $3 = 'completed'::todo_status
+                                    then coalesce(completed_at, now()) end,
+                updated_at = now(), version = version + 1
+            where id = $1 and user_id = $2 and deleted_at is null
+              and ($4::integer[] is null or version = any($4))
             returning *
             "#,
         )
         .bind(id)
         .bind(user_id)
         .bind(status)
-        .fetch_optional(&self.pool)
+        .bind(expected_versions)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(todo)
+    }
+
+    /// ToDoの全フィールドを上書き（過去バージョンへの復帰用）
+    pub async fn replace_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+        snapshot: &TodoSnapshot,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<Option<Todo>> {
+        let todo = sqlx::query_as::<_, Todo>(
+            r#"
+            update todos
+            set title = $3, description = $4, due_date = $5, status = $6, priority = $7,
+                tags = $8, estimate_minutes = $9, start_date = $10,
+                column_id = case when status = $6 then column_id end,
+                completed_at = case when $6 = 'completed'::todo_status
+                                    then coalesce(completed_at, now()) end,
+                updated_at = now(), version = version + 1
+            where id = $1 and user_id = $2 and deleted_at is null
+              and ($11::integer[] is null or version = any($11))
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(&snapshot.title)
+        .bind(&snapshot.description)
+        .bind(snapshot.due_date)
+        .bind(&snapshot.status)
+        .bind(&snapshot.priority)
+        .bind(&snapshot.tags)
+        .bind(snapshot.estimate_minutes)
+        .bind(snapshot.start_date)
+        .bind(expected_versions)
+        .fetch_optional(executor)
         .await?;
-        
+
+        Ok(todo)
+    }
+
+    /// Todoの削除（ゴミ箱へ移動）
+    pub async fn delete_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<Option<Todo>> {
+        let todo = sqlx::query_as::<_, Todo>(
+            r#"
+            update todos
+            set deleted_at = now(), version = version + 1
+            where id = $1 and user_id = $2 and deleted_at is null
+              and ($3::integer[] is null or version = any($3))
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(expected_versions)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(todo)
+    }
+
+    /// ゴミ箱内のToDo一覧を取得（削除日時の新しい順）
+    pub async fn find_deleted_by_user_id(
+        &self,
+        user_id: Uuid,
+        page: i64,
+        per_page: i64,
+    ) -> AppResult<(Vec<Todo>, i64)> {
+        let total = sqlx::query_scalar::<_, i64>(
+            "select count(*) from todos where user_id = $1 and deleted_at is not null",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let todos = sqlx::query_as::<_, Todo>(
+            r#"
+            select *
+              from todos
+             where user_id = $1
+               and deleted_at is not null
+             order by deleted_at desc
+             limit $2 offset $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(per_page)
+        .bind((page - 1) * per_page)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok((todos, total))
+    }
+
+    /// ゴミ箱からToDoを復元
+    pub async fn restore_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<Todo>> {
+        let todo = sqlx::query_as::<_, Todo>(
+            r#"
+            update todos
+            set deleted_at = null, updated_at = now(), version = version + 1
+            where id = $1 and user_id = $2 and deleted_at is not null
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(executor)
+        .await?;
+
         Ok(todo)
     }
-    
-    /// Todoの削除
-    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+
+    /// ゴミ箱内のToDoを完全に削除
+    pub async fn purge(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let mut tx = self.begin(user_id).await?;
         let result = sqlx::query(
-            "delete from todos where id = $1 and user_id = $2",
+            "delete from todos where id = $1 and user_id = $2 and deleted_at is not null",
         )
         .bind(id)
         .bind(user_id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        
+        tx.commit().await?;
+
         Ok(result.rows_affected() > 0)
     }
+
+    /// ユーザーのゴミ箱を空にする
+    pub async fn purge_all_by_user_id(&self, user_id: Uuid) -> AppResult<u64> {
+        let mut tx = self.begin(user_id).await?;
+        let result = sqlx::query("delete from todos where user_id = $1 and deleted_at is not null")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 指定日時より前にゴミ箱へ移動されたToDoがあるユーザーを取得
+    /// -> 定期ジョブで使用
+    pub async fn find_user_ids_with_trash_before(&self, cutoff: DateTime<Utc>) -> AppResult<Vec<Uuid>> {
+        let user_ids = sqlx::query_scalar::<_, Uuid>(
+            "select distinct user_id from todos where deleted_at < $1",
+        )
+        .bind(cutoff)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// 指定日時より前にゴミ箱へ移動されたユーザーのToDoを完全に削除
+    pub async fn purge_deleted_before(&self, user_id: Uuid, cutoff: DateTime<Utc>) -> AppResult<u64> {
+        let mut tx = self.begin(user_id).await?;
+        let result = sqlx::query("delete from todos where user_id = $1 and deleted_at < $2")
+            .bind(user_id)
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// ユーザーの全ToDoを並び順で取得（ボード表示用）
+    pub async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Todo>> {
+        let todos = sqlx::query_as::<_, Todo>(
+            r#"
+            select *
+              from todos
+             where user_id = $1
+               and deleted_at is null
+             order by position, id
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(todos)
+    }
+
+    /// アジェンダに表示する未完了のToDoを取得（期限か未来の開始日があるもの）
+    /// 期限、期限がなければ開始日の順に並べる
+    pub async fn find_agenda_by_user_id(
+        &self,
+        user_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> AppResult<Vec<Todo>> {
+        let todos = sqlx::query_as::<_, Todo>(
+            r#"
+            select *
+              from todos
+             where user_id = $1
+               and deleted_at is null
+               and status <> 'completed'
+               and (due_date is not null or start_date > $2)
+             order by coalesce(due_date, start_date), position, id
+            "#,
+        )
+        .bind(user_id)
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(todos)
+    }
+
+    /// ToDoの一覧が変わると変化する値（CalDAVのctag）
+    /// 削除・復元でも変わるよう、件数と最終更新・削除日時から作る
+    pub async fn collection_tag(&self, user_id: Uuid) -> AppResult<String> {
+        let tag = sqlx::query_scalar::<_, String>(
+            r#"
+            select count(*) filter (where deleted_at is null)::text
+                   || '-' || coalesce(
+                       extract(epoch from max(greatest(updated_at, deleted_at)))::text, '0'
+                   )
+              from todos
+             where user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    /// ボードの列へ移動（ステータスは列のカテゴリになる）
+    /// ランクキーを指定しない場合は現在の並び順を維持する
+    pub async fn move_to_column(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+        column_id: Uuid,
+        status: &TodoStatus,
+        position: Option<&str>,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<Option<Todo>> {
+        let todo = sqlx::query_as::<_, Todo>(
+            r#"
+            update todos
+            set column_id = $3, status = $4, position = coalesce($5, position),
+                completed_at = case when $4 = 'completed'::todo_status
+                                    then coalesce(completed_at, now()) end,
+                updated_at = now(), version = version + 1
+            where id = $1 and user_id = $2 and deleted_at is null
+              and ($6::integer[] is null or version = any($6))
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(column_id)
+        .bind(status)
+        .bind(position)
+        .bind(expected_versions)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(todo)
+    }
+
+    /// ユーザーの末尾のランクキーを取得（ゴミ箱内のToDoも含む）
+    /// 追加するToDoと同じトランザクションで、ユーザーのロックを取得してから呼び出す
+    pub async fn last_position_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        user_id: Uuid,
+    ) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, Option<String>>(
+            "select max(position) from todos where user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// 指定したキーの直後にあるToDoのランクキーを取得
+    pub async fn next_position(
+        &self,
+        user_id: Uuid,
+        position: &str,
+        exclude_id: Uuid,
+    ) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, String>(
+            r#"
+            select position
+              from todos
+             where user_id = $1
+               and deleted_at is null
+               and position > $2
+               and id <> $3
+             order by position
+             limit 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(position)
+        .bind(exclude_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// 指定したキーの直前にあるToDoのランクキーを取得
+    pub async fn prev_position(
+        &self,
+        user_id: Uuid,
+        position: &str,
+        exclude_id: Uuid,
+    ) -> AppResult<Option<String>> {
+        let position = sqlx::query_scalar::<_, String>(
+            r#"
+            select position
+              from todos
+             where user_id = $1
+               and deleted_at is null
+               and position < $2
+               and id <> $3
+             order by position desc
+             limit 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(position)
+        .bind(exclude_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// ランクキーを更新（並び替え）
+    pub async fn update_position(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        position: &str,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<Option<Todo>> {
+        let todo = sqlx::query_as::<_, Todo>(
+            r#"
+            update todos
+            set position = $3, updated_at = now(), version = version + 1
+            where id = $1 and user_id = $2 and deleted_at is null
+              and ($4::integer[] is null or version = any($4))
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(position)
+        .bind(expected_versions)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(todo)
+    }
+
+    /// 指定した長さを超えるランクキーを持つユーザーを取得
+    pub async fn find_user_ids_with_long_positions(&self, max_length: usize) -> AppResult<Vec<Uuid>> {
+        let user_ids = sqlx::query_scalar::<_, Uuid>(
+            "select distinct user_id from todos where length(position) > $1",
+        )
+        .bind(max_length as i32)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// ユーザーの全ToDoのランクキーを現在の順序のまま等間隔に振り直す
+    /// 並び順は変わらないため、バージョンは更新しない
+    pub async fn rebalance_positions(&self, user_id: Uuid) -> AppResult<u64> {
+        let mut tx = self.begin(user_id).await?;
+        let rebalanced = Self::rebalance_positions_with(&mut tx, user_id).await?;
+        tx.commit().await?;
+
+        Ok(rebalanced)
+    }
+
+    /// 指定したトランザクション内でランクキーを振り直す（コミットは呼び出し元で行う）
+    pub async fn rebalance_positions_with(conn: &mut PgConnection, user_id: Uuid) -> AppResult<u64> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            select id
+              from todos
+             where user_id = $1
+             order by position, id
+             for update
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&mut *conn)
+        .await?;
+        let positions = rank::spread(ids.len());
+
+        let result = sqlx::query(
+            r#"
+            update todos
+            set position = ranked.position
+            from unnest($1::uuid[], $2::text[]) as ranked(id, position)
+            where todos.id = ranked.id
+            "#,
+        )
+        .bind(&ids)
+        .bind(&positions)
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// ユーザーのToDoを変更するトランザクションを開始
+    /// 行ロックより先にユーザーのロックを取得する（`lock_user` を参照）
+    pub async fn begin(&self, user_id: Uuid) -> AppResult<Transaction<'static, Postgres>> {
+        let mut tx = self.pool.begin().await?;
+        Self::lock_user(&mut tx, user_id).await?;
+
+        Ok(tx)
+    }
+
+    /// ユーザーのToDoへの変更を直列化するロックを取得（トランザクションの終了まで保持）
+    /// `sync_seq` の採番トリガーも同じロックを取得するため、ToDoや列の行ロックより先に取得して
+    /// すべての経路でロックの順序を揃える（順序が異なるとデッドロックになる）
+    pub async fn lock_user(conn: &mut PgConnection, user_id: Uuid) -> AppResult<()> {
+        sqlx::query("select pg_advisory_xact_lock(hashtextextended($1::text, 0))")
+            .bind(user_id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 一覧取得と同じ条件に一致するToDoのIDを全件取得（ページネーションなし）
+    pub async fn find_ids_by_filter(
+        &self,
+        user_id: Uuid,
+        query: &TodoQuery,
+        filter: Option<&FilterExpr>,
+    ) -> AppResult<Vec<Uuid>> {
+        let mut builder = QueryBuilder::<Postgres>::new("select id from todos");
+        Self::push_filters(&mut builder, user_id, query, filter);
+        builder.push(" order by created_at");
+
+        let ids = builder
+            .build_query_scalar::<Uuid>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ids)
+    }
+
+    /// 対象のToDoを行ロック付きで取得（一括操作の変更前状態）
+    pub async fn lock_by_ids(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        ids: &[Uuid],
+    ) -> AppResult<Vec<Todo>> {
+        let todos = sqlx::query_as::<_, Todo>(
+            r#"
+            select *
+              from todos
+             where user_id = $1
+               and id = any($2)
+               and deleted_at is null
+             for update
+            "#,
+        )
+        .bind(user_id)
+        .bind(ids)
+        .fetch_all(conn)
+        .await?;
+
+        Ok(todos)
+    }
+
+    /// 更新前のToDoをロックして取得（ゴミ箱内も含む）
+    pub async fn lock_by_id(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<Todo>> {
+        let todo = sqlx::query_as::<_, Todo>(
+            "select * from todos where id = $1 and user_id = $2 for update",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(todo)
+    }
+
+    /// 複数のToDoに同じ操作を適用
+    pub async fn bulk_update(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        ids: &[Uuid],
+        action: &BulkTodoAction,
+    ) -> AppResult<Vec<Todo>> {
+        let mut builder = QueryBuilder::<Postgres>::new("update todos set ");
+
+        match action {
+            BulkTodoAction::SetStatus { status } => {
+                builder
+                    .push("status = ")
+                    .push_bind(status)
+                    .push(", column_id = case when status = ")
+                    .push_bind(status)
+                    .push(" then column_id end, completed_at = case when ")
+                    .push_bind(status)
+                    .push(" = 'completed'::todo_status then coalesce(completed_at, now()) end");
+            }
+            BulkTodoAction::SetPriority { priority } => {
+                builder.push("priority = ").push_bind(priority);
+            }
+            BulkTodoAction::SetDueDate { due_date } => {
+                builder.push("due_date = ").push_bind(due_date);
+            }
+            BulkTodoAction::AddTag { tag } => {
+                builder
+                    .push("tags = case when ")
+                    .push_bind(tag.trim())
+                    .push(" = any(tags) then tags else array_append(tags, ")
+                    .push_bind(tag.trim())
+                    .push(") end");
+            }
+            BulkTodoAction::RemoveTag { tag } => {
+                builder.push("tags = array_remove(tags, ").push_bind(tag.trim()).push(")");
+            }
+            BulkTodoAction::Delete => {
+                builder.push("deleted_at = now()");
+            }
+        }
+        if !matches!(action, BulkTodoAction::Delete) {
+            builder.push(", updated_at = now()");
+        }
+
+        builder
+            .push(", version = version + 1 where user_id = ")
+            .push_bind(user_id)
+            .push(" and id = any(")
+            .push_bind(ids)
+            .push(") and deleted_at is null returning *");
+
+        let todos = builder.build_query_as::<Todo>().fetch_all(conn).await?;
+
+        Ok(todos)
+    }
 }
@@ -1,7 +1,10 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::{error::AppResult, models::user::User};
+use crate::{
+    error::AppResult,
+    models::user::{User, WeekDay},
+};
 
 #[derive(Clone)]
 pub struct UserRepository {
@@ -60,4 +63,32 @@ impl UserRepository {
         .await?;
         Ok(user)
     }
+
+    /// プロフィールの更新
+    pub async fn update_profile(
+        &self,
+        id: Uuid,
+        display_name: Option<&str>,
+        time_zone: &str,
+        locale: &str,
+        week_start: WeekDay,
+    ) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            update users
+               set display_name = $2, time_zone = $3, locale = $4, week_start = $5,
+                   updated_at = now()
+             where id = $1
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(display_name)
+        .bind(time_zone)
+        .bind(locale)
+        .bind(week_start)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(user)
+    }
 }
@@ -0,0 +1,383 @@
+use serde_json::Value;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::{
+        todo_history::TodoEvent,
+        webhook::{Webhook, WebhookDelivery, WebhookDeliveryStatus},
+    },
+};
+
+#[derive(Clone)]
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn begin(&self) -> AppResult<Transaction<'static, Postgres>> {
+        Ok(self.pool.begin().await?)
+    }
+
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        url: &str,
+        description: Option<&str>,
+        events: &[String],
+        secret: &str,
+        active: bool,
+    ) -> AppResult<Webhook> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            insert into webhooks (user_id, url, description, events, secret, active)
+            values ($1, $2, $3, $4, $5, $6)
+            returning *
+            "#,
+        )
+        .bind(user_id)
+        .bind(url)
+        .bind(description)
+        .bind(events)
+        .bind(secret)
+        .bind(active)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    /// Webhook一覧（作成日時順）
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Webhook>> {
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            r#"
+            select *
+              from webhooks
+             where user_id = $1
+             order by created_at, id
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid, user_id: Uuid) -> AppResult<Option<Webhook>> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            "select * from webhooks where id = $1 and user_id = $2",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    /// 配信ジョブ用：ユーザーを問わずIDで取得
+    pub async fn find_by_ids(&self, ids: &[Uuid]) -> AppResult<Vec<Webhook>> {
+        let webhooks = sqlx::query_as::<_, Webhook>("select * from webhooks where id = any($1)")
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(webhooks)
+    }
+
+    /// 有効にした場合は連続失敗回数と自動無効化の日時をリセットする
+    pub async fn update(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        url: &str,
+        description: Option<&str>,
+        events: &[String],
+        active: bool,
+    ) -> AppResult<Option<Webhook>> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            update webhooks
+               set url = $3
+                 , description = $4
+                 , events = $5
+                 , active = $6
+                 , consecutive_failures = case when $6 then 0 else consecutive_failures end
+                 , disabled_at = case when $6 then null else disabled_at end
+                 , updated_at = now()
+             where id = $1
+               and user_id = $2
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(url)
+        .bind(description)
+        .bind(events)
+        .bind(active)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            delete from webhooks
+             where id = $1
+               and user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 配信成功時に連続失敗回数をリセット
+    pub async fn record_success(&self, id: Uuid) -> AppResult<Option<Webhook>> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            update webhooks
+               set consecutive_failures = 0
+             where id = $1
+            returning *
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    /// 配信失敗時に連続失敗回数を加算し、`disable_after` 回に達したら無効にする
+    pub async fn record_failure(&self, id: Uuid, disable_after: i32) -> AppResult<Option<Webhook>> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            update webhooks
+               set consecutive_failures = consecutive_failures + 1
+                 , active = active and consecutive_failures + 1 < $2
+                 , disabled_at = case
+                       when active and consecutive_failures + 1 >= $2 then now()
+                       else disabled_at
+                   end
+             where id = $1
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(disable_after)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    /// 配信を登録していない履歴を取得し、登録済みにする
+    pub async fn claim_pending_events_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        limit: i64,
+    ) -> AppResult<Vec<TodoEvent>> {
+        let events = sqlx::query_as::<_, TodoEvent>(
+            r#"
+            update todo_history h
+               set webhooks_dispatched_at = now()
+              from todos t
+             where t.id = h.todo_id
+               and h.id in (
+                   select id
+                     from todo_history
+                    where webhooks_dispatched_at is null
+                    order by seq
+                    limit $1
+                      for update skip locked
+               )
+            returning h.seq, h.id, h.todo_id, t.user_id, h.actor_id, h.version, h.action
+                    , h.changes, h.snapshot, h.created_at
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(events)
+    }
+
+    /// ユーザーの有効なWebhook
+    pub async fn find_active_by_user_ids_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        user_ids: &[Uuid],
+    ) -> AppResult<Vec<Webhook>> {
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            r#"
+            select *
+              from webhooks
+             where user_id = any($1)
+               and active
+             order by created_at, id
+            "#,
+        )
+        .bind(user_ids)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    pub async fn create_delivery_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        webhook_id: Uuid,
+        event: &str,
+        payload: &Value,
+        redelivery_of: Option<Uuid>,
+    ) -> AppResult<WebhookDelivery> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            insert into webhook_deliveries (webhook_id, event, payload, redelivery_of)
+            values ($1, $2, $3, $4)
+            returning *
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(event)
+        .bind(payload)
+        .bind(redelivery_of)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    /// 送信予定日時を過ぎた配信を取得し、`lease_seconds` 秒の間は他のジョブが取得しないようにする
+    pub async fn claim_due_deliveries(
+        &self,
+        limit: i64,
+        lease_seconds: f64,
+    ) -> AppResult<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            update webhook_deliveries
+               set next_attempt_at = now() + make_interval(secs => $2)
+             where id in (
+                   select d.id
+                     from webhook_deliveries d
+                     join webhooks w on w.id = d.webhook_id
+                    where d.status = 'pending'
+                      and d.next_attempt_at <= now()
+                      and w.active
+                    order by d.next_attempt_at
+                    limit $1
+                      for update of d skip locked
+               )
+            returning *
+            "#,
+        )
+        .bind(limit)
+        .bind(lease_seconds)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// 送信結果を記録
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_attempt(
+        &self,
+        id: Uuid,
+        status: WebhookDeliveryStatus,
+        retry_after_seconds: f64,
+        response_status: Option<i32>,
+        response_body: Option<&str>,
+        error: Option<&str>,
+        duration_ms: i32,
+    ) -> AppResult<WebhookDelivery> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            update webhook_deliveries
+               set status = $2
+                 , attempts = attempts + 1
+                 , next_attempt_at = now() + make_interval(secs => $3)
+                 , response_status = $4
+                 , response_body = $5
+                 , error = $6
+                 , duration_ms = $7
+                 , delivered_at = case when $2 = 'succeeded' then now() else delivered_at end
+             where id = $1
+            returning *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(retry_after_seconds)
+        .bind(response_status)
+        .bind(response_body)
+        .bind(error)
+        .bind(duration_ms)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    /// 無効になったWebhookの未送信の配信を失敗にする
+    pub async fn fail_pending_deliveries(&self, webhook_id: Uuid, error: &str) -> AppResult<u64> {
+        let result = sqlx::query(
+            r#"
+            update webhook_deliveries
+               set status = 'failed'
+                 , error = $2
+             where webhook_id = $1
+               and status = 'pending'
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 配信ログ（新しい順）
+    pub async fn find_deliveries(&self, webhook_id: Uuid, limit: i64) -> AppResult<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            select *
+              from webhook_deliveries
+             where webhook_id = $1
+             order by created_at desc, id
+             limit $2
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    pub async fn find_delivery(&self, id: Uuid, webhook_id: Uuid) -> AppResult<Option<WebhookDelivery>> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            "select * from webhook_deliveries where id = $1 and webhook_id = $2",
+        )
+        .bind(id)
+        .bind(webhook_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(delivery)
+    }
+}
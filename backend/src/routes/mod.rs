@@ -2,16 +2,25 @@
 
 use axum::{
     middleware,
-    routing::{get, patch, post},
+    routing::{any, delete, get, patch, post, put},
     Router,
 };
 
 use crate::{
-    handlers::{auth, todo},
-    middleware::auth::require_auth,
+    handlers::{
+        agenda, auth, board, caldav, calendar, event, saved_view, stats, sync, template,
+        time_entry, todo, transfer, webhook,
+    },
+    middleware::auth::{require_app_password, require_auth},
     AppState,
 };
 
+pub fn agenda_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(agenda::get_agenda))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
 pub fn auth_routes(state: AppState) -> Router<AppState> {
     let public = Router::new()
         .route("/register", post(auth::register))
@@ -21,18 +30,157 @@ pub fn auth_routes(state: AppState) -> Router<AppState> {
 
     let protected = Router::new()
         .route("/me", get(auth::me))
+        .route("/me/profile", get(auth::get_profile).put(auth::update_profile))
+        .route(
+            "/app-passwords",
+            get(auth::list_app_passwords).post(auth::create_app_password),
+        )
+        .route("/app-passwords/{id}", delete(auth::delete_app_password))
         .layer(middleware::from_fn_with_state(state, require_auth));
 
     public.merge(protected)
 }
 
+pub fn board_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(board::get_board))
+        .route("/columns", get(board::list_columns).post(board::create_column))
+        .route(
+            "/columns/{id}",
+            put(board::update_column).delete(board::delete_column),
+        )
+        .route("/columns/{id}/move", post(board::move_column))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
+/// CalDAV（PROPFIND / REPORT などの独自メソッドを扱うため `any` で受ける）
+/// 末尾のスラッシュの有無を区別するため、`nest` せずに完全なパスで定義する
+pub fn caldav_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/caldav", any(caldav::root))
+        .route("/caldav/", any(caldav::root))
+        .route("/caldav/{user_id}", any(caldav::principal))
+        .route("/caldav/{user_id}/", any(caldav::principal))
+        .route("/caldav/{user_id}/todos", any(caldav::collection))
+        .route("/caldav/{user_id}/todos/", any(caldav::collection))
+        .route("/caldav/{user_id}/todos/{name}", any(caldav::object))
+        .layer(middleware::from_fn_with_state(state, require_app_password))
+}
+
+pub fn calendar_routes(state: AppState) -> Router<AppState> {
+    let public = Router::new().route("/feed/{token}", get(calendar::feed));
+
+    let protected = Router::new()
+        .route(
+            "/token",
+            get(calendar::get_token)
+                .post(calendar::regenerate_token)
+                .delete(calendar::revoke_token),
+        )
+        .layer(middleware::from_fn_with_state(state, require_auth));
+
+    public.merge(protected)
+}
+
+pub fn event_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(event::stream))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
+pub fn sync_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(sync::pull).post(sync::push))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
+pub fn view_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(saved_view::list).post(saved_view::create))
+        .route(
+            "/{id}",
+            get(saved_view::get_by_id)
+                .put(saved_view::update)
+                .delete(saved_view::delete),
+        )
+        .route("/{id}/move", post(saved_view::move_view))
+        .route("/{id}/todos", get(saved_view::todos))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
+pub fn stats_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(stats::get_stats))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
+pub fn template_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(template::list).post(template::create))
+        .route(
+            "/{id}",
+            get(template::get_by_id)
+                .put(template::update)
+                .delete(template::delete),
+        )
+        .route("/{id}/instantiate", post(template::instantiate))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
+pub fn time_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/report", get(time_entry::report))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
 pub fn todo_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/", get(todo::list).post(todo::create))
+        .route("/quick", post(todo::quick_add))
+        .route("/export", get(transfer::export))
+        .route("/export.ics", get(calendar::export))
+        .route("/import", post(transfer::import))
+        .route("/import/{source}", post(transfer::import_external))
+        .route("/bulk", post(todo::bulk))
+        .route("/trash", get(todo::list_trash).delete(todo::empty_trash))
+        .route("/trash/{id}", delete(todo::purge))
         .route(
             "/{id}",
-            get(todo::get_by_id).put(todo::update).delete(todo::delete),
+            get(todo::get_by_id)
+                .put(todo::update)
+                .patch(todo::patch)
+                .delete(todo::delete),
         )
         .route("/{id}/status", patch(todo::update_status))
+        .route("/{id}/move", post(todo::move_todo))
+        .route("/{id}/blockers", post(todo::add_blocker))
+        .route("/{id}/blockers/{blocker_id}", delete(todo::remove_blocker))
+        .route("/{id}/timer/start", post(time_entry::start_timer))
+        .route("/{id}/timer/stop", post(time_entry::stop_timer))
+        .route(
+            "/{id}/time-entries",
+            get(time_entry::list).post(time_entry::create),
+        )
+        .route("/{id}/time-entries/{entry_id}", delete(time_entry::delete))
+        .route("/{id}/restore", post(todo::restore))
+        .route("/{id}/history", get(todo::history))
+        .route("/{id}/history/{version}/revert", post(todo::revert))
+        .layer(middleware::from_fn_with_state(state, require_auth))
+}
+
+pub fn webhook_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(webhook::list).post(webhook::create))
+        .route(
+            "/{id}",
+            get(webhook::get_by_id)
+                .put(webhook::update)
+                .delete(webhook::delete),
+        )
+        .route("/{id}/deliveries", get(webhook::deliveries))
+        .route(
+            "/{id}/deliveries/{delivery_id}/redeliver",
+            post(webhook::redeliver),
+        )
         .layer(middleware::from_fn_with_state(state, require_auth))
 }
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Datelike, Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::{
+        agenda::{AgendaBucket, AgendaResponse},
+        todo::Todo,
+    },
+    repositories::todo_repository::TodoRepository,
+    services::{profile_service::ProfileService, todo_service::TodoService},
+};
+
+#[derive(Clone)]
+pub struct AgendaService {
+    todo_repo: TodoRepository,
+    todo_service: TodoService,
+    profile_service: ProfileService,
+}
+
+impl AgendaService {
+    pub fn new(
+        todo_repo: TodoRepository,
+        todo_service: TodoService,
+        profile_service: ProfileService,
+    ) -> Self {
+        Self {
+            todo_repo,
+            todo_service,
+            profile_service,
+        }
+    }
+
+    /// 未完了のToDoを期限切れ・今日・明日・今週・それ以降に区分する
+    /// 日付はプロフィールのタイムゾーンで判定する
+    pub async fn get(&self, user_id: Uuid) -> AppResult<AgendaResponse> {
+        let user = self.profile_service.user(user_id).await?;
+        let now = user.now();
+        let today = now.date_naive();
+        let tomorrow = today + Duration::days(1);
+        let week_end = today
+            + Duration::days(
+                (6 - today.weekday().days_since(user.week_start.into())).into(),
+            );
+
+        let todos = self
+            .todo_repo
+            .find_agenda_by_user_id(user_id, now.to_utc())
+            .await?;
+
+        let mut buckets: [Vec<Todo>; 5] = Default::default();
+        for todo in todos {
+            let Some(at) = Self::agenda_at(&todo, now.to_utc()) else {
+                continue;
+            };
+            let date = at.with_timezone(&now.timezone()).date_naive();
+            let index = match date {
+                d if d < today => 0,
+                d if d == today => 1,
+                d if d == tomorrow => 2,
+                d if d <= week_end => 3,
+                _ => 4,
+            };
+            buckets[index].push(todo);
+        }
+
+        let [overdue, today_todos, tomorrow_todos, this_week, later] = buckets;
+        Ok(AgendaResponse {
+            date: today,
+            time_zone: user.time_zone,
+            overdue: self.bucket(overdue).await?,
+            today: self.bucket(today_todos).await?,
+            tomorrow: self.bucket(tomorrow_todos).await?,
+            this_week: self.bucket(this_week).await?,
+            later: self.bucket(later).await?,
+        })
+    }
+
+    /// アジェンダでの日時（期限、期限がなく開始日が未来なら開始日）
+    /// 開始日は期限以前のため、開始日が未来のToDoは開始日まで期限切れ・今日に表示されない
+    fn agenda_at(todo: &Todo, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        todo.due_date.or(todo.start_date.filter(|s| *s > now))
+    }
+
+    async fn bucket(&self, todos: Vec<Todo>) -> AppResult<AgendaBucket> {
+        Ok(AgendaBucket {
+            count: todos.len(),
+            todos: self.todo_service.responses(todos).await?,
+        })
+    }
+}
@@ -0,0 +1,76 @@
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::app_password::{
+        AppPasswordResponse, CreateAppPasswordRequest, CreatedAppPasswordResponse,
+    },
+    repositories::{
+        app_password_repository::AppPasswordRepository, user_repository::UserRepository,
+    },
+    services::auth_service::AuthService,
+};
+
+#[derive(Clone)]
+pub struct AppPasswordService {
+    app_password_repo: AppPasswordRepository,
+    user_repo: UserRepository,
+}
+
+impl AppPasswordService {
+    pub fn new(app_password_repo: AppPasswordRepository, user_repo: UserRepository) -> Self {
+        Self {
+            app_password_repo,
+            user_repo,
+        }
+    }
+
+    /// アプリパスワード一覧を取得
+    pub async fn list(&self, user_id: Uuid) -> AppResult<Vec<AppPasswordResponse>> {
+        let passwords = self.app_password_repo.find_by_user_id(user_id).await?;
+
+        Ok(passwords.into_iter().map(|p| p.into()).collect())
+    }
+
+    /// アプリパスワードを発行
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        req: CreateAppPasswordRequest,
+    ) -> AppResult<CreatedAppPasswordResponse> {
+        let password = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let stored = self
+            .app_password_repo
+            .create(user_id, req.name.trim(), &AuthService::hash_token(&password))
+            .await?;
+
+        Ok(CreatedAppPasswordResponse {
+            app_password: stored.into(),
+            password,
+        })
+    }
+
+    /// アプリパスワードを無効化
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
+        if !self.app_password_repo.delete(id, user_id).await? {
+            return Err(AppError::NotFound("App password not found".into()));
+        }
+        Ok(())
+    }
+
+    /// メールアドレスとアプリパスワードで認証
+    pub async fn authenticate(&self, email: &str, password: &str) -> AppResult<Uuid> {
+        let user = self
+            .user_repo
+            .find_by_email(email)
+            .await?
+            .ok_or_else(|| AppError::Auth("Invalid email or app password".into()))?;
+
+        self.app_password_repo
+            .touch_by_hash(user.id, &AuthService::hash_token(password))
+            .await?
+            .ok_or_else(|| AppError::Auth("Invalid email or app password".into()))?;
+
+        Ok(user.id)
+    }
+}
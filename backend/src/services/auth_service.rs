@@ -152,9 +152,9 @@ impl AuthService {
         Ok((auth_response, refresh_token_raw))
     }
 
-    /// リフレッシュトークンをSHA256でハッシュ化
+    /// トークン（リフレッシュトークン・カレンダー購読トークン）をSHA256でハッシュ化
     /// セキュリティの観点からDBにはハッシュ値を保存する
-    fn hash_token(token: &str) -> String {
+    pub fn hash_token(token: &str) -> String {
         let mut hasher = Sha256::new();
         hasher.update(token.as_bytes());
         format!("{:x}", hasher.finalize())
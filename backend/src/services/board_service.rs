@@ -0,0 +1,247 @@
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        board::{
+            BoardColumn, BoardColumnResponse, BoardColumnWithTodos, BoardResponse,
+            CreateBoardColumnRequest, MoveBoardColumnRequest, UpdateBoardColumnRequest,
+        },
+    },
+    repositories::{
+        board_column_repository::BoardColumnRepository, todo_repository::TodoRepository,
+    },
+    services::{rank, todo_service::TodoService},
+};
+
+#[derive(Clone)]
+pub struct BoardService {
+    column_repo: BoardColumnRepository,
+    todo_repo: TodoRepository,
+    todo_service: TodoService,
+}
+
+impl BoardService {
+    pub fn new(
+        column_repo: BoardColumnRepository,
+        todo_repo: TodoRepository,
+        todo_service: TodoService,
+    ) -> Self {
+        Self {
+            column_repo,
+            todo_repo,
+            todo_service,
+        }
+    }
+
+    /// ボード（列と列ごとのToDo）を取得
+    /// 列未指定のToDoはステータスに対応する先頭の列に表示する
+    pub async fn board(&self, user_id: Uuid) -> AppResult<BoardResponse> {
+        let columns = self.columns(user_id).await?;
+        let todos = self.todo_repo.find_all_by_user_id(user_id).await?;
+        let todos = self.todo_service.responses(todos).await?;
+
+        let mut board: Vec<BoardColumnWithTodos> = columns
+            .into_iter()
+            .map(|column| BoardColumnWithTodos {
+                column: column.into(),
+                todos: Vec::new(),
+            })
+            .collect();
+
+        for todo in todos {
+            let index = todo
+                .column_id
+                .and_then(|id| board.iter().position(|c| c.column.id == id))
+                .or_else(|| board.iter().position(|c| c.column.category == todo.status));
+            if let Some(index) = index {
+                board[index].todos.push(todo);
+            }
+        }
+
+        Ok(BoardResponse { columns: board })
+    }
+
+    /// 列一覧を取得
+    pub async fn list_columns(&self, user_id: Uuid) -> AppResult<Vec<BoardColumnResponse>> {
+        let columns = self.columns(user_id).await?;
+
+        Ok(columns.into_iter().map(|c| c.into()).collect())
+    }
+
+    /// 列の作成（末尾に追加）
+    pub async fn create_column(
+        &self,
+        user_id: Uuid,
+        req: CreateBoardColumnRequest,
+    ) -> AppResult<BoardColumnResponse> {
+        self.columns(user_id).await?;
+        self.check_name(user_id, &req.name, None).await?;
+
+        let last = self.column_repo.last_position(user_id).await?;
+        let position = match rank::between(last.as_deref(), None) {
+            Some(position) => position,
+            None => {
+                self.column_repo.rebalance_positions(user_id).await?;
+                let last = self.column_repo.last_position(user_id).await?;
+                rank::between(last.as_deref(), None)
+                    .ok_or_else(|| AppError::Internal("Failed to compute position".into()))?
+            }
+        };
+
+        let column = self
+            .column_repo
+            .create(user_id, &req.name, &req.category, &position, req.wip_limit)
+            .await?;
+
+        Ok(column.into())
+    }
+
+    /// 列の更新（名前・WIP制限）
+    pub async fn update_column(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: UpdateBoardColumnRequest,
+    ) -> AppResult<BoardColumnResponse> {
+        self.find_column(id, user_id).await?;
+        self.check_name(user_id, &req.name, Some(id)).await?;
+
+        let column = self
+            .column_repo
+            .update(id, user_id, &req.name, req.wip_limit)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Column not found".into()))?;
+
+        Ok(column.into())
+    }
+
+    /// 列の並び替え
+    pub async fn move_column(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: MoveBoardColumnRequest,
+    ) -> AppResult<BoardColumnResponse> {
+        if req.before.is_none() && req.after.is_none() {
+            return Err(AppError::Validation("Specify before or after".into()));
+        }
+        if req.before == Some(id) || req.after == Some(id) {
+            return Err(AppError::Validation(
+                "Cannot move a column relative to itself".into(),
+            ));
+        }
+        self.find_column(id, user_id).await?;
+
+        let position = match self.position_between(id, user_id, &req).await? {
+            Some(position) => position,
+            None => {
+                // キーの間に空きがない場合は振り直してから再計算
+                self.column_repo.rebalance_positions(user_id).await?;
+                self.position_between(id, user_id, &req)
+                    .await?
+                    .ok_or_else(|| AppError::Conflict("Failed to compute position".into()))?
+            }
+        };
+
+        let column = self
+            .column_repo
+            .update_position(id, user_id, &position)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Column not found".into()))?;
+
+        Ok(column.into())
+    }
+
+    /// 列の削除
+    /// 列内のToDoはステータスに対応する先頭の列に移る
+    /// カテゴリの最後の列は削除できない
+    pub async fn delete_column(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
+        let column = self.find_column(id, user_id).await?;
+
+        let count = self
+            .column_repo
+            .count_by_category(user_id, &column.category)
+            .await?;
+        if count <= 1 {
+            return Err(AppError::Conflict(
+                "Cannot delete the last column of a status category".into(),
+            ));
+        }
+
+        let deleted = self.column_repo.delete(id, user_id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Column not found".into()));
+        }
+        Ok(())
+    }
+
+    /// 列一覧を取得（列が1つもない場合はデフォルト列を作成）
+    async fn columns(&self, user_id: Uuid) -> AppResult<Vec<BoardColumn>> {
+        let columns = self.column_repo.find_by_user_id(user_id).await?;
+        if !columns.is_empty() {
+            return Ok(columns);
+        }
+
+        self.column_repo.create_defaults(user_id).await?;
+        self.column_repo.find_by_user_id(user_id).await
+    }
+
+    /// 認可チェック付きで列を取得
+    async fn find_column(&self, id: Uuid, user_id: Uuid) -> AppResult<BoardColumn> {
+        self.column_repo
+            .find_by_id_and_user_id(id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Column not found".into()))
+    }
+
+    /// 同名の列がないことを確認
+    async fn check_name(&self, user_id: Uuid, name: &str, exclude_id: Option<Uuid>) -> AppResult<()> {
+        match self.column_repo.find_by_name(user_id, name).await? {
+            Some(column) if Some(column.id) != exclude_id => {
+                Err(AppError::Conflict("Column name already exists".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 移動先の前後の列からランクキーを生成
+    /// キーの間に空きがない場合は `None`
+    async fn position_between(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: &MoveBoardColumnRequest,
+    ) -> AppResult<Option<String>> {
+        let after = match req.after {
+            Some(after) => Some(self.find_column(after, user_id).await?.position),
+            None => None,
+        };
+        let before = match req.before {
+            Some(before) => Some(self.find_column(before, user_id).await?.position),
+            None => None,
+        };
+
+        let (lower, upper) = match (after, before) {
+            (Some(after), Some(before)) => {
+                if after > before {
+                    return Err(AppError::Validation(
+                        "The after column must be positioned before the before column".into(),
+                    ));
+                }
+                (Some(after), Some(before))
+            }
+            (Some(after), None) => {
+                let next = self.column_repo.next_position(user_id, &after, id).await?;
+                (Some(after), next)
+            }
+            (None, Some(before)) => {
+                let prev = self.column_repo.prev_position(user_id, &before, id).await?;
+                (prev, Some(before))
+            }
+            (None, None) => (None, None),
+        };
+
+        Ok(rank::between(lower.as_deref(), upper.as_deref()))
+    }
+}
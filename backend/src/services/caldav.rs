@@ -0,0 +1,215 @@
+//! CalDAV（RFC 4791）/ WebDAV（RFC 4918）のXML
+//!
+//! - PROPFIND / REPORT のリクエストから要求されたプロパティ・href・コンポーネントを取り出す
+//! - レスポンスは `multistatus` として組み立てる
+
+use quick_xml::{events::Event, name::ResolveResult, NsReader};
+
+pub const DAV: &str = "DAV:";
+pub const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+pub const CALENDARSERVER: &str = "http://calendarserver.org/ns/";
+
+/// 名前空間付きの要素名
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropName {
+    pub namespace: String,
+    pub name: String,
+}
+
+impl PropName {
+    pub fn new(namespace: &str, name: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    pub fn is(&self, namespace: &str, name: &str) -> bool {
+        self.namespace == namespace && self.name == name
+    }
+}
+
+/// PROPFIND / REPORT のリクエスト
+#[derive(Debug, Default, PartialEq)]
+pub struct DavRequest {
+    /// ルート要素（`propfind` / `calendar-query` / `calendar-multiget` など、本文がない場合は `None`）
+    pub root: Option<PropName>,
+    /// 要求されたプロパティ（空の場合は `allprop` として扱う）
+    pub props: Vec<PropName>,
+    pub hrefs: Vec<String>,
+    /// `comp-filter` で指定されたコンポーネント名（`VCALENDAR` を除く）
+    pub components: Vec<String>,
+}
+
+/// リクエストの本文を読み取る
+pub fn parse_request(body: &str) -> Result<DavRequest, String> {
+    let mut request = DavRequest::default();
+    if body.trim().is_empty() {
+        return Ok(request);
+    }
+
+    let mut reader = NsReader::from_str(body);
+    reader.config_mut().trim_text(true);
+    let mut stack: Vec<PropName> = Vec::new();
+
+    loop {
+        let (namespace, event) = reader
+            .read_resolved_event()
+            .map_err(|e| format!("Invalid XML: {}", e))?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let name = PropName {
+                    namespace: match namespace {
+                        ResolveResult::Bound(ns) => String::from_utf8_lossy(ns.as_ref()).into_owned(),
+                        _ => String::new(),
+                    },
+                    name: String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+                };
+
+                if request.root.is_none() {
+                    request.root = Some(name.clone());
+                }
+                if stack.last().is_some_and(|p| p.is(DAV, "prop")) {
+                    request.props.push(name.clone());
+                }
+                if name.is(CALDAV, "comp-filter") {
+                    for attr in e.attributes().flatten() {
+                        if attr.key.local_name().as_ref() == b"name" {
+                            let value = attr
+                                .unescape_value()
+                                .map_err(|e| format!("Invalid XML: {}", e))?
+                                .to_ascii_uppercase();
+                            if value != "VCALENDAR" {
+                                request.components.push(value);
+                            }
+                        }
+                    }
+                }
+
+                if matches!(event, Event::Start(_)) {
+                    stack.push(name);
+                }
+            }
+            Event::End(_) => {
+                stack.pop();
+            }
+            Event::Text(ref t) if stack.last().is_some_and(|p| p.is(DAV, "href")) => {
+                let href = t.unescape().map_err(|e| format!("Invalid XML: {}", e))?;
+                request.hrefs.push(href.trim().to_string());
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(request)
+}
+
+/// `multistatus` の1件分
+#[derive(Debug, Default)]
+pub struct DavResponse {
+    pub href: String,
+    /// 見つかったプロパティと値（XML）
+    pub found: Vec<(PropName, String)>,
+    /// 対応していないプロパティ
+    pub missing: Vec<PropName>,
+    /// リソース自体が見つからない場合のステータス
+    pub status: Option<&'static str>,
+}
+
+/// `multistatus` を組み立てる
+pub fn multistatus(responses: &[DavResponse]) -> String {
+    let mut xml = String::from(concat!(
+        r#"<?xml version="1.0" encoding="utf-8"?>"#,
+        r#"<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:cs="http://calendarserver.org/ns/">"#,
+    ));
+    for response in responses {
+        xml.push_str("<d:response>");
+        xml.push_str(&format!("<d:href>{}</d:href>", escape(&response.href)));
+        if let Some(status) = response.status {
+            xml.push_str(&format!("<d:status>HTTP/1.1 {}</d:status>", status));
+        }
+        if !response.found.is_empty() {
+            xml.push_str("<d:propstat><d:prop>");
+            for (name, value) in &response.found {
+                xml.push_str(&element(name, value));
+            }
+            xml.push_str("</d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat>");
+        }
+        if !response.missing.is_empty() {
+            xml.push_str("<d:propstat><d:prop>");
+            for name in &response.missing {
+                xml.push_str(&element(name, ""));
+            }
+            xml.push_str("</d:prop><d:status>HTTP/1.1 404 Not Found</d:status></d:propstat>");
+        }
+        xml.push_str("</d:response>");
+    }
+    xml.push_str("</d:multistatus>");
+    xml
+}
+
+/// 要素を出力する（既知の名前空間は接頭辞を使う）
+fn element(name: &PropName, value: &str) -> String {
+    let (tag, declaration) = match name.namespace.as_str() {
+        DAV => (format!("d:{}", name.name), String::new()),
+        CALDAV => (format!("c:{}", name.name), String::new()),
+        CALENDARSERVER => (format!("cs:{}", name.name), String::new()),
+        namespace => (
+            format!("x:{}", name.name),
+            format!(r#" xmlns:x="{}""#, escape(namespace)),
+        ),
+    };
+
+    if value.is_empty() {
+        format!("<{}{}/>", tag, declaration)
+    } else {
+        format!("<{}{}>{}</{}>", tag, declaration, value, tag)
+    }
+}
+
+/// XMLのテキスト・属性値のエスケープ
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// パスのセグメントをパーセントエンコードする（非予約文字以外）
+pub fn percent_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// パーセントエンコードを戻す（不正な値はそのまま）
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
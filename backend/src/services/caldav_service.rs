@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        caldav::CaldavResource,
+        todo::{CreateTodoRequest, TodoPatch, TodoPriority, TodoStatus},
+    },
+    repositories::{
+        caldav_object_repository::CaldavObjectRepository, todo_repository::TodoRepository,
+    },
+    services::{ical, profile_service::ProfileService, todo_service::TodoService},
+};
+
+/// リソース名・UIDの最大文字数
+const MAX_NAME_LENGTH: usize = 255;
+
+/// PUTの結果
+pub struct CaldavPutResult {
+    pub created: bool,
+    pub version: i32,
+}
+
+/// CalDAVのタスクコレクション
+/// 変更は `TodoService` を通すため、検証・履歴はREST APIと同じになる
+#[derive(Clone)]
+pub struct CaldavService {
+    object_repo: CaldavObjectRepository,
+    todo_repo: TodoRepository,
+    todo_service: TodoService,
+    profile_service: ProfileService,
+}
+
+impl CaldavService {
+    pub fn new(
+        object_repo: CaldavObjectRepository,
+        todo_repo: TodoRepository,
+        todo_service: TodoService,
+        profile_service: ProfileService,
+    ) -> Self {
+        Self {
+            object_repo,
+            todo_repo,
+            todo_service,
+            profile_service,
+        }
+    }
+
+    /// コレクション内の全リソース（ゴミ箱のToDoを除く）
+    pub async fn resources(&self, user_id: Uuid) -> AppResult<Vec<CaldavResource>> {
+        let objects: HashMap<Uuid, _> = self
+            .object_repo
+            .find_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .map(|o| (o.todo_id, o))
+            .collect();
+        let todos = self.todo_repo.find_all_by_user_id(user_id).await?;
+
+        Ok(todos
+            .into_iter()
+            .map(|t| {
+                let object = objects.get(&t.id);
+                CaldavResource::new(t, object)
+            })
+            .collect())
+    }
+
+    /// リソース名でリソースを取得
+    pub async fn resource(&self, user_id: Uuid, name: &str) -> AppResult<Option<CaldavResource>> {
+        if let Some(object) = self.object_repo.find_by_name(user_id, name).await? {
+            let todo = self
+                .todo_repo
+                .find_by_id_and_user_id(object.todo_id, user_id)
+                .await?;
+            return Ok(todo.map(|t| CaldavResource::new(t, Some(&object))));
+        }
+
+        let Some(id) = name.strip_suffix(".ics").and_then(|n| n.parse::<Uuid>().ok()) else {
+            return Ok(None);
+        };
+        let Some(todo) = self.todo_repo.find_by_id_and_user_id(id, user_id).await? else {
+            return Ok(None);
+        };
+        let object = self.object_repo.find_by_todo_id(todo.id).await?;
+        Ok(Some(CaldavResource::new(todo, object.as_ref())))
+    }
+
+    /// コレクションのctag
+    pub async fn ctag(&self, user_id: Uuid) -> AppResult<String> {
+        self.todo_repo.collection_tag(user_id).await
+    }
+
+    /// VTODOを保存（新しい名前なら作成、既存のリソースなら置き換え）
+    /// `create_only` は `If-None-Match: *` の指定
+    pub async fn put(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        body: &str,
+        expected_versions: Option<&[i32]>,
+        create_only: bool,
+    ) -> AppResult<CaldavPutResult> {
+        let tz = self.profile_service.user(user_id).await?.tz();
+        let parsed = ical::parse_vtodo(body, &tz).map_err(AppError::Validation)?;
+        let uid = parsed
+            .uid
+            .unwrap_or_else(|| name.trim_end_matches(".ics").to_string());
+        if name.chars().count() > MAX_NAME_LENGTH || uid.chars().count() > MAX_NAME_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Resource name and UID must be at most {} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+        let existing = self.resource(user_id, name).await?;
+
+        match existing {
+            Some(_) if create_only => Err(AppError::PreconditionFailed(
+                "Resource already exists".into(),
+            )),
+            Some(resource) => {
+                let patch = json!({
+                    "title": parsed.title.unwrap_or_default(),
+                    "description": parsed.description,
+                    "startDate": parsed.start_date,
+                    "dueDate": parsed.due_date,
+                    "status": parsed.status.unwrap_or(TodoStatus::Pending),
+                    "priority": parsed.priority.unwrap_or(TodoPriority::Medium),
+                    "tags": parsed.tags,
+                });
+                let todo = self
+                    .todo_service
+                    .patch(resource.todo.id, user_id, TodoPatch::Merge(patch), expected_versions)
+                    .await?;
+                Ok(CaldavPutResult {
+                    created: false,
+                    version: todo.version,
+                })
+            }
+            None if expected_versions.is_some() => {
+                Err(AppError::PreconditionFailed("Resource not found".into()))
+            }
+            None => {
+                let req = CreateTodoRequest {
+                    title: parsed.title.unwrap_or_default(),
+                    description: parsed.description,
+                    start_date: parsed.start_date,
+                    due_date: parsed.due_date,
+                    status: parsed.status,
+                    priority: parsed.priority,
+                    tags: Some(parsed.tags),
+                    estimate_minutes: None,
+                };
+                req.validate()
+                    .map_err(|e| AppError::Validation(e.to_string()))?;
+
+                // ToDoとリソースを同じトランザクションで作成する
+                let mut tx = self.todo_repo.begin(user_id).await?;
+                let todo = self.todo_service.create_in(&mut tx, user_id, req).await?;
+                CaldavObjectRepository::create_with(&mut *tx, todo.id, user_id, name, &uid)
+                    .await
+                    .map_err(Self::map_unique_violation)?;
+                tx.commit().await?;
+
+                Ok(CaldavPutResult {
+                    created: true,
+                    version: todo.version,
+                })
+            }
+        }
+    }
+
+    /// 同じ名前のリソースが同時に作成された場合は一意制約で検出する
+    fn map_unique_violation(e: AppError) -> AppError {
+        match e {
+            AppError::Database(sqlx::Error::Database(ref db)) if db.is_unique_violation() => {
+                AppError::PreconditionFailed("Resource already exists".into())
+            }
+            e => e,
+        }
+    }
+
+    /// リソースを削除（ToDoはゴミ箱に移動する）
+    pub async fn delete(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<()> {
+        let resource = self
+            .resource(user_id, name)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Resource not found".into()))?;
+
+        self.todo_service
+            .delete(resource.todo.id, user_id, expected_versions)
+            .await
+    }
+}
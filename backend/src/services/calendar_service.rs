@@ -0,0 +1,79 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::calendar::{CalendarComponent, CalendarFeedResponse, CalendarTokenResponse},
+    repositories::{
+        calendar_token_repository::CalendarTokenRepository, todo_repository::TodoRepository,
+    },
+    services::{auth_service::AuthService, ical},
+};
+
+/// カレンダーの名前
+const CALENDAR_NAME: &str = "ToDo";
+
+#[derive(Clone)]
+pub struct CalendarService {
+    token_repo: CalendarTokenRepository,
+    todo_repo: TodoRepository,
+}
+
+impl CalendarService {
+    pub fn new(token_repo: CalendarTokenRepository, todo_repo: TodoRepository) -> Self {
+        Self {
+            token_repo,
+            todo_repo,
+        }
+    }
+
+    /// 期限のあるToDoをiCalendarで出力
+    pub async fn export(
+        &self,
+        user_id: Uuid,
+        components: &[CalendarComponent],
+    ) -> AppResult<String> {
+        let todos = self.todo_repo.find_all_by_user_id(user_id).await?;
+
+        Ok(ical::render(&todos, components, CALENDAR_NAME, Utc::now()))
+    }
+
+    /// 購読トークンでiCalendarを出力（無効なトークンは404）
+    pub async fn feed(&self, token: &str, components: &[CalendarComponent]) -> AppResult<String> {
+        let stored = self
+            .token_repo
+            .find_by_token_hash(&AuthService::hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Calendar feed not found".into()))?;
+
+        self.export(stored.user_id, components).await
+    }
+
+    /// 購読URLの発行状況を取得
+    pub async fn get_feed(&self, user_id: Uuid) -> AppResult<CalendarFeedResponse> {
+        Ok(self.token_repo.find_by_user_id(user_id).await?.into())
+    }
+
+    /// 購読トークンを発行（再発行すると以前のURLは使えなくなる）
+    pub async fn regenerate_token(&self, user_id: Uuid) -> AppResult<CalendarTokenResponse> {
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let stored = self
+            .token_repo
+            .upsert(user_id, &AuthService::hash_token(&token))
+            .await?;
+
+        Ok(CalendarTokenResponse {
+            url: format!("/api/calendar/feed/{}.ics", token),
+            token,
+            created_at: stored.created_at,
+        })
+    }
+
+    /// 購読トークンを無効化
+    pub async fn revoke_token(&self, user_id: Uuid) -> AppResult<()> {
+        if !self.token_repo.delete(user_id).await? {
+            return Err(AppError::NotFound("Calendar feed not found".into()));
+        }
+        Ok(())
+    }
+}
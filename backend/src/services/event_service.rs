@@ -0,0 +1,185 @@
+//! ToDoの変更のリアルタイム通知
+//!
+//! 履歴の追加はトリガーで `todo_events` チャンネルに通知される（複数のインスタンスでも届く）
+//! 1つの `LISTEN` 接続で受け取った通知を各ストリームに伝え、
+//! ストリームは最後に送った通し番号より後の履歴をデータベースから取得して送る
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use futures::{stream, Stream};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::JoinHandle,
+};
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult, models::todo_history::TodoEvent,
+    repositories::todo_history_repository::TodoHistoryRepository,
+};
+
+/// ストリームへの通知を溜めておく件数（超えた場合は全ストリームが再取得する）
+const NOTICE_CAPACITY: usize = 1024;
+/// 一度に取得する履歴の件数
+const FETCH_BATCH: i64 = 200;
+/// 再接続時に再送する最大件数（超えた場合は `reset` を送る）
+const MAX_REPLAY: i64 = 1000;
+/// `LISTEN` の接続が切れた場合に再接続するまでの時間
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// ストリームに送る内容
+#[derive(Debug, Clone)]
+pub enum EventStreamItem {
+    Todo(Box<TodoEvent>),
+    /// 再送できる件数を超えたため、一覧を取得し直す必要がある（`seq` は最新の通し番号）
+    Reset { seq: i64 },
+}
+
+/// `LISTEN` の接続からストリームへの通知
+#[derive(Debug, Clone, Copy)]
+enum Notice {
+    /// ユーザーのToDoが変更された
+    Changed(Uuid),
+    /// 通知を取りこぼした可能性がある（全ストリームが再取得する）
+    Resync,
+}
+
+#[derive(Clone)]
+pub struct EventService {
+    history_repo: TodoHistoryRepository,
+    sender: broadcast::Sender<Notice>,
+    listener: Arc<OnceLock<JoinHandle<()>>>,
+}
+
+struct StreamState {
+    history_repo: TodoHistoryRepository,
+    receiver: broadcast::Receiver<Notice>,
+    user_id: Uuid,
+    last_seq: i64,
+    pending: VecDeque<EventStreamItem>,
+    has_more: bool,
+}
+
+impl EventService {
+    pub fn new(history_repo: TodoHistoryRepository) -> Self {
+        let (sender, _) = broadcast::channel(NOTICE_CAPACITY);
+
+        Self {
+            history_repo,
+            sender,
+            listener: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// ユーザーのToDoの変更イベントのストリーム
+    /// `last_event_id` を指定した場合はそれより後のイベントを再送してから続ける（省略時は接続以降のみ）
+    pub async fn subscribe(
+        &self,
+        user_id: Uuid,
+        last_event_id: Option<i64>,
+    ) -> AppResult<impl Stream<Item = EventStreamItem>> {
+        self.listener
+            .get_or_init(|| tokio::spawn(Self::listen(self.history_repo.clone(), self.sender.clone())));
+        // 取得との間の変更を取りこぼさないよう、先に通知を購読する
+        let receiver = self.sender.subscribe();
+
+        let mut pending = VecDeque::new();
+        let last_seq = match last_event_id {
+            Some(last_event_id) => {
+                let events = self
+                    .history_repo
+                    .find_events_since(user_id, last_event_id, MAX_REPLAY + 1)
+                    .await?;
+                if events.len() as i64 > MAX_REPLAY {
+                    let seq = self.history_repo.latest_seq(user_id).await?;
+                    pending.push_back(EventStreamItem::Reset { seq });
+                    seq
+                } else {
+                    let seq = events.last().map_or(last_event_id, |e| e.seq);
+                    pending.extend(events.into_iter().map(|e| EventStreamItem::Todo(Box::new(e))));
+                    seq
+                }
+            }
+            None => self.history_repo.latest_seq(user_id).await?,
+        };
+
+        let state = StreamState {
+            history_repo: self.history_repo.clone(),
+            receiver,
+            user_id,
+            last_seq,
+            pending,
+            has_more: false,
+        };
+
+        Ok(stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.pending.pop_front() {
+                    return Some((item, state));
+                }
+                if !state.has_more {
+                    match state.receiver.recv().await {
+                        Ok(Notice::Changed(user_id)) if user_id != state.user_id => continue,
+                        Ok(_) | Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+
+                match state
+                    .history_repo
+                    .find_events_since(state.user_id, state.last_seq, FETCH_BATCH)
+                    .await
+                {
+                    Ok(events) => {
+                        state.has_more = events.len() as i64 == FETCH_BATCH;
+                        if let Some(last) = events.last() {
+                            state.last_seq = last.seq;
+                        }
+                        state
+                            .pending
+                            .extend(events.into_iter().map(|e| EventStreamItem::Todo(Box::new(e))));
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to fetch todo events: {}", e);
+                        state.has_more = false;
+                    }
+                }
+            }
+        }))
+    }
+
+    /// 通知を受け取り、ストリームに伝える（接続が切れた場合は再接続する）
+    async fn listen(history_repo: TodoHistoryRepository, sender: broadcast::Sender<Notice>) {
+        loop {
+            match history_repo.listen().await {
+                Ok(mut listener) => {
+                    // 接続していなかった間の変更をストリームに取得させる
+                    let _ = sender.send(Notice::Resync);
+                    loop {
+                        match listener.try_recv().await {
+                            Ok(Some(notification)) => {
+                                if let Ok(user_id) = notification.payload().parse() {
+                                    let _ = sender.send(Notice::Changed(user_id));
+                                }
+                            }
+                            // 接続が切れて再接続した
+                            Ok(None) => {
+                                let _ = sender.send(Notice::Resync);
+                            }
+                            Err(e) => {
+                                tracing::warn!("Lost connection for todo events: {}", e);
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to listen for todo events: {}", e),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+}
@@ -0,0 +1,488 @@
+//! ToDo一覧の絞り込み式
+//!
+//! `priority:high and (status:pending or due:<today) and not tag:someday` のような式を
+//! 構文木に変換する。SQLへの変換は `TodoRepository` で行う。
+//!
+//! - 条件は `項目:値` で、`status` / `priority` / `tag` / `due` / `start` / `completed` / `created` を指定できる
+//! - `priority` と日付の項目は `<` / `<=` / `>` / `>=` で範囲を指定できる（例: `priority:>=medium`, `due:<+7d`）
+//! - 日付は保存済みビューと同じ指定（`today`, `+7d`, `2026-03-16` など）で、`none` は未設定を表す
+//! - `and`（省略可）/ `or` / `not`（または先頭の `-`）と括弧で組み合わせる
+//! - 項目のない語はタイトル・説明の部分一致検索になり、空白を含む値は `"..."` で囲む
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+use crate::{
+    error::AppError,
+    models::{
+        saved_view::resolve_date,
+        todo::{TodoPriority, TodoStatus},
+    },
+};
+
+/// 絞り込み式の最大文字数
+pub const MAX_LENGTH: usize = 1000;
+
+/// 括弧と `not` の入れ子の上限
+const MAX_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Condition(Condition),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Status(TodoStatus),
+    Priority(Comparison, TodoPriority),
+    /// 日時の比較（日付で指定した場合、`start` と `end` はその日の始まりと終わり）
+    Date {
+        field: DateField,
+        comparison: Comparison,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    /// 日時が未設定
+    NoDate(DateField),
+    Tag(String),
+    /// タイトル・説明の部分一致
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateField {
+    Due,
+    Start,
+    Completed,
+    Created,
+}
+
+impl DateField {
+    /// 対応する `todos` のカラム
+    pub fn column(self) -> &'static str {
+        match self {
+            DateField::Due => "due_date",
+            DateField::Start => "start_date",
+            DateField::Completed => "completed_at",
+            DateField::Created => "created_at",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// 構文エラー（`position` は1始まりの文字位置）
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid filter at position {}: {}", self.position, self.message)
+    }
+}
+
+impl From<FilterError> for AppError {
+    fn from(e: FilterError) -> Self {
+        AppError::Validation(e.to_string())
+    }
+}
+
+/// 絞り込み式を構文木に変換する
+/// 相対的な日付は `now` のタイムゾーンで解決する
+pub fn parse(input: &str, now: DateTime<Tz>) -> Result<FilterExpr, FilterError> {
+    let length = input.chars().count();
+    if length > MAX_LENGTH {
+        return Err(FilterError {
+            position: MAX_LENGTH + 1,
+            message: format!("Filter must be at most {} characters", MAX_LENGTH),
+        });
+    }
+
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        depth: 0,
+        end: length + 1,
+        now,
+    };
+
+    let expr = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        return Err(parser.unexpected(token));
+    }
+
+    Ok(expr)
+}
+
+// Tokenizer
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    /// 先頭の `-`（`not` と同じ）
+    Minus,
+    Word(Word),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Word {
+    text: String,
+    /// 引用符を含むか（キーワードとして扱わない）
+    quoted: bool,
+    /// 引用符の外にある最初の `:` の位置（`text` 内の文字数）
+    colon: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FilterError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let position = i + 1;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '(' || c == ')' {
+            let kind = if c == '(' {
+                TokenKind::LParen
+            } else {
+                TokenKind::RParen
+            };
+            tokens.push(Token { kind, position });
+            i += 1;
+            continue;
+        }
+        if c == '-' && chars.get(i + 1).is_some_and(|n| !n.is_whitespace() && *n != ')') {
+            tokens.push(Token {
+                kind: TokenKind::Minus,
+                position,
+            });
+            i += 1;
+            continue;
+        }
+
+        let mut text = String::new();
+        let mut length = 0;
+        let mut quoted = false;
+        let mut colon = None;
+        while i < chars.len() {
+            let c = chars[i];
+            if c.is_whitespace() || c == '(' || c == ')' {
+                break;
+            }
+            if c == '"' {
+                quoted = true;
+                let open = i + 1;
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => {
+                            return Err(FilterError {
+                                position: open,
+                                message: "Unterminated quoted string".into(),
+                            })
+                        }
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.get(i + 1), Some('"') | Some('\\')) => {
+                            text.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(c) => {
+                            text.push(*c);
+                            i += 1;
+                        }
+                    }
+                    length += 1;
+                }
+                i += 1;
+                continue;
+            }
+            if c == ':' && colon.is_none() && !quoted {
+                colon = Some(length);
+            }
+            text.push(c);
+            length += 1;
+            i += 1;
+        }
+
+        tokens.push(Token {
+            kind: TokenKind::Word(Word {
+                text,
+                quoted,
+                colon,
+            }),
+            position,
+        });
+    }
+
+    Ok(tokens)
+}
+
+// Parser
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    depth: usize,
+    /// 入力の終端の位置
+    end: usize,
+    now: DateTime<Tz>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    /// 次のトークンが指定したキーワードか
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|t| is_keyword(t, keyword))
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut left = self.parse_and()?;
+        while self.peek_keyword("or") {
+            self.next();
+            let right = self.parse_and()?;
+            left = FilterExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            if self.peek_keyword("and") {
+                self.next();
+            } else if !self.peek().is_some_and(|t| {
+                matches!(
+                    t.kind,
+                    TokenKind::LParen | TokenKind::Minus | TokenKind::Word(_)
+                ) && !is_keyword(t, "or")
+            }) {
+                break;
+            }
+            // `and` は省略できる
+            let right = self.parse_unary()?;
+            left = FilterExpr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, FilterError> {
+        let Some(token) = self
+            .peek()
+            .filter(|t| t.kind == TokenKind::Minus || is_keyword(t, "not"))
+            .cloned()
+        else {
+            return self.parse_primary();
+        };
+
+        self.next();
+        self.enter(&token)?;
+        let expr = self.parse_unary()?;
+        self.depth -= 1;
+        Ok(FilterExpr::Not(Box::new(expr)))
+    }
+
+    fn parse_primary(&mut self) -> Result<FilterExpr, FilterError> {
+        let Some(token) = self.next() else {
+            return Err(FilterError {
+                position: self.end,
+                message: "Unexpected end of filter".into(),
+            });
+        };
+
+        match token.kind {
+            TokenKind::LParen => {
+                self.enter(&token)?;
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    }) => {}
+                    Some(other) => return Err(self.unexpected(&other)),
+                    None => {
+                        return Err(FilterError {
+                            position: token.position,
+                            message: "Unclosed '('".into(),
+                        })
+                    }
+                }
+                self.depth -= 1;
+                Ok(expr)
+            }
+            TokenKind::Word(ref word) if !is_keyword(&token, "and") && !is_keyword(&token, "or") => {
+                self.condition(word, token.position)
+                    .map(FilterExpr::Condition)
+            }
+            _ => Err(self.unexpected(&token)),
+        }
+    }
+
+    /// 入れ子を1段深くする
+    fn enter(&mut self, token: &Token) -> Result<(), FilterError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(FilterError {
+                position: token.position,
+                message: format!("Filter must be nested at most {} levels", MAX_DEPTH),
+            });
+        }
+        Ok(())
+    }
+
+    fn unexpected(&self, token: &Token) -> FilterError {
+        let found = match &token.kind {
+            TokenKind::LParen => "'('".to_string(),
+            TokenKind::RParen => "')'".to_string(),
+            TokenKind::Minus => "'-'".to_string(),
+            TokenKind::Word(word) => format!("'{}'", word.text),
+        };
+        FilterError {
+            position: token.position,
+            message: format!("Unexpected {}", found),
+        }
+    }
+
+    /// `項目:値` または検索語を条件に変換する
+    fn condition(&self, word: &Word, position: usize) -> Result<Condition, FilterError> {
+        let Some(colon) = word.colon else {
+            if word.text.trim().is_empty() {
+                return Err(FilterError {
+                    position,
+                    message: "Search text must not be empty".into(),
+                });
+            }
+            return Ok(Condition::Text(word.text.clone()));
+        };
+
+        let field: String = word.text.chars().take(colon).collect();
+        let raw: String = word.text.chars().skip(colon + 1).collect();
+        let value_position = position + colon + 1;
+
+        let (comparison, value, value_position) = split_comparison(&raw, value_position);
+        let error = |message: String| FilterError {
+            position: value_position,
+            message,
+        };
+        if value.is_empty() {
+            return Err(error(format!("Missing value for '{}'", field)));
+        }
+
+        let field_lower = field.to_lowercase();
+        let date_field = match field_lower.as_str() {
+            "due" => Some(DateField::Due),
+            "start" => Some(DateField::Start),
+            "completed" => Some(DateField::Completed),
+            "created" => Some(DateField::Created),
+            _ => None,
+        };
+        if let Some(field) = date_field {
+            if value.eq_ignore_ascii_case("none") {
+                if comparison != Comparison::Eq {
+                    return Err(error("'none' cannot be compared".into()));
+                }
+                return Ok(Condition::NoDate(field));
+            }
+            let start = resolve_date(value, &self.now, false);
+            let end = resolve_date(value, &self.now, true);
+            return match (start, end) {
+                (Some(start), Some(end)) => Ok(Condition::Date {
+                    field,
+                    comparison,
+                    start,
+                    end,
+                }),
+                _ => Err(error(format!("Invalid date '{}'", value))),
+            };
+        }
+
+        match field_lower.as_str() {
+            "status" => {
+                if comparison != Comparison::Eq {
+                    return Err(error("Status cannot be compared".into()));
+                }
+                let status = match value.to_lowercase().as_str() {
+                    "pending" => TodoStatus::Pending,
+                    "inprogress" | "in_progress" => TodoStatus::InProgress,
+                    "completed" => TodoStatus::Completed,
+                    _ => return Err(error(format!("Invalid status '{}'", value))),
+                };
+                Ok(Condition::Status(status))
+            }
+            "priority" => {
+                let priority = match value.to_lowercase().as_str() {
+                    "low" => TodoPriority::Low,
+                    "medium" => TodoPriority::Medium,
+                    "high" => TodoPriority::High,
+                    _ => return Err(error(format!("Invalid priority '{}'", value))),
+                };
+                Ok(Condition::Priority(comparison, priority))
+            }
+            "tag" => {
+                if comparison != Comparison::Eq {
+                    return Err(error("Tag cannot be compared".into()));
+                }
+                Ok(Condition::Tag(value.trim().to_string()))
+            }
+            _ => Err(FilterError {
+                position,
+                message: format!("Unknown field '{}'", field),
+            }),
+        }
+    }
+}
+
+fn is_keyword(token: &Token, keyword: &str) -> bool {
+    matches!(&token.kind, TokenKind::Word(w) if !w.quoted && w.text.eq_ignore_ascii_case(keyword))
+}
+
+/// 値の先頭の比較演算子を取り出す
+fn split_comparison(value: &str, position: usize) -> (Comparison, &str, usize) {
+    for (operator, comparison) in [
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+    ] {
+        if let Some(rest) = value.strip_prefix(operator) {
+            return (comparison, rest, position + operator.len());
+        }
+    }
+    (Comparison::Eq, value, position)
+}
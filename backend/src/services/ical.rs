@@ -0,0 +1,370 @@
+//! iCalendar（RFC 5545）の出力と読み込み
+//!
+//! - ToDoはVTODO、期限日時はVEVENTとして出力する
+//! - ステータスは `NEEDS-ACTION` / `IN-PROCESS` / `COMPLETED`、優先度は 9（低）/ 5（中）/ 1（高）に対応付ける
+//! - 行は CRLF で区切り、75オクテットを超える行は折り返す
+//! - CalDAVで受け取ったVTODOは同じ対応付けでToDoの項目に変換する
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+
+use crate::models::{
+    calendar::CalendarComponent,
+    saved_view::start_of_day,
+    todo::{Todo, TodoPriority, TodoStatus},
+};
+
+const PRODID: &str = "-//todo-web//todo-backend//JA";
+
+/// 期限のあるToDoをカレンダーとして出力する
+pub fn render(
+    todos: &[Todo],
+    components: &[CalendarComponent],
+    name: &str,
+    now: DateTime<Utc>,
+) -> String {
+    let mut lines = vec![
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+    for todo in todos.iter().filter(|t| t.due_date.is_some()) {
+        for component in components {
+            match component {
+                CalendarComponent::Vtodo => lines.extend(vtodo(todo, &todo_uid(todo), now)),
+                CalendarComponent::Vevent => lines.extend(vevent(todo, now)),
+            }
+        }
+    }
+
+    calendar(lines)
+}
+
+/// 1件のToDoをVTODOのカレンダーオブジェクトとして出力する（CalDAVのリソース）
+pub fn render_todo(todo: &Todo, uid: &str, now: DateTime<Utc>) -> String {
+    calendar(vtodo(todo, uid, now))
+}
+
+/// VCALENDARで囲み、行を折り返す
+fn calendar(components: Vec<String>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    lines.extend(components);
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+/// ToDoをVTODOの行に変換する
+fn vtodo(todo: &Todo, uid: &str, now: DateTime<Utc>) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VTODO".to_string(),
+        format!("UID:{}", escape_text(uid)),
+        format!("DTSTAMP:{}", format_datetime(now)),
+        format!("CREATED:{}", format_datetime(todo.created_at)),
+        format!("LAST-MODIFIED:{}", format_datetime(todo.updated_at)),
+        format!("SEQUENCE:{}", todo.version),
+        format!("SUMMARY:{}", escape_text(&todo.title)),
+    ];
+    lines.extend(common_properties(todo));
+    if let Some(start) = todo.start_date {
+        lines.push(format!("DTSTART:{}", format_datetime(start)));
+    }
+    if let Some(due) = todo.due_date {
+        lines.push(format!("DUE:{}", format_datetime(due)));
+    }
+    lines.push(format!("STATUS:{}", status(&todo.status)));
+    if todo.status == TodoStatus::Completed {
+        if let Some(completed) = todo.completed_at {
+            lines.push(format!("COMPLETED:{}", format_datetime(completed)));
+        }
+        lines.push("PERCENT-COMPLETE:100".to_string());
+    }
+    lines.push("END:VTODO".to_string());
+
+    lines
+}
+
+/// ToDoの期限日時をVEVENTの行に変換する（時間を占有しない予定）
+fn vevent(todo: &Todo, now: DateTime<Utc>) -> Vec<String> {
+    let Some(due) = todo.due_date else {
+        return Vec::new();
+    };
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}-due@todo-web", todo.id),
+        format!("DTSTAMP:{}", format_datetime(now)),
+        format!("CREATED:{}", format_datetime(todo.created_at)),
+        format!("LAST-MODIFIED:{}", format_datetime(todo.updated_at)),
+        format!("SEQUENCE:{}", todo.version),
+        format!("SUMMARY:{}", escape_text(&todo.title)),
+    ];
+    lines.extend(common_properties(todo));
+    lines.push(format!("DTSTART:{}", format_datetime(due)));
+    lines.push("TRANSP:TRANSPARENT".to_string());
+    lines.push("END:VEVENT".to_string());
+
+    lines
+}
+
+fn common_properties(todo: &Todo) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(description) = todo.description.as_deref().filter(|d| !d.is_empty()) {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    lines.push(format!("PRIORITY:{}", priority(&todo.priority)));
+    if !todo.tags.is_empty() {
+        let tags: Vec<String> = todo.tags.iter().map(|t| escape_text(t)).collect();
+        lines.push(format!("CATEGORIES:{}", tags.join(",")));
+    }
+    lines
+}
+
+/// VTODOのUID
+pub fn todo_uid(todo: &Todo) -> String {
+    format!("{}@todo-web", todo.id)
+}
+
+/// ステータスをVTODOのSTATUSに変換
+pub fn status(status: &TodoStatus) -> &'static str {
+    match status {
+        TodoStatus::Pending => "NEEDS-ACTION",
+        TodoStatus::InProgress => "IN-PROCESS",
+        TodoStatus::Completed => "COMPLETED",
+    }
+}
+
+/// 優先度をPRIORITY（1が最高、9が最低）に変換
+pub fn priority(priority: &TodoPriority) -> u8 {
+    match priority {
+        TodoPriority::High => 1,
+        TodoPriority::Medium => 5,
+        TodoPriority::Low => 9,
+    }
+}
+
+/// UTCの日時（`20260316T093000Z`）
+pub fn format_datetime(datetime: DateTime<Utc>) -> String {
+    datetime.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// TEXT値のエスケープ
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 75オクテットごとに折り返し（UTF-8の文字の途中では折り返さない）、CRLFを付ける
+pub fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut width = 0;
+    for c in line.chars() {
+        // 2行目以降は先頭の空白を含めて75オクテット
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+// Parser
+
+/// VTODOから読み取った項目
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedVtodo {
+    pub uid: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub status: Option<TodoStatus>,
+    pub priority: Option<TodoPriority>,
+    pub tags: Vec<String>,
+}
+
+/// カレンダーオブジェクトの最初のVTODOを読み取る
+/// TZIDのない日時と日付のみの値は `tz` で解釈する
+pub fn parse_vtodo(text: &str, tz: &Tz) -> Result<ParsedVtodo, String> {
+    let unfolded = text
+        .replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut parsed = ParsedVtodo::default();
+    let mut found = false;
+    // VTODO内のVALARMなどは読み飛ばす
+    let mut depth = 0;
+    let mut completed = false;
+    for line in unfolded.lines().map(|l| l.trim_end_matches('\r')) {
+        let Some((name, params, value)) = split_property(line) else {
+            continue;
+        };
+        match (name.as_str(), value) {
+            ("BEGIN", "VTODO") if !found && depth == 0 => {
+                found = true;
+                depth = 1;
+                continue;
+            }
+            ("BEGIN", _) if depth > 0 => depth += 1,
+            ("END", _) if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        if depth != 1 {
+            continue;
+        }
+
+        match name.as_str() {
+            "UID" => parsed.uid = Some(unescape_text(value)),
+            "SUMMARY" => parsed.title = Some(unescape_text(value)),
+            "DESCRIPTION" => parsed.description = Some(unescape_text(value)),
+            "DTSTART" => parsed.start_date = Some(parse_datetime(value, &params, tz)?),
+            "DUE" => parsed.due_date = Some(parse_datetime(value, &params, tz)?),
+            "STATUS" => {
+                parsed.status = Some(match value.to_ascii_uppercase().as_str() {
+                    "IN-PROCESS" => TodoStatus::InProgress,
+                    "COMPLETED" | "CANCELLED" => TodoStatus::Completed,
+                    _ => TodoStatus::Pending,
+                })
+            }
+            "COMPLETED" => completed = true,
+            "PRIORITY" => {
+                parsed.priority = Some(match value.trim().parse::<u8>() {
+                    Ok(1..=4) => TodoPriority::High,
+                    Ok(6..=9) => TodoPriority::Low,
+                    _ => TodoPriority::Medium,
+                })
+            }
+            "CATEGORIES" => {
+                for tag in split_list(value) {
+                    let tag = unescape_text(tag.trim());
+                    if !tag.is_empty() && !parsed.tags.contains(&tag) {
+                        parsed.tags.push(tag);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !found {
+        return Err("Calendar object must contain a VTODO".into());
+    }
+    if parsed.status.is_none() && completed {
+        parsed.status = Some(TodoStatus::Completed);
+    }
+    Ok(parsed)
+}
+
+/// プロパティのパラメーター（名前は大文字）
+type Params = Vec<(String, String)>;
+
+/// `NAME;PARAM=VALUE:value` を名前（大文字）・パラメーター・値に分ける
+fn split_property(line: &str) -> Option<(String, Params, &str)> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.trim().to_ascii_uppercase(), v.trim_matches('"').to_string()))
+        .collect();
+
+    Some((name, params, value))
+}
+
+/// DATE-TIME（UTC・TZID付き・ローカル）とDATEを解釈する
+fn parse_datetime(value: &str, params: &[(String, String)], tz: &Tz) -> Result<DateTime<Utc>, String> {
+    let param = |name: &str| params.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str());
+    let value = value.trim();
+    let invalid = || format!("Invalid date '{}'", value);
+
+    if param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+        return start_of_day(date, tz).ok_or_else(invalid);
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let datetime = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+        return Ok(datetime.and_utc());
+    }
+
+    let datetime = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    let zone = param("TZID")
+        .and_then(|id| id.trim_start_matches('/').parse::<Tz>().ok())
+        .unwrap_or(*tz);
+    datetime
+        .and_local_timezone(zone)
+        .earliest()
+        .map(|d| d.with_timezone(&Utc))
+        .ok_or_else(invalid)
+}
+
+/// エスケープされていないカンマで区切る
+fn split_list(value: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                items.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&value[start..]);
+    items
+}
+
+/// TEXT値のエスケープを戻す
+pub fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
@@ -0,0 +1,192 @@
+//! Microsoft To Do（Microsoft Graph の `todoTask`）
+//!
+//! タスクの配列、`value` を持つレスポンス、または `lists`（`displayName` と `tasks`）に対応
+//!
+//! - `importance` の `high` / `normal` / `low` → 高 / 中 / 低
+//! - `waitingOnOthers` / `deferred` は未着手として取り込む
+//! - チェックリストはサブタスク、`categories` はタグ、リスト名はタグ `+リスト名`
+//! - HTMLの本文はテキストに変換する
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde_json::Value;
+
+use super::{parse_datetime, text, ImportedTask, Notes};
+use crate::models::todo::{TodoPriority, TodoStatus};
+
+pub fn parse(content: &Value, tz: &Tz, notes: &mut Notes) -> Result<Vec<ImportedTask>, String> {
+    let items = match content {
+        Value::Array(items) => items,
+        Value::Object(object) => object
+            .get("lists")
+            .or(object.get("value"))
+            .and_then(Value::as_array)
+            .ok_or("Microsoft To Do JSON must contain lists or value")?,
+        _ => return Err("Microsoft To Do content must be JSON".into()),
+    };
+
+    let mut tasks = Vec::new();
+    for item in items {
+        match item.get("tasks").and_then(Value::as_array) {
+            // リスト
+            Some(list_tasks) => {
+                let list = text(item.get("displayName"))
+                    .filter(|_| text(item.get("wellknownListName")).as_deref() != Some("defaultList"));
+                for task in list_tasks {
+                    let mut task = parse_task(task, tz, notes);
+                    if let Some(list) = &list {
+                        task.push_tag(format!("+{}", list));
+                    }
+                    tasks.push(task);
+                }
+            }
+            None => tasks.push(parse_task(item, tz, notes)),
+        }
+    }
+
+    Ok(tasks)
+}
+
+fn parse_task(item: &Value, tz: &Tz, notes: &mut Notes) -> ImportedTask {
+    let title = text(item.get("title")).unwrap_or_default();
+    let mut task = ImportedTask {
+        title: title.clone(),
+        due_date: date_time(item.get("dueDateTime"), tz, &title, "dueDateTime", notes),
+        start_date: date_time(item.get("startDateTime"), tz, &title, "startDateTime", notes),
+        created_at: text(item.get("createdDateTime")).and_then(|d| parse_datetime(&d, tz)),
+        ..Default::default()
+    };
+
+    if let Some(body) = item.get("body") {
+        let content = text(body.get("content")).unwrap_or_default();
+        if text(body.get("contentType")).as_deref() == Some("html") {
+            let plain = html_to_text(&content);
+            if !plain.is_empty() {
+                notes.transformed(&title, "body", "HTML converted to plain text");
+            }
+            task.append_description(&plain);
+        } else {
+            task.append_description(&content);
+        }
+    }
+
+    task.priority = match text(item.get("importance")).as_deref() {
+        Some("high") => Some(TodoPriority::High),
+        Some("low") => Some(TodoPriority::Low),
+        _ => None,
+    };
+    task.status = match text(item.get("status")).as_deref() {
+        Some("inProgress") => Some(TodoStatus::InProgress),
+        Some("completed") => {
+            task.completed_at = date_time(item.get("completedDateTime"), tz, &title, "completedDateTime", notes);
+            Some(TodoStatus::Completed)
+        }
+        Some(status @ ("waitingOnOthers" | "deferred")) => {
+            notes.transformed(&title, "status", format!("{} imported as pending", status));
+            None
+        }
+        _ => None,
+    };
+
+    for category in item.get("categories").and_then(Value::as_array).into_iter().flatten() {
+        if let Some(category) = text(Some(category)) {
+            task.push_tag(category);
+        }
+    }
+    for checklist_item in item.get("checklistItems").and_then(Value::as_array).into_iter().flatten() {
+        let mut subtask = ImportedTask {
+            title: text(checklist_item.get("displayName")).unwrap_or_default(),
+            created_at: text(checklist_item.get("createdDateTime")).and_then(|d| parse_datetime(&d, tz)),
+            ..Default::default()
+        };
+        if checklist_item.get("isChecked").and_then(Value::as_bool).unwrap_or(false) {
+            subtask.status = Some(TodoStatus::Completed);
+            subtask.completed_at =
+                text(checklist_item.get("checkedDateTime")).and_then(|d| parse_datetime(&d, tz));
+        }
+        task.subtasks.push(subtask);
+    }
+
+    if item.get("recurrence").is_some_and(|r| !r.is_null()) {
+        notes.skipped(&title, "recurrence", "Recurrence");
+    }
+    if item.get("isReminderOn").and_then(Value::as_bool).unwrap_or(false) {
+        notes.skipped(&title, "reminderDateTime", "Reminder");
+    }
+    if item.get("hasAttachments").and_then(Value::as_bool).unwrap_or(false) {
+        notes.skipped(&title, "attachments", "Attachments");
+    }
+
+    task
+}
+
+/// `{ "dateTime": "2026-03-20T00:00:00.0000000", "timeZone": "UTC" }`
+/// IANA名でないタイムゾーン（Windowsのタイムゾーン名など）はユーザーのタイムゾーンで解釈する
+fn date_time(
+    value: Option<&Value>,
+    tz: &Tz,
+    title: &str,
+    field: &str,
+    notes: &mut Notes,
+) -> Option<DateTime<Utc>> {
+    let value = value.filter(|v| v.is_object())?;
+    let date_time = text(value.get("dateTime"))?;
+    let zone = match text(value.get("timeZone")) {
+        Some(name) => match name.parse::<Tz>() {
+            Ok(zone) => zone,
+            Err(_) => {
+                notes.transformed(title, field, format!("Time zone '{}' interpreted as the profile time zone", name));
+                *tz
+            }
+        },
+        None => *tz,
+    };
+
+    let parsed = parse_datetime(&date_time, &zone);
+    if parsed.is_none() {
+        notes.skipped(title, field, format!("Unsupported date '{}'", date_time));
+    }
+    parsed
+}
+
+/// タグを取り除き、段落・改行を改行にする
+fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    let mut tag = String::new();
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                let name = tag
+                    .trim_start_matches('/')
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .next()
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                if matches!(name.as_str(), "br" | "p" | "div" | "li") && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            _ if in_tag => tag.push(c),
+            _ => text.push(c),
+        }
+    }
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
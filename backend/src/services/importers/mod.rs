@@ -0,0 +1,221 @@
+//! 他のツールのエクスポートファイルの読み取り
+//!
+//! 各形式のパーサーは `ImportedTask` のツリーに変換し、
+//! 対応する項目がない・値を変換したフィールドを `Notes` に記録する
+
+pub mod microsoft_todo;
+pub mod taskwarrior;
+pub mod todoist;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use serde_json::Value;
+
+use crate::{
+    models::{
+        saved_view::start_of_day,
+        todo::{TodoPriority, TodoStatus},
+        transfer::{ImportNote, ImportNoteKind, ImportSource},
+    },
+    services::transfer_service::MAX_IMPORT_ROWS,
+};
+
+/// サブタスクを含めた階層の上限（ルートを1階層目とする）
+pub const MAX_DEPTH: usize = 4;
+
+/// 取り込むタスク（`subtasks` はサブタスクとして作成する）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportedTask {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: Option<TodoStatus>,
+    pub priority: Option<TodoPriority>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub estimate_minutes: Option<i32>,
+    pub tags: Vec<String>,
+    pub subtasks: Vec<ImportedTask>,
+}
+
+impl ImportedTask {
+    /// サブタスクを含む件数
+    pub fn count(&self) -> usize {
+        1 + self.subtasks.iter().map(Self::count).sum::<usize>()
+    }
+
+    pub fn push_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !tag.trim().is_empty() && !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// 説明の末尾に段落を追加
+    pub fn append_description(&mut self, text: &str) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        self.description = Some(match self.description.take() {
+            Some(description) if !description.is_empty() => format!("{}\n\n{}", description, text),
+            _ => text.to_string(),
+        });
+    }
+}
+
+/// 取り込まなかった・変換した項目の記録
+#[derive(Debug, Default)]
+pub struct Notes(pub Vec<ImportNote>);
+
+impl Notes {
+    pub fn skipped(&mut self, item: &str, field: &str, detail: impl Into<String>) {
+        self.push(item, field, ImportNoteKind::Skipped, detail.into());
+    }
+
+    pub fn transformed(&mut self, item: &str, field: &str, detail: impl Into<String>) {
+        self.push(item, field, ImportNoteKind::Transformed, detail.into());
+    }
+
+    fn push(&mut self, item: &str, field: &str, kind: ImportNoteKind, detail: String) {
+        self.0.push(ImportNote {
+            item: item.to_string(),
+            field: field.to_string(),
+            kind,
+            detail,
+        });
+    }
+}
+
+/// ファイルを読み取ってタスクのツリーにする
+/// 日付のみ・タイムゾーンのない日時は `tz` で解釈する
+pub fn parse(
+    source: ImportSource,
+    content: &Value,
+    tz: &Tz,
+    notes: &mut Notes,
+) -> Result<Vec<ImportedTask>, String> {
+    match source {
+        ImportSource::Todoist => todoist::parse(content, tz, notes),
+        ImportSource::Taskwarrior => taskwarrior::parse(content, tz, notes),
+        ImportSource::MicrosoftTodo => microsoft_todo::parse(content, tz, notes),
+    }
+}
+
+/// 親のインデックス付きの一覧からツリーを組み立てる（親が見つからない場合はルート）
+/// `MAX_DEPTH` より深いタスクは上限の階層に収まる祖先の子として取り込み、`notes` に記録する
+/// ファイルで深さが決まるため、再帰せずに組み立てる
+pub fn build_tree(
+    items: Vec<(ImportedTask, Option<usize>)>,
+    notes: &mut Notes,
+) -> Result<Vec<ImportedTask>, String> {
+    let len = items.len();
+    if len > MAX_IMPORT_ROWS {
+        return Err(format!("At most {} tasks can be imported at once", MAX_IMPORT_ROWS));
+    }
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); len];
+    let mut roots = Vec::new();
+    let mut slots: Vec<Option<ImportedTask>> = Vec::with_capacity(len);
+    for (index, (task, parent)) in items.into_iter().enumerate() {
+        match parent.filter(|p| *p < len && *p != index) {
+            Some(parent) => children[parent].push(index),
+            None => roots.push(index),
+        }
+        slots.push(Some(task));
+    }
+
+    // ルートから幅優先で辿り、取り込む親子関係を決める
+    // 循環している親子関係は、辿れなかったタスクをルートとして取り込む
+    let mut parents: Vec<Option<usize>> = vec![None; len];
+    let mut depths: Vec<usize> = vec![0; len];
+    let mut subtasks: Vec<Vec<usize>> = vec![Vec::new(); len];
+    let mut visited = vec![false; len];
+    let mut order = Vec::with_capacity(len);
+    let mut tree_roots = Vec::new();
+    for root in roots.into_iter().chain(0..len) {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        tree_roots.push(root);
+        let start = order.len();
+        order.push(root);
+        let mut cursor = start;
+        while cursor < order.len() {
+            let index = order[cursor];
+            cursor += 1;
+            for &child in &children[index] {
+                if visited[child] {
+                    continue;
+                }
+                visited[child] = true;
+                let parent = if depths[index] + 1 < MAX_DEPTH {
+                    depths[child] = depths[index] + 1;
+                    index
+                } else {
+                    // 上限の階層のタスクの子は、その親（上限の1つ上の階層）の子にする
+                    let ancestor = parents[index].unwrap_or(index);
+                    depths[child] = depths[index];
+                    let title = slots[child].as_ref().map(|t| t.title.as_str()).unwrap_or_default();
+                    let ancestor_title = slots[ancestor].as_ref().map(|t| t.title.as_str()).unwrap_or_default();
+                    notes.transformed(
+                        title,
+                        "parent",
+                        format!("Nested deeper than {} levels; imported under '{}'", MAX_DEPTH, ancestor_title),
+                    );
+                    ancestor
+                };
+                parents[child] = Some(parent);
+                subtasks[parent].push(child);
+                order.push(child);
+            }
+        }
+    }
+
+    // 深いタスクから順に親へ移す
+    for &index in order.iter().rev() {
+        let attached: Vec<ImportedTask> = subtasks[index]
+            .iter()
+            .filter_map(|&child| slots[child].take())
+            .collect();
+        if let Some(task) = slots[index].as_mut() {
+            task.subtasks = attached;
+        }
+    }
+
+    Ok(tree_roots
+        .into_iter()
+        .filter_map(|index| slots[index].take())
+        .collect())
+}
+
+/// 日時の文字列を読み取る
+/// RFC 3339、タイムゾーンのない日時（`tz` で解釈）、日付のみ（`tz` での0時）に対応
+pub fn parse_datetime(text: &str, tz: &Tz) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(text, format) {
+            return naive
+                .and_local_timezone(*tz)
+                .earliest()
+                .map(|d| d.with_timezone(&Utc));
+        }
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| start_of_day(date, tz))
+}
+
+/// JSONの値を文字列として読み取る（数値のIDにも対応）
+pub fn text(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,115 @@
+//! Taskwarrior（`task export` のJSON配列、または1行1タスクのJSON）
+//!
+//! - `pending` / `waiting` → 未着手、`completed` → 完了（`end` が完了日時）
+//! - `deleted` と繰り返しの親（`recurring`）は取り込まない
+//! - 優先度 `H` / `M` / `L` → 高 / 中 / 低
+//! - `scheduled`（なければ `wait`）→ 開始日、`project` → タグ `+project`
+//! - 注釈（`annotations`）は説明に追加する
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use serde_json::Value;
+
+use super::{parse_datetime, text, ImportedTask, Notes};
+use crate::models::todo::{TodoPriority, TodoStatus};
+
+pub fn parse(content: &Value, tz: &Tz, notes: &mut Notes) -> Result<Vec<ImportedTask>, String> {
+    let lines;
+    let items: &[Value] = match content {
+        Value::Array(items) => items,
+        // 古いバージョンの `task export` は1行1タスク
+        Value::String(text) => {
+            lines = text
+                .lines()
+                .map(|l| l.trim().trim_end_matches(','))
+                .filter(|l| !l.is_empty() && *l != "[" && *l != "]")
+                .map(serde_json::from_str)
+                .collect::<Result<Vec<Value>, _>>()
+                .map_err(|e| format!("Invalid JSON: {}", e))?;
+            &lines
+        }
+        _ => return Err("Taskwarrior content must be a JSON array".into()),
+    };
+
+    let mut tasks = Vec::with_capacity(items.len());
+    for item in items {
+        let title = text(item.get("description")).unwrap_or_default();
+        let date = |key: &str| text(item.get(key)).and_then(|d| parse_date(&d, tz));
+
+        let status = text(item.get("status")).unwrap_or_else(|| "pending".into());
+        match status.as_str() {
+            "deleted" => {
+                notes.skipped(&title, "status", "Deleted task");
+                continue;
+            }
+            "recurring" => {
+                notes.skipped(&title, "status", "Recurring template (its instances are imported)");
+                continue;
+            }
+            _ => {}
+        }
+
+        let mut task = ImportedTask {
+            title: title.clone(),
+            due_date: date("due"),
+            start_date: date("scheduled").or_else(|| date("wait")),
+            created_at: date("entry"),
+            ..Default::default()
+        };
+        match status.as_str() {
+            "completed" => {
+                task.status = Some(TodoStatus::Completed);
+                task.completed_at = date("end");
+            }
+            "waiting" => {
+                notes.transformed(&title, "status", "waiting imported as pending");
+            }
+            _ => {}
+        }
+        if item.get("start").is_some() && task.status.is_none() {
+            task.status = Some(TodoStatus::InProgress);
+        }
+
+        task.priority = match text(item.get("priority")).as_deref() {
+            Some("H") => Some(TodoPriority::High),
+            Some("M") => Some(TodoPriority::Medium),
+            Some("L") => Some(TodoPriority::Low),
+            _ => None,
+        };
+        if let Some(project) = text(item.get("project")) {
+            task.push_tag(format!("+{}", project));
+        }
+        for tag in item.get("tags").and_then(Value::as_array).into_iter().flatten() {
+            if let Some(tag) = text(Some(tag)) {
+                task.push_tag(tag);
+            }
+        }
+        for annotation in item.get("annotations").and_then(Value::as_array).into_iter().flatten() {
+            if let Some(description) = text(annotation.get("description")) {
+                task.append_description(&description);
+            }
+        }
+
+        if item.get("recur").is_some() {
+            notes.skipped(&title, "recur", "Recurrence (this instance is imported)");
+        }
+        if item.get("depends").is_some() {
+            notes.skipped(&title, "depends", "Dependencies");
+        }
+        if item.get("until").is_some() {
+            notes.skipped(&title, "until", "Expiration date");
+        }
+
+        tasks.push(task);
+    }
+
+    Ok(tasks)
+}
+
+/// `20260301T090000Z` 形式（それ以外は共通の形式）
+fn parse_date(text: &str, tz: &Tz) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text, "%Y%m%dT%H%M%SZ")
+        .map(|d| d.and_utc())
+        .ok()
+        .or_else(|| parse_datetime(text, tz))
+}
@@ -0,0 +1,193 @@
+//! Todoist
+//!
+//! - バックアップ（プロジェクトごとのCSV）: `TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,...,DATE,...`
+//! - APIのJSON: タスクの配列、または `items` / `tasks` / `results` と `projects` を持つオブジェクト
+//!
+//! 優先度は p1 → 高、p2 → 高、p3 → 中、p4（優先度なし）→ 中
+//! プロジェクトはタグ `+プロジェクト名`、ラベルはタグとして取り込む
+
+use std::collections::HashMap;
+
+use chrono_tz::Tz;
+use serde_json::Value;
+
+use super::{build_tree, parse_datetime, text, ImportedTask, Notes};
+use crate::models::todo::{TodoPriority, TodoStatus};
+
+pub fn parse(content: &Value, tz: &Tz, notes: &mut Notes) -> Result<Vec<ImportedTask>, String> {
+    match content {
+        Value::String(csv) => parse_csv(csv, tz, notes),
+        _ => parse_json(content, tz, notes),
+    }
+}
+
+fn parse_csv(content: &str, tz: &Tz, notes: &mut Notes) -> Result<Vec<ImportedTask>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV: {}", e))?
+        .iter()
+        .map(str::to_ascii_uppercase)
+        .collect();
+    if !headers.iter().any(|h| h == "TYPE") || !headers.iter().any(|h| h == "CONTENT") {
+        return Err("Todoist CSV must have TYPE and CONTENT columns".into());
+    }
+
+    let mut items: Vec<(ImportedTask, Option<usize>)> = Vec::new();
+    // インデントごとの直近のタスク
+    let mut ancestors: Vec<usize> = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+        let field = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h == name)
+                .and_then(|i| record.get(i))
+                .unwrap_or_default()
+        };
+
+        match field("TYPE") {
+            "task" => {
+                let mut task = ImportedTask::default();
+                let mut title = Vec::new();
+                for word in field("CONTENT").split_whitespace() {
+                    match word.strip_prefix('@').filter(|l| !l.is_empty()) {
+                        Some(label) => task.push_tag(label),
+                        None => title.push(word),
+                    }
+                }
+                task.title = title.join(" ");
+                task.append_description(field("DESCRIPTION"));
+                task.priority = priority(field("PRIORITY").parse().ok(), &task.title, notes);
+                let date = field("DATE");
+                if !date.is_empty() {
+                    match parse_datetime(date, tz) {
+                        Some(due) => task.due_date = Some(due),
+                        None => notes.skipped(&task.title, "DATE", format!("Unsupported date '{}'", date)),
+                    }
+                }
+                if field("DURATION_UNIT") == "minute" {
+                    task.estimate_minutes = field("DURATION").parse().ok();
+                }
+
+                let indent = field("INDENT").parse::<usize>().unwrap_or(1).max(1);
+                ancestors.truncate(indent - 1);
+                let parent = ancestors.last().copied();
+                ancestors.push(items.len());
+                items.push((task, parent));
+            }
+            "note" => match items.last_mut() {
+                Some((task, _)) => {
+                    task.append_description(field("CONTENT"));
+                    notes.transformed(&task.title, "note", "Comment appended to description");
+                }
+                None => notes.skipped("", "note", "Comment without a task"),
+            },
+            "section" => notes.skipped("", "section", format!("Section '{}'", field("CONTENT"))),
+            _ => {}
+        }
+    }
+
+    build_tree(items, notes)
+}
+
+fn parse_json(content: &Value, tz: &Tz, notes: &mut Notes) -> Result<Vec<ImportedTask>, String> {
+    let (tasks, projects) = match content {
+        Value::Array(tasks) => (tasks, None),
+        Value::Object(object) => {
+            let tasks = ["items", "tasks", "results"]
+                .iter()
+                .find_map(|key| object.get(*key).and_then(Value::as_array))
+                .ok_or("Todoist JSON must contain items, tasks or results")?;
+            (tasks, object.get("projects").and_then(Value::as_array))
+        }
+        _ => return Err("Todoist content must be CSV text or JSON".into()),
+    };
+    let project_names: HashMap<String, String> = projects
+        .into_iter()
+        .flatten()
+        .filter_map(|p| Some((text(p.get("id"))?, text(p.get("name"))?)))
+        .collect();
+    let indexes: HashMap<String, usize> = tasks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, t)| Some((text(t.get("id"))?, i)))
+        .collect();
+
+    let mut items = Vec::with_capacity(tasks.len());
+    for item in tasks {
+        let mut task = ImportedTask {
+            title: text(item.get("content")).unwrap_or_default(),
+            ..Default::default()
+        };
+        if let Some(description) = text(item.get("description")) {
+            task.append_description(&description);
+        }
+        task.priority = priority(item.get("priority").and_then(Value::as_u64), &task.title, notes);
+
+        let completed = ["checked", "is_completed", "completed"]
+            .iter()
+            .any(|key| item.get(*key).and_then(Value::as_bool).unwrap_or(false));
+        if completed {
+            task.status = Some(TodoStatus::Completed);
+            task.completed_at = text(item.get("completed_at")).and_then(|d| parse_datetime(&d, tz));
+        }
+        task.created_at = text(item.get("added_at").or(item.get("created_at")))
+            .and_then(|d| parse_datetime(&d, tz));
+
+        if let Some(due) = item.get("due").filter(|d| d.is_object()) {
+            let zone: Tz = text(due.get("timezone"))
+                .and_then(|z| z.parse().ok())
+                .unwrap_or(*tz);
+            let date = text(due.get("datetime")).or(text(due.get("date")));
+            task.due_date = date.as_deref().and_then(|d| parse_datetime(d, &zone));
+            if date.is_some() && task.due_date.is_none() {
+                notes.skipped(&task.title, "due", format!("Unsupported date '{}'", date.unwrap_or_default()));
+            }
+            if due.get("is_recurring").and_then(Value::as_bool).unwrap_or(false) {
+                let rule = text(due.get("string")).unwrap_or_default();
+                notes.skipped(&task.title, "due.is_recurring", format!("Recurrence '{}' (only the next date is imported)", rule));
+            }
+        }
+        if let Some(duration) = item.get("duration").filter(|d| d.is_object()) {
+            if text(duration.get("unit")).as_deref() == Some("minute") {
+                task.estimate_minutes = duration.get("amount").and_then(Value::as_i64).map(|m| m as i32);
+            } else {
+                notes.skipped(&task.title, "duration", "Only durations in minutes are supported");
+            }
+        }
+
+        if let Some(project) = text(item.get("project_id")).and_then(|id| project_names.get(&id)) {
+            task.push_tag(format!("+{}", project));
+        }
+        for label in item.get("labels").and_then(Value::as_array).into_iter().flatten() {
+            if let Some(label) = text(Some(label)) {
+                task.push_tag(label);
+            }
+        }
+
+        let parent = text(item.get("parent_id")).and_then(|id| indexes.get(&id).copied());
+        items.push((task, parent));
+    }
+
+    build_tree(items, notes)
+}
+
+/// Todoistの優先度（4 が最も高い p1）
+fn priority(value: Option<u64>, title: &str, notes: &mut Notes) -> Option<TodoPriority> {
+    match value {
+        Some(4) => Some(TodoPriority::High),
+        Some(3) => {
+            notes.transformed(title, "priority", "p2 imported as high");
+            Some(TodoPriority::High)
+        }
+        Some(2) => {
+            notes.transformed(title, "priority", "p3 imported as medium");
+            Some(TodoPriority::Medium)
+        }
+        _ => None,
+    }
+}
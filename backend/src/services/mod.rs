@@ -1,3 +1,24 @@
 //! Business logic
+pub mod agenda_service;
+pub mod app_password_service;
 pub mod auth_service;
-pub mod todo_service;
\ No newline at end of file
+pub mod board_service;
+pub mod caldav;
+pub mod caldav_service;
+pub mod calendar_service;
+pub mod event_service;
+pub mod filter;
+pub mod ical;
+pub mod importers;
+pub mod profile_service;
+pub mod quick_add;
+pub mod rank;
+pub mod saved_view_service;
+pub mod stats_service;
+pub mod sync_service;
+pub mod template_service;
+pub mod time_entry_service;
+pub mod todo_service;
+pub mod todotxt;
+pub mod transfer_service;
+pub mod webhook_service;
@@ -0,0 +1,67 @@
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        auth::MeResponse,
+        user::{ProfileResponse, UpdateProfileRequest, User},
+    },
+    repositories::user_repository::UserRepository,
+};
+
+#[derive(Clone)]
+pub struct ProfileService {
+    user_repo: UserRepository,
+}
+
+impl ProfileService {
+    pub fn new(user_repo: UserRepository) -> Self {
+        Self { user_repo }
+    }
+
+    /// 現在のユーザー情報を取得
+    pub async fn me(&self, user_id: Uuid) -> AppResult<MeResponse> {
+        let user = self.user(user_id).await?;
+
+        Ok(MeResponse {
+            id: user.id,
+            email: user.email.clone(),
+            created_at: user.created_at,
+            profile: user.into(),
+        })
+    }
+
+    /// プロフィールを取得
+    pub async fn get(&self, user_id: Uuid) -> AppResult<ProfileResponse> {
+        Ok(self.user(user_id).await?.into())
+    }
+
+    /// プロフィールを更新
+    pub async fn update(
+        &self,
+        user_id: Uuid,
+        req: UpdateProfileRequest,
+    ) -> AppResult<ProfileResponse> {
+        let display_name = req
+            .display_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty());
+
+        let user = self
+            .user_repo
+            .update_profile(user_id, display_name, &req.time_zone, &req.locale, req.week_start)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+        Ok(user.into())
+    }
+
+    /// ユーザーを取得（日付の解釈にプロフィールを使う）
+    pub async fn user(&self, user_id: Uuid) -> AppResult<User> {
+        self.user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))
+    }
+}
@@ -0,0 +1,429 @@
+//! 1行の入力からToDoの項目を取り出すクイック追加
+//!
+//! `Pay rent tomorrow 9am !high #finance` や `来週金曜 15時 歯医者 !高 #健康` のような入力を
+//! タイトル・期限・優先度・タグに分解する。
+//!
+//! - `#タグ` はタグ、`!high` / `!高` などは優先度
+//! - 期限は英語（`today`, `tomorrow`, `fri`, `next friday`, `in 3 days`, `3/20`, `9am`, `21:00` など）と
+//!   日本語（`今日`, `明日`, `明後日`, `金曜`, `来週金曜`, `3日後`, `3月20日`, `午後3時`, `9時半` など）で指定できる
+//! - 日付と時刻はそれぞれ最初に見つかったものだけを使い、残りはタイトルになる
+//! - 日付のみの場合はその日の0時、時刻のみの場合は当日として、指定したタイムゾーンで解釈する
+//! - `next week` / `来週金曜` などの週は `week_start` の曜日から始まる週として数える
+
+use chrono::{
+    DateTime, Datelike, Duration, Months, NaiveDate, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+
+use crate::models::todo::{QuickAddParsed, TodoPriority};
+
+/// 入力を解析する（相対的な日付は `now` のタイムゾーンで解決）
+pub fn parse(input: &str, now: DateTime<Tz>, week_start: Weekday) -> QuickAddParsed {
+    let today = now.date_naive();
+    let words: Vec<&str> = input.split_whitespace().collect();
+
+    let mut title: Vec<String> = Vec::new();
+    let mut schedule = Schedule::default();
+    let mut priority = None;
+    let mut tags: Vec<String> = Vec::new();
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+
+        if let Some(tag) = word.strip_prefix('#').or_else(|| word.strip_prefix('＃')) {
+            if !tag.is_empty() {
+                if !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+                i += 1;
+                continue;
+            }
+        }
+        if let Some(value) = word.strip_prefix('!').or_else(|| word.strip_prefix('！')) {
+            if let (None, Some(p)) = (&priority, parse_priority(value)) {
+                priority = Some(p);
+                i += 1;
+                continue;
+            }
+        }
+        if let Some((length, part)) = english(&words[i..], today, week_start) {
+            if schedule.accepts(&part) {
+                schedule.set(part);
+                i += length;
+                continue;
+            }
+        }
+
+        let rest = japanese(word, today, week_start, &mut schedule);
+        if !rest.is_empty() {
+            title.push(rest);
+        }
+        i += 1;
+    }
+
+    QuickAddParsed {
+        title: title.join(" "),
+        due_date: schedule.resolve(today, &now.timezone()),
+        priority,
+        tags,
+    }
+}
+
+fn parse_priority(value: &str) -> Option<TodoPriority> {
+    match value.to_lowercase().as_str() {
+        "high" | "h" | "3" | "高" => Some(TodoPriority::High),
+        "medium" | "med" | "m" | "2" | "中" => Some(TodoPriority::Medium),
+        "low" | "l" | "1" | "低" => Some(TodoPriority::Low),
+        _ => None,
+    }
+}
+
+// Schedule
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Part {
+    Date(NaiveDate),
+    Time(NaiveTime),
+}
+
+#[derive(Debug, Default)]
+struct Schedule {
+    date: Option<NaiveDate>,
+    time: Option<NaiveTime>,
+}
+
+impl Schedule {
+    /// まだ設定されていない種類か（2つ目以降の日付・時刻はタイトルとして扱う）
+    fn accepts(&self, part: &Part) -> bool {
+        match part {
+            Part::Date(_) => self.date.is_none(),
+            Part::Time(_) => self.time.is_none(),
+        }
+    }
+
+    fn set(&mut self, part: Part) {
+        match part {
+            Part::Date(date) => self.date = Some(date),
+            Part::Time(time) => self.time = Some(time),
+        }
+    }
+
+    fn resolve(&self, today: NaiveDate, tz: &Tz) -> Option<DateTime<Utc>> {
+        if self.date.is_none() && self.time.is_none() {
+            return None;
+        }
+
+        let local = self
+            .date
+            .unwrap_or(today)
+            .and_time(self.time.unwrap_or(NaiveTime::MIN));
+        // 夏時間の切り替えで存在しない時刻は1時間後にずらす
+        tz.from_local_datetime(&local)
+            .earliest()
+            .or_else(|| tz.from_local_datetime(&(local + Duration::hours(1))).earliest())
+            .map(|d| d.with_timezone(&Utc))
+    }
+}
+
+// English
+
+/// 先頭の語から日付・時刻を読み取り、消費した語数を返す
+fn english(words: &[&str], today: NaiveDate, week_start: Weekday) -> Option<(usize, Part)> {
+    let first = words.first()?.to_lowercase();
+
+    // 前置詞は後ろに日付・時刻が続く場合だけ取り除く
+    if matches!(first.as_str(), "on" | "at" | "by" | "due") {
+        return english_expression(&words[1..], today, week_start).map(|(length, part)| (length + 1, part));
+    }
+
+    english_expression(words, today, week_start)
+}
+
+fn english_expression(words: &[&str], today: NaiveDate, week_start: Weekday) -> Option<(usize, Part)> {
+    let first = words.first()?.to_lowercase();
+    let second = words.get(1).map(|w| w.to_lowercase());
+
+    match first.as_str() {
+        "today" => return Some((1, Part::Date(today))),
+        "tomorrow" | "tmr" | "tmrw" => return Some((1, Part::Date(today + Duration::days(1)))),
+        "noon" => return Some((1, Part::Time(NaiveTime::from_hms_opt(12, 0, 0)?))),
+        "next" | "this" => {
+            let second = second?;
+            let weeks = if first == "next" { 1 } else { 0 };
+            if second == "week" && weeks == 1 {
+                return Some((2, Part::Date(week_day(today, weeks, week_start, week_start))));
+            }
+            let weekday = parse_weekday(&second)?;
+            return Some((2, Part::Date(week_day(today, weeks, weekday, week_start))));
+        }
+        "in" => {
+            let amount: u32 = second?.parse().ok()?;
+            let unit = words.get(2)?.to_lowercase();
+            let date = match unit.as_str() {
+                "day" | "days" => today.checked_add_signed(Duration::days(amount.into())),
+                "week" | "weeks" => today.checked_add_signed(Duration::weeks(amount.into())),
+                "month" | "months" => today.checked_add_months(Months::new(amount)),
+                _ => None,
+            }?;
+            return Some((3, Part::Date(date)));
+        }
+        _ => {}
+    }
+
+    if let Some(weekday) = parse_weekday(&first) {
+        return Some((1, Part::Date(upcoming(today, weekday))));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&first, "%Y-%m-%d") {
+        return Some((1, Part::Date(date)));
+    }
+    if let Some((month, day)) = first.split_once('/') {
+        let date = month_day(today, month.parse().ok()?, day.parse().ok()?)?;
+        return Some((1, Part::Date(date)));
+    }
+    if let Some(time) = english_time(&first) {
+        return Some((1, Part::Time(time)));
+    }
+    // `9 am` のように数字と午前・午後が分かれている場合
+    if let Some(meridiem @ ("am" | "pm")) = second.as_deref() {
+        let time = english_time(&format!("{}{}", first, meridiem))?;
+        return Some((2, Part::Time(time)));
+    }
+
+    None
+}
+
+/// `9am` / `9:30pm` / `21:00` 形式の時刻（午前・午後もコロンもない数字は時刻としない）
+fn english_time(word: &str) -> Option<NaiveTime> {
+    let (clock, meridiem) = if let Some(clock) = word.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = word.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (word, None)
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour.parse().ok()?, minute.parse().ok()?),
+        Some(_) => return None,
+        None if meridiem.is_some() => (clock.parse().ok()?, 0),
+        None => return None,
+    };
+    if hour > 23 {
+        return None;
+    }
+
+    let hour = match meridiem {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+// Japanese
+
+/// 語の中にある日本語の日付・時刻を取り除き、残りの文字列を返す
+fn japanese(word: &str, today: NaiveDate, week_start: Weekday, schedule: &mut Schedule) -> String {
+    let chars: Vec<char> = word.chars().collect();
+    let mut rest = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match japanese_expression(&chars[i..], today, week_start) {
+            Some((length, part)) if schedule.accepts(&part) => {
+                schedule.set(part);
+                i += length;
+                // 直後の助詞も取り除く
+                for particle in ["までに", "まで", "に", "の"] {
+                    let particle: Vec<char> = particle.chars().collect();
+                    if chars[i..].starts_with(&particle) {
+                        i += particle.len();
+                        break;
+                    }
+                }
+            }
+            _ => {
+                rest.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+
+    rest
+}
+
+fn japanese_expression(chars: &[char], today: NaiveDate, week_start: Weekday) -> Option<(usize, Part)> {
+    let starts = |s: &str| {
+        let prefix: Vec<char> = s.chars().collect();
+        chars.starts_with(&prefix).then_some(prefix.len())
+    };
+
+    for (word, days) in [
+        ("明後日", 2),
+        ("あさって", 2),
+        ("明日", 1),
+        ("あした", 1),
+        ("今日", 0),
+        ("きょう", 0),
+    ] {
+        if let Some(length) = starts(word) {
+            return Some((length, Part::Date(today + Duration::days(days))));
+        }
+    }
+
+    for (word, weeks) in [("再来週", 2), ("来週", 1), ("今週", 0)] {
+        if let Some(length) = starts(word) {
+            return match japanese_weekday(&chars[length..]) {
+                Some((day_length, weekday)) => Some((
+                    length + day_length,
+                    Part::Date(week_day(today, weeks, weekday, week_start)),
+                )),
+                // 曜日のない `来週` は週の始まり
+                None if weeks > 0 => Some((length, Part::Date(week_day(today, weeks, week_start, week_start)))),
+                None => None,
+            };
+        }
+    }
+
+    if let Some((length, weekday)) = japanese_weekday(chars) {
+        return Some((length, Part::Date(upcoming(today, weekday))));
+    }
+
+    if let Some((length, time)) = japanese_time(chars) {
+        return Some((length, Part::Time(time)));
+    }
+
+    // `3日後` / `2週間後` / `3月20日`
+    let (amount, digits) = number(chars)?;
+    let rest = &chars[digits..];
+    let after = |s: &str| {
+        let suffix: Vec<char> = s.chars().collect();
+        rest.starts_with(&suffix).then_some(digits + suffix.len())
+    };
+    if let Some(length) = after("日後") {
+        let date = today.checked_add_signed(Duration::days(amount.into()))?;
+        return Some((length, Part::Date(date)));
+    }
+    if let Some(length) = after("週間後") {
+        let date = today.checked_add_signed(Duration::weeks(amount.into()))?;
+        return Some((length, Part::Date(date)));
+    }
+    if rest.first() == Some(&'月') {
+        let (day, day_digits) = number(&rest[1..])?;
+        if rest.get(1 + day_digits) == Some(&'日') {
+            let date = month_day(today, amount, day)?;
+            return Some((digits + day_digits + 2, Part::Date(date)));
+        }
+    }
+
+    None
+}
+
+/// `金曜` / `金曜日`
+fn japanese_weekday(chars: &[char]) -> Option<(usize, Weekday)> {
+    let weekday = match chars.first()? {
+        '月' => Weekday::Mon,
+        '火' => Weekday::Tue,
+        '水' => Weekday::Wed,
+        '木' => Weekday::Thu,
+        '金' => Weekday::Fri,
+        '土' => Weekday::Sat,
+        '日' => Weekday::Sun,
+        _ => return None,
+    };
+    if chars.get(1) != Some(&'曜') {
+        return None;
+    }
+    let length = if chars.get(2) == Some(&'日') { 3 } else { 2 };
+    Some((length, weekday))
+}
+
+/// `9時` / `9時30分` / `9時半` / `午後3時`
+fn japanese_time(chars: &[char]) -> Option<(usize, NaiveTime)> {
+    let (pm, offset) = match chars.get(..2) {
+        Some(['午', '前']) => (Some(false), 2),
+        Some(['午', '後']) => (Some(true), 2),
+        _ => (None, 0),
+    };
+
+    let (hour, digits) = number(&chars[offset..])?;
+    let mut length = offset + digits;
+    if chars.get(length) != Some(&'時') {
+        return None;
+    }
+    length += 1;
+
+    let mut minute = 0;
+    if chars.get(length) == Some(&'半') {
+        minute = 30;
+        length += 1;
+    } else if let Some((m, minute_digits)) = number(&chars[length..]) {
+        if chars.get(length + minute_digits) == Some(&'分') {
+            minute = m;
+            length += minute_digits + 1;
+        }
+    }
+
+    let hour = match pm {
+        Some(true) if hour < 12 => hour + 12,
+        Some(_) if hour > 12 => return None,
+        _ => hour,
+    };
+    Some((length, NaiveTime::from_hms_opt(hour, minute, 0)?))
+}
+
+/// 先頭の数字（全角を含む）を読み取り、値と桁数を返す
+fn number(chars: &[char]) -> Option<(u32, usize)> {
+    let digits: Vec<u32> = chars
+        .iter()
+        .map_while(|c| match c {
+            '0'..='9' => c.to_digit(10),
+            '０'..='９' => Some(*c as u32 - '０' as u32),
+            _ => None,
+        })
+        .take(4)
+        .collect();
+    if digits.is_empty() {
+        return None;
+    }
+
+    Some((digits.iter().fold(0, |n, d| n * 10 + d), digits.len()))
+}
+
+// Date helpers
+
+/// 今日以降で最初の指定した曜日
+fn upcoming(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    today + Duration::days(days.into())
+}
+
+/// `weeks` 週後の週（`week_start` 始まり）の指定した曜日
+fn week_day(today: NaiveDate, weeks: i64, weekday: Weekday, week_start: Weekday) -> NaiveDate {
+    let start = today - Duration::days(today.weekday().days_since(week_start).into());
+    start + Duration::weeks(weeks) + Duration::days(weekday.days_since(week_start).into())
+}
+
+/// 月日から日付を作成（今日より前なら翌年）
+fn month_day(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if date < today {
+        return NaiveDate::from_ymd_opt(today.year() + 1, month, day);
+    }
+    Some(date)
+}
@@ -0,0 +1,105 @@
+//! 手動並び替え用のランクキー
+//!
+//! キーは base62 の桁を小数点以下に並べた値（0.k1k2k3...）として扱い、
+//! 文字列の辞書順（C照合順序）が値の大小と一致する。
+//! 2つのキーの間には常に新しいキーを生成できるため、並び替え時に
+//! 他のToDoのキーを振り直す必要がない。末尾が `0` のキーは作らない。
+
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const BASE: usize = DIGITS.len();
+
+/// これより長いキーを持つユーザーは定期ジョブで振り直す
+pub const MAX_KEY_LENGTH: usize = 16;
+
+/// 有効なランクキーか
+pub fn is_valid(key: &str) -> bool {
+    !key.is_empty() && !key.ends_with('0') && key.bytes().all(|b| DIGITS.contains(&b))
+}
+
+/// `lower` と `upper` の間に入るキーを生成
+/// `None` はそれぞれ先頭・末尾を表す
+/// キーが不正な場合や `lower >= upper` の場合は `None`
+pub fn between(lower: Option<&str>, upper: Option<&str>) -> Option<String> {
+    if lower.is_some_and(|k| !is_valid(k)) || upper.is_some_and(|k| !is_valid(k)) {
+        return None;
+    }
+    if let (Some(lower), Some(upper)) = (lower, upper) {
+        if lower >= upper {
+            return None;
+        }
+    }
+
+    let lower = lower.map(to_digits).unwrap_or_default();
+    let upper = upper.map(to_digits);
+
+    Some(from_digits(&midpoint(&lower, upper.as_deref())))
+}
+
+/// `count` 件分のキーを等間隔で生成（振り直し用）
+pub fn spread(count: usize) -> Vec<String> {
+    let mut length = 1u32;
+    while (BASE as u128).pow(length) <= count as u128 {
+        length += 1;
+    }
+    let space = (BASE as u128).pow(length);
+
+    (1..=count as u128)
+        .map(|i| {
+            let mut value = i * space / (count as u128 + 1);
+            let mut digits = vec![0u8; length as usize];
+            for digit in digits.iter_mut().rev() {
+                *digit = (value % BASE as u128) as u8;
+                value /= BASE as u128;
+            }
+            while digits.last() == Some(&0) {
+                digits.pop();
+            }
+            from_digits(&digits)
+        })
+        .collect()
+}
+
+fn midpoint(lower: &[u8], upper: Option<&[u8]>) -> Vec<u8> {
+    if let Some(upper) = upper {
+        // 共通の接頭辞はそのまま引き継ぐ
+        let common = upper
+            .iter()
+            .enumerate()
+            .take_while(|(i, d)| lower.get(*i).copied().unwrap_or(0) == **d)
+            .count();
+        if common > 0 {
+            let mut result = upper[..common].to_vec();
+            result.extend(midpoint(
+                lower.get(common..).unwrap_or_default(),
+                Some(&upper[common..]),
+            ));
+            return result;
+        }
+    }
+
+    let lower_digit = lower.first().copied().unwrap_or(0) as usize;
+    let upper_digit = upper.map(|u| u[0] as usize).unwrap_or(BASE);
+
+    if upper.is_none() && !lower.is_empty() && lower_digit + 1 < BASE {
+        // 末尾への追加でキーが伸びないよう、中間ではなく1つ先の値にする
+        vec![(lower_digit + 1) as u8]
+    } else if upper_digit - lower_digit > 1 {
+        vec![((lower_digit + upper_digit) / 2) as u8]
+    } else if let Some(upper) = upper.filter(|u| u.len() > 1) {
+        vec![upper[0]]
+    } else {
+        let mut result = vec![lower_digit as u8];
+        result.extend(midpoint(lower.get(1..).unwrap_or_default(), None));
+        result
+    }
+}
+
+fn to_digits(key: &str) -> Vec<u8> {
+    key.bytes()
+        .map(|b| DIGITS.iter().position(|d| *d == b).unwrap_or(0) as u8)
+        .collect()
+}
+
+fn from_digits(digits: &[u8]) -> String {
+    digits.iter().map(|d| DIGITS[*d as usize] as char).collect()
+}
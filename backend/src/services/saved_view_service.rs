@@ -0,0 +1,233 @@
+use chrono::Utc;
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        saved_view::{
+            MoveSavedViewRequest, SavedView, SavedViewRequest, SavedViewResponse,
+            SavedViewTodosQuery,
+        },
+        todo::TodoListResponse,
+    },
+    repositories::saved_view_repository::SavedViewRepository,
+    services::{filter, profile_service::ProfileService, rank, todo_service::TodoService},
+};
+
+#[derive(Clone)]
+pub struct SavedViewService {
+    view_repo: SavedViewRepository,
+    todo_service: TodoService,
+    profile_service: ProfileService,
+}
+
+impl SavedViewService {
+    pub fn new(
+        view_repo: SavedViewRepository,
+        todo_service: TodoService,
+        profile_service: ProfileService,
+    ) -> Self {
+        Self {
+            view_repo,
+            todo_service,
+            profile_service,
+        }
+    }
+
+    /// ビュー一覧を取得
+    pub async fn list(&self, user_id: Uuid) -> AppResult<Vec<SavedViewResponse>> {
+        let views = self.view_repo.find_by_user_id(user_id).await?;
+
+        Ok(views.into_iter().map(|v| v.into()).collect())
+    }
+
+    /// ビュー詳細を取得
+    pub async fn get_by_id(&self, id: Uuid, user_id: Uuid) -> AppResult<SavedViewResponse> {
+        Ok(self.find_view(id, user_id).await?.into())
+    }
+
+    /// ビューの作成（末尾に追加）
+    pub async fn create(&self, user_id: Uuid, req: SavedViewRequest) -> AppResult<SavedViewResponse> {
+        Self::check_filter(&req)?;
+        self.check_name(user_id, &req.name, None).await?;
+
+        let last = self.view_repo.last_position(user_id).await?;
+        let position = match rank::between(last.as_deref(), None) {
+            Some(position) => position,
+            None => {
+                self.view_repo.rebalance_positions(user_id).await?;
+                let last = self.view_repo.last_position(user_id).await?;
+                rank::between(last.as_deref(), None)
+                    .ok_or_else(|| AppError::Internal("Failed to compute position".into()))?
+            }
+        };
+
+        let view = self
+            .view_repo
+            .create(user_id, &req.name, &req.filters, &position)
+            .await
+            .map_err(Self::map_unique_violation)?;
+
+        Ok(view.into())
+    }
+
+    /// ビューの更新（名前・条件）
+    pub async fn update(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: SavedViewRequest,
+    ) -> AppResult<SavedViewResponse> {
+        Self::check_filter(&req)?;
+        self.find_view(id, user_id).await?;
+        self.check_name(user_id, &req.name, Some(id)).await?;
+
+        let view = self
+            .view_repo
+            .update(id, user_id, &req.name, &req.filters)
+            .await
+            .map_err(Self::map_unique_violation)?
+            .ok_or_else(|| AppError::NotFound("View not found".into()))?;
+
+        Ok(view.into())
+    }
+
+    /// ビューの並び替え
+    pub async fn move_view(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: MoveSavedViewRequest,
+    ) -> AppResult<SavedViewResponse> {
+        if req.before.is_none() && req.after.is_none() {
+            return Err(AppError::Validation("Specify before or after".into()));
+        }
+        if req.before == Some(id) || req.after == Some(id) {
+            return Err(AppError::Validation(
+                "Cannot move a view relative to itself".into(),
+            ));
+        }
+        self.find_view(id, user_id).await?;
+
+        let position = match self.position_between(id, user_id, &req).await? {
+            Some(position) => position,
+            None => {
+                // キーの間に空きがない場合は振り直してから再計算
+                self.view_repo.rebalance_positions(user_id).await?;
+                self.position_between(id, user_id, &req)
+                    .await?
+                    .ok_or_else(|| AppError::Conflict("Failed to compute position".into()))?
+            }
+        };
+
+        let view = self
+            .view_repo
+            .update_position(id, user_id, &position)
+            .await?
+            .ok_or_else(|| AppError::NotFound("View not found".into()))?;
+
+        Ok(view.into())
+    }
+
+    /// ビューの削除
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
+        if !self.view_repo.delete(id, user_id).await? {
+            return Err(AppError::NotFound("View not found".into()));
+        }
+
+        Ok(())
+    }
+
+    /// ビューの条件でToDo一覧を取得
+    /// 相対的な日付は実行時点のプロフィールのタイムゾーンでの日付で解決する
+    pub async fn todos(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        query: SavedViewTodosQuery,
+    ) -> AppResult<TodoListResponse> {
+        let view = self.find_view(id, user_id).await?;
+        let now = self.profile_service.user(user_id).await?.now();
+        let query = view.filters.to_query(now, query.page, query.per_page);
+
+        self.todo_service.list(user_id, query).await
+    }
+
+    /// 認可チェック付きでビューを取得
+    async fn find_view(&self, id: Uuid, user_id: Uuid) -> AppResult<SavedView> {
+        self.view_repo
+            .find_by_id_and_user_id(id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("View not found".into()))
+    }
+
+    /// 同名のビューがないことを確認
+    async fn check_name(&self, user_id: Uuid, name: &str, exclude_id: Option<Uuid>) -> AppResult<()> {
+        match self.view_repo.find_by_name(user_id, name).await? {
+            Some(view) if Some(view.id) != exclude_id => {
+                Err(AppError::Conflict("View name already exists".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 絞り込み式の構文を確認
+    fn check_filter(req: &SavedViewRequest) -> AppResult<()> {
+        if let Some(input) = req.filters.filter.as_deref().filter(|f| !f.trim().is_empty()) {
+            filter::parse(input, Utc::now().with_timezone(&Tz::UTC))?;
+        }
+
+        Ok(())
+    }
+
+    /// 同時に作成された場合は一意制約で検出する
+    fn map_unique_violation(e: AppError) -> AppError {
+        match e {
+            AppError::Database(sqlx::Error::Database(ref db)) if db.is_unique_violation() => {
+                AppError::Conflict("View name already exists".into())
+            }
+            e => e,
+        }
+    }
+
+    /// 移動先の前後のビューからランクキーを生成
+    /// キーの間に空きがない場合は `None`
+    async fn position_between(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: &MoveSavedViewRequest,
+    ) -> AppResult<Option<String>> {
+        let after = match req.after {
+            Some(after) => Some(self.find_view(after, user_id).await?.position),
+            None => None,
+        };
+        let before = match req.before {
+            Some(before) => Some(self.find_view(before, user_id).await?.position),
+            None => None,
+        };
+
+        let (lower, upper) = match (after, before) {
+            (Some(after), Some(before)) => {
+                if after > before {
+                    return Err(AppError::Validation(
+                        "The after view must be positioned before the before view".into(),
+                    ));
+                }
+                (Some(after), Some(before))
+            }
+            (Some(after), None) => {
+                let next = self.view_repo.next_position(user_id, &after, id).await?;
+                (Some(after), next)
+            }
+            (None, Some(before)) => {
+                let prev = self.view_repo.prev_position(user_id, &before, id).await?;
+                (prev, Some(before))
+            }
+            (None, None) => (None, None),
+        };
+
+        Ok(rank::between(lower.as_deref(), upper.as_deref()))
+    }
+}
@@ -0,0 +1,97 @@
+use chrono::{Datelike, Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        saved_view::start_of_day,
+        stats::{StatsInterval, StatsQuery, StatsResponse},
+    },
+    repositories::stats_repository::StatsRepository,
+    services::profile_service::ProfileService,
+};
+
+/// 推移で一度に集計できる日数の上限
+const MAX_STATS_DAYS: i64 = 366;
+
+/// 期間を省略した場合の日数
+const DEFAULT_STATS_DAYS: i64 = 30;
+
+#[derive(Clone)]
+pub struct StatsService {
+    stats_repo: StatsRepository,
+    profile_service: ProfileService,
+}
+
+impl StatsService {
+    pub fn new(stats_repo: StatsRepository, profile_service: ProfileService) -> Self {
+        Self {
+            stats_repo,
+            profile_service,
+        }
+    }
+
+    /// 件数・推移・平均リードタイム・連続完了日数を集計
+    /// 日付はプロフィールのタイムゾーンで区切る
+    pub async fn get(&self, user_id: Uuid, query: StatsQuery) -> AppResult<StatsResponse> {
+        let user = self.profile_service.user(user_id).await?;
+        let tz = user.tz();
+        let today = user.now().date_naive();
+
+        let to = query.to.unwrap_or(today);
+        let from = query
+            .from
+            .unwrap_or(to - Duration::days(DEFAULT_STATS_DAYS - 1));
+        if to < from {
+            return Err(AppError::Validation("to must not be before from".into()));
+        }
+        if (to - from).num_days() >= MAX_STATS_DAYS {
+            return Err(AppError::Validation(format!(
+                "Stats range must be at most {} days",
+                MAX_STATS_DAYS
+            )));
+        }
+
+        // 週単位の場合は最初の期間を週の始まりに揃える
+        let (first, step_days) = match query.interval {
+            StatsInterval::Day => (from, 1),
+            StatsInterval::Week => {
+                let offset = from.weekday().days_since(user.week_start.into());
+                (from - Duration::days(offset.into()), 7)
+            }
+        };
+        let (start, end) = to
+            .succ_opt()
+            .and_then(|next| Some((start_of_day(from, &tz)?, start_of_day(next, &tz)?)))
+            .ok_or_else(|| AppError::Validation("Stats range is out of range".into()))?;
+
+        let by_status = self.stats_repo.count_by_status(user_id).await?;
+        let by_priority = self.stats_repo.count_by_priority(user_id).await?;
+        let overdue = self.stats_repo.count_overdue(user_id, Utc::now()).await?;
+        let series = self
+            .stats_repo
+            .series(user_id, first, to, step_days, tz.name())
+            .await?;
+        let average_lead_time_seconds = self
+            .stats_repo
+            .average_lead_time(user_id, start, end)
+            .await?;
+        let streak = self
+            .stats_repo
+            .completion_streak(user_id, today, tz.name())
+            .await?;
+
+        Ok(StatsResponse {
+            from,
+            to,
+            interval: query.interval,
+            time_zone: user.time_zone,
+            by_status,
+            by_priority,
+            overdue,
+            series,
+            average_lead_time_seconds,
+            streak,
+        })
+    }
+}
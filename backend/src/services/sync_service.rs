@@ -0,0 +1,400 @@
+//! オフライン対応クライアントとの差分同期
+//!
+//! ToDoの作成・更新・削除のたびにトリガーで通し番号を振り、カーソル（通し番号）より後の変更を返す
+//! 完全に削除したToDoは記録（tombstone）を残し、削除として返す
+//!
+//! クライアントの変更は送信された順に1件ずつ適用する
+//! 更新・削除の元にしたバージョンより後にサーバーで変更されたフィールドは、サーバーの値を優先する
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        sync::{
+            SyncConflict, SyncDeletion, SyncMutation, SyncMutationResult, SyncMutationStatus,
+            SyncOperation, SyncPushRequest, SyncPushResponse, SyncResponse,
+        },
+        todo::{CreateTodoRequest, Todo, TodoPatch, TodoResponse},
+        todo_history::TodoSnapshot,
+    },
+    repositories::{
+        sync_repository::SyncRepository, todo_history_repository::TodoHistoryRepository,
+    },
+    services::todo_service::TodoService,
+};
+
+/// 一度に返す変更の件数（省略時）
+const DEFAULT_LIMIT: i64 = 500;
+/// 一度に返す変更の件数の上限
+const MAX_LIMIT: i64 = 1000;
+/// 一度に送信できる変更の件数の上限
+const MAX_MUTATIONS: usize = 500;
+/// 並行する更新と競合した場合に適用をやり直す回数
+const MAX_ATTEMPTS: usize = 3;
+
+/// 変更を適用した結果（状態・適用後のToDo・競合）
+type Outcome = (SyncMutationStatus, Option<TodoResponse>, Vec<SyncConflict>);
+
+/// カーソルより後の変更
+enum Change {
+    Todo(Box<Todo>),
+    Deleted(SyncDeletion),
+}
+
+#[derive(Clone)]
+pub struct SyncService {
+    sync_repo: SyncRepository,
+    history_repo: TodoHistoryRepository,
+    todo_service: TodoService,
+}
+
+impl SyncService {
+    pub fn new(
+        sync_repo: SyncRepository,
+        history_repo: TodoHistoryRepository,
+        todo_service: TodoService,
+    ) -> Self {
+        Self {
+            sync_repo,
+            history_repo,
+            todo_service,
+        }
+    }
+
+    /// カーソルより後の変更を取得
+    pub async fn pull(
+        &self,
+        user_id: Uuid,
+        since: Option<&str>,
+        limit: Option<i64>,
+    ) -> AppResult<SyncResponse> {
+        let since = Self::parse_cursor(since)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        self.changes(user_id, since, limit).await
+    }
+
+    /// クライアントの変更を順に適用し、カーソルより後の変更（適用した変更を含む）と結果を返す
+    pub async fn push(&self, user_id: Uuid, req: SyncPushRequest) -> AppResult<SyncPushResponse> {
+        let since = Self::parse_cursor(req.cursor.as_deref())?;
+        if req.mutations.len() > MAX_MUTATIONS {
+            return Err(AppError::Validation(format!(
+                "At most {} mutations can be sent at once",
+                MAX_MUTATIONS
+            )));
+        }
+
+        let mut results = Vec::with_capacity(req.mutations.len());
+        for mutation in req.mutations {
+            results.push(self.apply(user_id, mutation).await?);
+        }
+
+        Ok(SyncPushResponse {
+            changes: self.changes(user_id, since, DEFAULT_LIMIT).await?,
+            results,
+        })
+    }
+
+    /// 通し番号の順に `limit` 件まで取得
+    /// 初回（カーソルが0）は削除済みのToDoを返さない
+    async fn changes(&self, user_id: Uuid, since: i64, limit: i64) -> AppResult<SyncResponse> {
+        let initial = since == 0;
+        let todos = self
+            .sync_repo
+            .find_todos_since(user_id, since, limit + 1, !initial)
+            .await?;
+        let tombstones = if initial {
+            Vec::new()
+        } else {
+            self.sync_repo
+                .find_tombstones_since(user_id, since, limit + 1)
+                .await?
+        };
+
+        let mut changes: Vec<(i64, Change)> = todos
+            .into_iter()
+            .map(|t| (t.sync_seq, Change::Todo(Box::new(t.todo))))
+            .chain(tombstones.into_iter().map(|t| {
+                let deletion = SyncDeletion {
+                    id: t.todo_id,
+                    deleted_at: t.deleted_at,
+                };
+                (t.sync_seq, Change::Deleted(deletion))
+            }))
+            .collect();
+        changes.sort_by_key(|(seq, _)| *seq);
+        let has_more = changes.len() as i64 > limit;
+        changes.truncate(limit as usize);
+        let cursor = changes.last().map_or(since, |(seq, _)| *seq);
+
+        let mut todos = Vec::new();
+        let mut deleted = Vec::new();
+        for (_, change) in changes {
+            match change {
+                Change::Todo(todo) => match todo.deleted_at {
+                    Some(deleted_at) => deleted.push(SyncDeletion {
+                        id: todo.id,
+                        deleted_at,
+                    }),
+                    None => todos.push(*todo),
+                },
+                Change::Deleted(deletion) => deleted.push(deletion),
+            }
+        }
+
+        Ok(SyncResponse {
+            cursor: cursor.to_string(),
+            has_more,
+            todos: self.todo_service.responses(todos).await?,
+            deleted,
+        })
+    }
+
+    /// 変更を適用する
+    /// 検証エラーなどは結果の `rejected` として返し、並行する更新と競合した場合はやり直す
+    async fn apply(&self, user_id: Uuid, mutation: SyncMutation) -> AppResult<SyncMutationResult> {
+        let mut outcome = Err(AppError::Conflict(
+            "Todo has been modified by another request".into(),
+        ));
+        for _ in 0..MAX_ATTEMPTS {
+            outcome = match mutation.op {
+                SyncOperation::Create => self.apply_create(user_id, &mutation).await,
+                SyncOperation::Update => self.apply_update(user_id, &mutation).await,
+                SyncOperation::Delete => self.apply_delete(user_id, &mutation).await,
+            };
+            if !matches!(outcome, Err(AppError::PreconditionFailed(_))) {
+                break;
+            }
+        }
+
+        let (status, todo, conflicts, error) = match outcome {
+            Ok((status, todo, conflicts)) => (status, todo, conflicts, None),
+            Err(
+                AppError::Validation(msg)
+                | AppError::NotFound(msg)
+                | AppError::Conflict(msg)
+                | AppError::PreconditionFailed(msg),
+            ) => (SyncMutationStatus::Rejected, None, Vec::new(), Some(msg)),
+            Err(e) => return Err(e),
+        };
+
+        Ok(SyncMutationResult {
+            mutation_id: mutation.mutation_id,
+            id: mutation.id,
+            op: mutation.op,
+            status,
+            todo,
+            conflicts,
+            error,
+        })
+    }
+
+    /// クライアントが生成したIDで作成（作成済みの場合は再送とみなす）
+    async fn apply_create(&self, user_id: Uuid, mutation: &SyncMutation) -> AppResult<Outcome> {
+        if let Some(todo) = self.sync_repo.find_todo(mutation.id).await? {
+            if todo.user_id != user_id {
+                return Err(AppError::Conflict("Todo ID is already in use".into()));
+            }
+            let todo = match todo.deleted_at {
+                Some(_) => None,
+                None => self.response(todo).await?,
+            };
+            return Ok((SyncMutationStatus::Applied, todo, Vec::new()));
+        }
+        if let Some(tombstone) = self.sync_repo.find_tombstone(mutation.id).await? {
+            if tombstone.user_id != user_id {
+                return Err(AppError::Conflict("Todo ID is already in use".into()));
+            }
+            return Ok((SyncMutationStatus::Applied, None, Vec::new()));
+        }
+
+        Self::check_fields(&mutation.fields)?;
+        let req: CreateTodoRequest = serde_json::from_value(Value::Object(mutation.fields.clone()))
+            .map_err(|e| AppError::Validation(e.to_string()))?;
+        req.validate()
+            .map_err(|e| AppError::Validation(e.to_string()))?;
+
+        // 同じIDが並行して作成された場合はやり直して再送として扱う
+        let todo = self
+            .todo_service
+            .create_with_id(mutation.id, user_id, req)
+            .await?
+            .ok_or_else(|| AppError::PreconditionFailed("Todo ID was created concurrently".into()))?;
+
+        Ok((SyncMutationStatus::Applied, Some(todo), Vec::new()))
+    }
+
+    /// 変更したフィールドのみを適用する
+    /// 元のバージョンより後にサーバーでも変更されたフィールドは、値が異なればサーバーの値を優先する
+    async fn apply_update(&self, user_id: Uuid, mutation: &SyncMutation) -> AppResult<Outcome> {
+        let base_version = Self::base_version(mutation)?;
+        Self::check_fields(&mutation.fields)?;
+
+        let todo = match self.sync_repo.find_todo(mutation.id).await? {
+            Some(todo) if todo.user_id == user_id => todo,
+            Some(_) => return Err(AppError::NotFound("Todo not found".into())),
+            None => {
+                return match self.sync_repo.find_tombstone(mutation.id).await? {
+                    Some(tombstone) if tombstone.user_id == user_id => Ok((
+                        SyncMutationStatus::Conflict,
+                        None,
+                        vec![Self::deleted_conflict(tombstone.deleted_at)],
+                    )),
+                    _ => Err(AppError::NotFound("Todo not found".into())),
+                };
+            }
+        };
+        if let Some(deleted_at) = todo.deleted_at {
+            return Ok((
+                SyncMutationStatus::Conflict,
+                None,
+                vec![Self::deleted_conflict(deleted_at)],
+            ));
+        }
+
+        let mut patch = mutation.fields.clone();
+        let mut conflicts = Vec::new();
+        if base_version != todo.version {
+            let base = self.base_snapshot(&todo, base_version).await?;
+            let current = Self::snapshot(&todo)?;
+            for (field, client_value) in &mutation.fields {
+                let server_value = Self::field(&current, field);
+                if Self::field_changed(base.as_ref(), &current, field) && client_value != server_value {
+                    conflicts.push(SyncConflict {
+                        field: field.clone(),
+                        client_value: client_value.clone(),
+                        server_value: server_value.clone(),
+                    });
+                    patch.remove(field);
+                }
+            }
+        }
+
+        let todo = if patch.is_empty() {
+            self.response(todo).await?
+        } else {
+            Some(
+                self.todo_service
+                    .patch(
+                        todo.id,
+                        user_id,
+                        TodoPatch::Merge(Value::Object(patch)),
+                        Some(&[todo.version]),
+                    )
+                    .await?,
+            )
+        };
+
+        Ok((Self::status(&conflicts), todo, conflicts))
+    }
+
+    /// ゴミ箱へ移動する（削除済み・存在しない場合は再送とみなす）
+    /// 元のバージョンより後にサーバーで変更されていた場合は削除しない（`conflicts` は変更されたフィールド）
+    async fn apply_delete(&self, user_id: Uuid, mutation: &SyncMutation) -> AppResult<Outcome> {
+        let base_version = Self::base_version(mutation)?;
+
+        let todo = match self.sync_repo.find_todo(mutation.id).await? {
+            Some(todo) if todo.user_id != user_id => {
+                return Err(AppError::NotFound("Todo not found".into()))
+            }
+            Some(todo) if todo.deleted_at.is_none() => todo,
+            _ => return Ok((SyncMutationStatus::Applied, None, Vec::new())),
+        };
+
+        if base_version != todo.version {
+            let base = self.base_snapshot(&todo, base_version).await?;
+            let current = Self::snapshot(&todo)?;
+            let conflicts: Vec<SyncConflict> = TodoPatch::FIELDS
+                .iter()
+                .filter(|field| Self::field_changed(base.as_ref(), &current, field))
+                .map(|field| SyncConflict {
+                    field: field.to_string(),
+                    client_value: base
+                        .as_ref()
+                        .map_or(Value::Null, |b| Self::field(b, field).clone()),
+                    server_value: Self::field(&current, field).clone(),
+                })
+                .collect();
+            if !conflicts.is_empty() {
+                return Ok((SyncMutationStatus::Conflict, self.response(todo).await?, conflicts));
+            }
+        }
+
+        self.todo_service
+            .delete(todo.id, user_id, Some(&[todo.version]))
+            .await?;
+
+        Ok((SyncMutationStatus::Applied, None, Vec::new()))
+    }
+
+    /// カーソルを解析（省略時は0）
+    fn parse_cursor(cursor: Option<&str>) -> AppResult<i64> {
+        match cursor.map(str::trim).filter(|c| !c.is_empty()) {
+            Some(cursor) => cursor
+                .parse()
+                .ok()
+                .filter(|seq: &i64| *seq >= 0)
+                .ok_or_else(|| AppError::Validation(format!("Invalid cursor '{}'", cursor))),
+            None => Ok(0),
+        }
+    }
+
+    fn base_version(mutation: &SyncMutation) -> AppResult<i32> {
+        mutation
+            .base_version
+            .ok_or_else(|| AppError::Validation("baseVersion is required".into()))
+    }
+
+    /// 更新できるフィールドのみか確認
+    fn check_fields(fields: &Map<String, Value>) -> AppResult<()> {
+        match fields.keys().find(|k| !TodoPatch::FIELDS.contains(&k.as_str())) {
+            Some(unknown) => Err(AppError::Validation(format!("Unknown field: {}", unknown))),
+            None => Ok(()),
+        }
+    }
+
+    /// 元のバージョンの時点のスナップショット（履歴にない場合は `None`）
+    async fn base_snapshot(&self, todo: &Todo, base_version: i32) -> AppResult<Option<Value>> {
+        self.history_repo
+            .find_snapshot_at_todo_version(todo.id, base_version)
+            .await
+    }
+
+    fn snapshot(todo: &Todo) -> AppResult<Value> {
+        serde_json::to_value(TodoSnapshot::from(todo))
+            .map_err(|e| AppError::Internal(format!("Failed to serialize snapshot: {}", e)))
+    }
+
+    fn field<'a>(snapshot: &'a Value, field: &str) -> &'a Value {
+        snapshot.get(field).unwrap_or(&Value::Null)
+    }
+
+    /// 元のバージョンより後にサーバーで変更されたか（元のスナップショットがない場合は変更されたとみなす）
+    fn field_changed(base: Option<&Value>, current: &Value, field: &str) -> bool {
+        base.is_none_or(|base| Self::field(base, field) != Self::field(current, field))
+    }
+
+    fn deleted_conflict(deleted_at: DateTime<Utc>) -> SyncConflict {
+        SyncConflict {
+            field: "deletedAt".into(),
+            client_value: Value::Null,
+            server_value: json!(deleted_at),
+        }
+    }
+
+    fn status(conflicts: &[SyncConflict]) -> SyncMutationStatus {
+        if conflicts.is_empty() {
+            SyncMutationStatus::Applied
+        } else {
+            SyncMutationStatus::Conflict
+        }
+    }
+
+    async fn response(&self, todo: Todo) -> AppResult<Option<TodoResponse>> {
+        Ok(self.todo_service.responses(vec![todo]).await?.pop())
+    }
+}
@@ -0,0 +1,271 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        saved_view::start_of_day,
+        template::{
+            render_placeholders, InstantiateTemplateRequest, InstantiateTemplateResponse,
+            TemplateRequest, TemplateResponse, TodoTemplate,
+        },
+        todo::{normalize_tags, TodoPriority, TodoStatus},
+        todo_history::TodoHistoryAction,
+    },
+    repositories::{
+        template_repository::TemplateRepository, todo_history_repository::TodoHistoryRepository,
+        todo_repository::TodoRepository,
+    },
+    services::{profile_service::ProfileService, todo_service::TodoService},
+};
+
+/// タイトルの最大文字数（ToDoの作成時と同じ）
+const MAX_TITLE_LENGTH: usize = 255;
+
+#[derive(Clone)]
+pub struct TemplateService {
+    template_repo: TemplateRepository,
+    todo_repo: TodoRepository,
+    todo_service: TodoService,
+    profile_service: ProfileService,
+}
+
+impl TemplateService {
+    pub fn new(
+        template_repo: TemplateRepository,
+        todo_repo: TodoRepository,
+        todo_service: TodoService,
+        profile_service: ProfileService,
+    ) -> Self {
+        Self {
+            template_repo,
+            todo_repo,
+            todo_service,
+            profile_service,
+        }
+    }
+
+    /// テンプレート一覧を取得
+    pub async fn list(&self, user_id: Uuid) -> AppResult<Vec<TemplateResponse>> {
+        let templates = self.template_repo.find_by_user_id(user_id).await?;
+
+        Ok(templates.into_iter().map(|t| t.into()).collect())
+    }
+
+    /// テンプレート詳細を取得
+    pub async fn get_by_id(&self, id: Uuid, user_id: Uuid) -> AppResult<TemplateResponse> {
+        Ok(self.find_template(id, user_id).await?.into())
+    }
+
+    /// テンプレートを作成
+    /// 名前はユーザーごとに一意
+    pub async fn create(&self, user_id: Uuid, req: TemplateRequest) -> AppResult<TemplateResponse> {
+        self.check_name(user_id, &req.name, None).await?;
+        let tags = normalize_tags(req.tags.as_deref().unwrap_or_default());
+
+        let template = self
+            .template_repo
+            .create(
+                user_id,
+                &req.name,
+                &req.title,
+                req.description.as_deref(),
+                &req.priority.unwrap_or(TodoPriority::Medium),
+                req.due_offset_days,
+                &tags,
+                req.subtasks.as_deref().unwrap_or_default(),
+            )
+            .await
+            .map_err(Self::map_unique_violation)?;
+
+        Ok(template.into())
+    }
+
+    /// テンプレートを更新（全項目を置き換える）
+    pub async fn update(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: TemplateRequest,
+    ) -> AppResult<TemplateResponse> {
+        self.find_template(id, user_id).await?;
+        self.check_name(user_id, &req.name, Some(id)).await?;
+        let tags = normalize_tags(req.tags.as_deref().unwrap_or_default());
+
+        let template = self
+            .template_repo
+            .update(
+                id,
+                user_id,
+                &req.name,
+                &req.title,
+                req.description.as_deref(),
+                &req.priority.unwrap_or(TodoPriority::Medium),
+                req.due_offset_days,
+                &tags,
+                req.subtasks.as_deref().unwrap_or_default(),
+            )
+            .await
+            .map_err(Self::map_unique_violation)?
+            .ok_or_else(|| AppError::NotFound("Template not found".into()))?;
+
+        Ok(template.into())
+    }
+
+    /// テンプレートを削除
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
+        if !self.template_repo.delete(id, user_id).await? {
+            return Err(AppError::NotFound("Template not found".into()));
+        }
+
+        Ok(())
+    }
+
+    /// テンプレートからToDoを作成
+    /// 親ToDoとサブタスクを1つのトランザクションで作成し、末尾に順番に追加する
+    pub async fn instantiate(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: InstantiateTemplateRequest,
+    ) -> AppResult<InstantiateTemplateResponse> {
+        let template = self.find_template(id, user_id).await?;
+        let now = self.profile_service.user(user_id).await?.now();
+        let tz = now.timezone();
+        let date = req.date.unwrap_or_else(|| now.date_naive());
+        let title = Self::render_title(&template.title, date)?;
+        let subtask_titles = template
+            .subtasks
+            .iter()
+            .map(|subtask| Self::render_title(&subtask.title, date))
+            .collect::<AppResult<Vec<_>>>()?;
+        let status = TodoStatus::Pending;
+
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let mut positions =
+            TodoService::append_positions(&mut tx, user_id, template.subtasks.len() + 1).await?;
+        positions.reverse();
+
+        let parent = TodoRepository::create_with(
+            &mut *tx,
+            user_id,
+            &title,
+            template
+                .description
+                .as_deref()
+                .map(|d| render_placeholders(d, date))
+                .as_deref(),
+            Self::due_date(date, template.due_offset_days, &tz)?,
+            &status,
+            &template.priority,
+            &template.tags,
+            &positions.pop().unwrap_or_default(),
+            None,
+            None,
+            None,
+        )
+        .await?;
+
+        let mut todos = vec![parent];
+        for (subtask, title) in template.subtasks.iter().zip(&subtask_titles) {
+            let todo = TodoRepository::create_with(
+                &mut *tx,
+                user_id,
+                title,
+                subtask
+                    .description
+                    .as_deref()
+                    .map(|d| render_placeholders(d, date))
+                    .as_deref(),
+                Self::due_date(date, subtask.due_offset_days, &tz)?,
+                &status,
+                &template.priority,
+                &template.tags,
+                &positions.pop().unwrap_or_default(),
+                None,
+                None,
+                Some(todos[0].id),
+            )
+            .await?;
+            todos.push(todo);
+        }
+
+        for todo in &todos {
+            if let Some((changes, snapshot)) = TodoService::history_entry(None, todo)? {
+                TodoHistoryRepository::create_with(
+                    &mut *tx,
+                    todo.id,
+                    user_id,
+                    TodoHistoryAction::Created,
+                    &changes,
+                    &snapshot,
+                )
+                .await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(InstantiateTemplateResponse {
+            todos: self.todo_service.responses(todos).await?,
+        })
+    }
+
+    /// 認可チェック付きでテンプレートを取得
+    async fn find_template(&self, id: Uuid, user_id: Uuid) -> AppResult<TodoTemplate> {
+        self.template_repo
+            .find_by_id_and_user_id(id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Template not found".into()))
+    }
+
+    /// 同名のテンプレートがないことを確認
+    async fn check_name(&self, user_id: Uuid, name: &str, exclude_id: Option<Uuid>) -> AppResult<()> {
+        if let Some(existing) = self.template_repo.find_by_name(user_id, name).await? {
+            if Some(existing.id) != exclude_id {
+                return Err(AppError::Conflict("Template name already exists".into()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 同時に作成された場合は一意制約で検出する
+    fn map_unique_violation(e: AppError) -> AppError {
+        match e {
+            AppError::Database(sqlx::Error::Database(ref db)) if db.is_unique_violation() => {
+                AppError::Conflict("Template name already exists".into())
+            }
+            e => e,
+        }
+    }
+
+    /// タイトルのプレースホルダーを置換（置換後に最大文字数を超える場合はエラー）
+    fn render_title(title: &str, date: NaiveDate) -> AppResult<String> {
+        let rendered = render_placeholders(title, date);
+        if rendered.chars().count() > MAX_TITLE_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Rendered title must be at most {} characters",
+                MAX_TITLE_LENGTH
+            )));
+        }
+
+        Ok(rendered)
+    }
+
+    /// 基準日からの日数で期限（タイムゾーンでの0時）を計算
+    fn due_date(
+        date: NaiveDate,
+        offset_days: Option<i32>,
+        tz: &Tz,
+    ) -> AppResult<Option<DateTime<Utc>>> {
+        let Some(offset) = offset_days else {
+            return Ok(None);
+        };
+
+        date.checked_add_signed(Duration::days(offset.into()))
+            .and_then(|d| start_of_day(d, tz))
+            .map(Some)
+            .ok_or_else(|| AppError::Validation("Due date is out of range".into()))
+    }
+}
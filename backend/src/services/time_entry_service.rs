@@ -0,0 +1,168 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::time_entry::{
+        CreateTimeEntryRequest, TimeEntryResponse, TimeReportQuery, TimeReportResponse,
+    },
+    models::saved_view::start_of_day,
+    repositories::{time_entry_repository::TimeEntryRepository, todo_repository::TodoRepository},
+    services::profile_service::ProfileService,
+};
+
+/// レポートで一度に集計できる日数の上限
+const MAX_REPORT_DAYS: i64 = 366;
+
+#[derive(Clone)]
+pub struct TimeEntryService {
+    time_entry_repo: TimeEntryRepository,
+    todo_repo: TodoRepository,
+    profile_service: ProfileService,
+}
+
+impl TimeEntryService {
+    pub fn new(
+        time_entry_repo: TimeEntryRepository,
+        todo_repo: TodoRepository,
+        profile_service: ProfileService,
+    ) -> Self {
+        Self {
+            time_entry_repo,
+            todo_repo,
+            profile_service,
+        }
+    }
+
+    /// タイマーを開始
+    /// 計測中のタイマーはユーザーごとに1つまで
+    pub async fn start_timer(&self, todo_id: Uuid, user_id: Uuid) -> AppResult<TimeEntryResponse> {
+        self.check_todo(todo_id, user_id).await?;
+
+        if let Some(running) = self.time_entry_repo.find_running_by_user_id(user_id).await? {
+            return Err(AppError::Conflict(format!(
+                "A timer is already running for todo {}",
+                running.todo_id
+            )));
+        }
+
+        let entry = self
+            .time_entry_repo
+            .create(todo_id, user_id, Utc::now(), None, None)
+            .await
+            .map_err(|e| match e {
+                // 同時に開始された場合は一意制約で検出する
+                AppError::Database(sqlx::Error::Database(ref db)) if db.is_unique_violation() => {
+                    AppError::Conflict("A timer is already running".into())
+                }
+                e => e,
+            })?;
+
+        Ok(entry.into())
+    }
+
+    /// タイマーを停止
+    pub async fn stop_timer(&self, todo_id: Uuid, user_id: Uuid) -> AppResult<TimeEntryResponse> {
+        self.check_todo(todo_id, user_id).await?;
+
+        let entry = self
+            .time_entry_repo
+            .stop(todo_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("No running timer for this todo".into()))?;
+
+        Ok(entry.into())
+    }
+
+    /// ToDoの作業時間の記録一覧を取得
+    pub async fn list(&self, todo_id: Uuid, user_id: Uuid) -> AppResult<Vec<TimeEntryResponse>> {
+        self.check_todo(todo_id, user_id).await?;
+
+        let entries = self.time_entry_repo.find_by_todo_id(todo_id).await?;
+
+        Ok(entries.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// 作業時間を手動で登録
+    pub async fn create(
+        &self,
+        todo_id: Uuid,
+        user_id: Uuid,
+        req: CreateTimeEntryRequest,
+    ) -> AppResult<TimeEntryResponse> {
+        if req.ended_at <= req.started_at {
+            return Err(AppError::Validation("endedAt must be after startedAt".into()));
+        }
+        self.check_todo(todo_id, user_id).await?;
+
+        let entry = self
+            .time_entry_repo
+            .create(
+                todo_id,
+                user_id,
+                req.started_at,
+                Some(req.ended_at),
+                req.note.as_deref(),
+            )
+            .await?;
+
+        Ok(entry.into())
+    }
+
+    /// 作業時間の記録を削除
+    pub async fn delete(&self, id: Uuid, todo_id: Uuid, user_id: Uuid) -> AppResult<()> {
+        self.check_todo(todo_id, user_id).await?;
+
+        let deleted = self.time_entry_repo.delete(id, todo_id, user_id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Time entry not found".into()));
+        }
+        Ok(())
+    }
+
+    /// 期間内の作業時間を日別・ToDo別・タグ別に集計
+    /// 日付はプロフィールのタイムゾーンで区切る
+    pub async fn report(&self, user_id: Uuid, query: TimeReportQuery) -> AppResult<TimeReportResponse> {
+        if query.to < query.from {
+            return Err(AppError::Validation("to must not be before from".into()));
+        }
+        if (query.to - query.from).num_days() >= MAX_REPORT_DAYS {
+            return Err(AppError::Validation(format!(
+                "Report range must be at most {} days",
+                MAX_REPORT_DAYS
+            )));
+        }
+
+        let tz = self.profile_service.user(user_id).await?.tz();
+        let (from, to) = query
+            .to
+            .succ_opt()
+            .and_then(|to| Some((start_of_day(query.from, &tz)?, start_of_day(to, &tz)?)))
+            .ok_or_else(|| AppError::Validation("Report range is out of range".into()))?;
+
+        let by_day = self
+            .time_entry_repo
+            .report_by_day(user_id, from, to, tz.name())
+            .await?;
+        let by_todo = self.time_entry_repo.report_by_todo(user_id, from, to).await?;
+        let by_tag = self.time_entry_repo.report_by_tag(user_id, from, to).await?;
+
+        Ok(TimeReportResponse {
+            from: query.from,
+            to: query.to,
+            total_seconds: by_day.iter().map(|d| d.seconds).sum(),
+            by_day,
+            by_todo,
+            by_tag,
+        })
+    }
+
+    /// 認可チェック（ゴミ箱内のToDoは対象外）
+    async fn check_todo(&self, todo_id: Uuid, user_id: Uuid) -> AppResult<()> {
+        self.todo_repo
+            .find_by_id_and_user_id(todo_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Todo not found".into()))?;
+        Ok(())
+    }
+}
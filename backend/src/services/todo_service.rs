@@ -1,42 +1,187 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use json_patch::PatchErrorKind;
+use serde_json::{json, Value};
+use sqlx::PgConnection;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::{
     error::{AppError, AppResult},
-    models::todo::{
-        CreateTodoRequest, TodoListResponse, TodoPriority, TodoQuery, TodoResponse, TodoStatus,
-        UpdateTodoRequest, UpdateTodoStatusRequest,
+    models::{
+        todo::{
+            is_valid_tag, normalize_tags, BulkTodoAction, BulkTodoRequest, BulkTodoResponse,
+            BulkTodoResult, CreateTodoRequest, MoveTodoRequest, QuickAddTodoRequest,
+            QuickAddTodoResponse, Todo, TodoListResponse, TodoPatch, TodoPriority,
+            TodoQuery, TodoResponse, TodoStatus, TrashQuery, UpdateTodoRequest,
+            UpdateTodoStatusRequest, MAX_TAG_LENGTH,
+        },
+        todo_dependency::AddBlockerRequest,
+        todo_history::{TodoHistoryAction, TodoHistoryResponse, TodoSnapshot},
+    },
+    repositories::{
+        board_column_repository::BoardColumnRepository,
+        time_entry_repository::TimeEntryRepository,
+        todo_dependency_repository::TodoDependencyRepository,
+        todo_history_repository::TodoHistoryRepository, todo_repository::TodoRepository,
+    },
+    services::{
+        filter::{self, FilterExpr},
+        profile_service::ProfileService,
+        quick_add, rank,
     },
-    repositories::todo_repository::TodoRepository,
 };
 
+/// 一括操作で一度に処理できる件数の上限
+const MAX_BULK_ITEMS: usize = 500;
+
 #[derive(Clone)]
 pub struct TodoService {
     todo_repo: TodoRepository,
+    history_repo: TodoHistoryRepository,
+    dependency_repo: TodoDependencyRepository,
+    time_entry_repo: TimeEntryRepository,
+    profile_service: ProfileService,
 }
 
 impl TodoService {
-    pub fn new(todo_repo: TodoRepository) -> Self {
-        Self { todo_repo }
+    pub fn new(
+        todo_repo: TodoRepository,
+        history_repo: TodoHistoryRepository,
+        dependency_repo: TodoDependencyRepository,
+        time_entry_repo: TimeEntryRepository,
+        profile_service: ProfileService,
+    ) -> Self {
+        Self {
+            todo_repo,
+            history_repo,
+            dependency_repo,
+            time_entry_repo,
+            profile_service,
+        }
     }
 
     /// ToDoの作成
     pub async fn create(&self, user_id: Uuid, req: CreateTodoRequest) -> AppResult<TodoResponse> {
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let todo = self.create_in(&mut tx, user_id, req).await?;
+        tx.commit().await?;
+
+        self.response(todo).await
+    }
+
+    /// 指定したトランザクション内でToDoを作成し、履歴を記録する
+    /// 関連するデータと同時に作成する場合に使用する（コミットは呼び出し元で行う）
+    pub async fn create_in(
+        &self,
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        req: CreateTodoRequest,
+    ) -> AppResult<Todo> {
         let status = req.status.unwrap_or(TodoStatus::Pending);
         let priority = req.priority.unwrap_or(TodoPriority::Medium);
+        Self::check_schedule(req.start_date, req.due_date)?;
+        let tags = normalize_tags(req.tags.as_deref().unwrap_or_default());
+        let position = Self::append_position(conn, user_id).await?;
 
-        let todo = self
-            .todo_repo
-            .create(
-                user_id,
-                &req.title,
-                req.description.as_deref(),
-                req.due_date,
-                &status,
-                &priority,
-            )
-            .await?;
+        let todo = TodoRepository::create_with(
+            &mut *conn,
+            user_id,
+            &req.title,
+            req.description.as_deref(),
+            req.due_date,
+            &status,
+            &priority,
+            &tags,
+            &position,
+            req.estimate_minutes,
+            req.start_date,
+            None,
+        )
+        .await?;
+        Self::record_history(conn, user_id, TodoHistoryAction::Created, None, &todo).await?;
 
-        Ok(todo.into())
+        Ok(todo)
+    }
+
+    /// クライアントが生成したIDでToDoを作成（同期用）
+    /// IDが既に使われている場合は `None`
+    pub async fn create_with_id(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: CreateTodoRequest,
+    ) -> AppResult<Option<TodoResponse>> {
+        let status = req.status.unwrap_or(TodoStatus::Pending);
+        let priority = req.priority.unwrap_or(TodoPriority::Medium);
+        Self::check_schedule(req.start_date, req.due_date)?;
+        let tags = normalize_tags(req.tags.as_deref().unwrap_or_default());
+
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let position = Self::append_position(&mut tx, user_id).await?;
+        let Some(todo) = TodoRepository::create_with_id(
+            &mut *tx,
+            id,
+            user_id,
+            &req.title,
+            req.description.as_deref(),
+            req.due_date,
+            &status,
+            &priority,
+            &tags,
+            &position,
+            req.estimate_minutes,
+            req.start_date,
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+        Self::record_history(&mut tx, user_id, TodoHistoryAction::Created, None, &todo).await?;
+        tx.commit().await?;
+
+        self.response(todo).await.map(Some)
+    }
+
+    /// 1行の入力を解析してToDoを作成
+    /// 解析結果と作成したToDoを返す
+    pub async fn quick_add(
+        &self,
+        user_id: Uuid,
+        req: QuickAddTodoRequest,
+    ) -> AppResult<QuickAddTodoResponse> {
+        let user = self.profile_service.user(user_id).await?;
+        let time_zone: Tz = match req.time_zone.as_deref() {
+            Some(name) => name
+                .parse()
+                .map_err(|_| AppError::Validation(format!("Invalid time zone '{}'", name)))?,
+            None => user.tz(),
+        };
+        let parsed = quick_add::parse(
+            &req.text,
+            Utc::now().with_timezone(&time_zone),
+            user.week_start.into(),
+        );
+
+        let create = CreateTodoRequest {
+            title: parsed.title.clone(),
+            description: None,
+            start_date: None,
+            due_date: parsed.due_date,
+            status: None,
+            priority: parsed.priority.clone(),
+            tags: Some(parsed.tags.clone()),
+            estimate_minutes: None,
+        };
+        create
+            .validate()
+            .map_err(|e| AppError::Validation(e.to_string()))?;
+
+        let todo = self.create(user_id, create).await?;
+
+        Ok(QuickAddTodoResponse { parsed, todo })
     }
 
     /// ToDo一覧を取得
@@ -44,10 +189,15 @@ impl TodoService {
         let per_page = query.per_page.clamp(1, 100);
         let page = query.page.max(1);
 
-        let (todos, total) = self.todo_repo.find_by_user_id(user_id, &query).await?;
+        let filter = self.parse_filter(user_id, &query).await?;
+
+        let (todos, total) = self
+            .todo_repo
+            .find_by_user_id(user_id, &query, filter.as_ref())
+            .await?;
 
         Ok(TodoListResponse {
-            items: todos.into_iter().map(|t| t.into()).collect(),
+            items: self.responses(todos).await?,
             total,
             page,
             per_page,
@@ -57,13 +207,9 @@ impl TodoService {
     /// ToDo詳細を取得
     /// 認可チェックも実施
     pub async fn get_by_id(&self, id: Uuid, user_id: Uuid) -> AppResult<TodoResponse> {
-        let todo = self
-            .todo_repo
-            .find_by_id_and_user_id(id, user_id)
-            .await?
-            .ok_or_else(|| AppError::NotFound("Todo not found".into()))?;
+        let todo = self.find_todo(id, user_id).await?;
 
-        Ok(todo.into())
+        self.response(todo).await
     }
 
     /// ToDoを更新
@@ -73,46 +219,824 @@ impl TodoService {
         id: Uuid,
         user_id: Uuid,
         req: UpdateTodoRequest,
+        expected_versions: Option<&[i32]>,
     ) -> AppResult<TodoResponse> {
-        let todo = self
-            .todo_repo
-            .update(
-                id,
-                user_id,
-                req.title.as_deref(),
-                req.description.as_deref(),
-                req.due_date,
-                req.status.as_ref(),
-                req.priority.as_ref(),
-            )
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let before = Self::lock_todo(&mut tx, id, user_id).await?;
+        Self::check_version(&before, expected_versions)?;
+        Self::check_schedule(
+            req.start_date.or(before.start_date),
+            req.due_date.or(before.due_date),
+        )?;
+        let tags = req.tags.as_deref().map(normalize_tags);
+
+        let todo = TodoRepository::update_with(
+            &mut *tx,
+            id,
+            user_id,
+            req.title.as_deref(),
+            req.description.as_deref(),
+            req.due_date,
+            req.status.as_ref(),
+            req.priority.as_ref(),
+            tags.as_deref(),
+            req.estimate_minutes,
+            req.start_date,
+            expected_versions,
+        )
+        .await?
+        .ok_or_else(|| Self::write_conflict(expected_versions))?;
+        Self::check_status_change(&mut tx, &before, &todo, false).await?;
+        Self::record_history(&mut tx, user_id, TodoHistoryAction::Updated, Some(&before), &todo)
+            .await?;
+        tx.commit().await?;
+
+        self.response(todo).await
+    }
+
+    /// JSON Merge Patch / JSON Patch による部分更新
+    /// `null` や `remove` で任意項目（説明・期限）を削除できる
+    pub async fn patch(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        patch: TodoPatch,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<TodoResponse> {
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let before = Self::lock_todo(&mut tx, id, user_id).await?;
+        Self::check_version(&before, expected_versions)?;
+
+        let patched = Self::apply_patch(&before, &patch)?;
+
+        let todo = TodoRepository::replace_with(&mut *tx, id, user_id, &patched, expected_versions)
             .await?
-            .ok_or_else(|| AppError::NotFound("Todo not found".into()))?;
-        
-        Ok(todo.into())
+            .ok_or_else(|| Self::write_conflict(expected_versions))?;
+        Self::check_status_change(&mut tx, &before, &todo, false).await?;
+        Self::record_history(&mut tx, user_id, TodoHistoryAction::Updated, Some(&before), &todo)
+            .await?;
+        tx.commit().await?;
+
+        self.response(todo).await
     }
-    
+
     /// ステータスの更新
     pub async fn update_status(
         &self,
         id: Uuid,
         user_id: Uuid,
         req: UpdateTodoStatusRequest,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<TodoResponse> {
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let before = Self::lock_todo(&mut tx, id, user_id).await?;
+        Self::check_version(&before, expected_versions)?;
+
+        let todo =
+            TodoRepository::update_status_with(&mut *tx, id, user_id, &req.status, expected_versions)
+                .await?
+                .ok_or_else(|| Self::write_conflict(expected_versions))?;
+        Self::check_status_change(&mut tx, &before, &todo, req.force).await?;
+        Self::record_history(
+            &mut tx,
+            user_id,
+            TodoHistoryAction::StatusChanged,
+            Some(&before),
+            &todo,
+        )
+        .await?;
+        tx.commit().await?;
+
+        self.response(todo).await
+    }
+
+    /// ToDoを並び替える
+    /// 隣接するToDoの間のキーを生成するため、他のToDoは更新しない
+    /// 列を指定した場合はWIP制限を確認したうえで列へ移動する
+    pub async fn move_todo(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: MoveTodoRequest,
+        expected_versions: Option<&[i32]>,
     ) -> AppResult<TodoResponse> {
-        let todo = self
+        if req.before.is_none() && req.after.is_none() && req.column_id.is_none() {
+            return Err(AppError::Validation("Specify before, after or columnId".into()));
+        }
+        if req.before == Some(id) || req.after == Some(id) {
+            return Err(AppError::Validation("Cannot move a todo relative to itself".into()));
+        }
+
+        let current = self.find_todo(id, user_id).await?;
+        Self::check_version(&current, expected_versions)?;
+
+        let position = if req.before.is_some() || req.after.is_some() {
+            match self.position_between(id, user_id, &req).await? {
+                Some(position) => Some(position),
+                None => {
+                    // キーの間に空きがない場合は振り直してから再計算
+                    self.todo_repo.rebalance_positions(user_id).await?;
+                    Some(
+                        self.position_between(id, user_id, &req)
+                            .await?
+                            .ok_or_else(|| AppError::Conflict("Failed to compute position".into()))?,
+                    )
+                }
+            }
+        } else {
+            None
+        };
+
+        let todo = match (req.column_id, position) {
+            (Some(column_id), position) => {
+                self.move_to_column(&current, column_id, position.as_deref(), expected_versions)
+                    .await?
+            }
+            (None, Some(position)) => self
+                .todo_repo
+                .update_position(id, user_id, &position, expected_versions)
+                .await?
+                .ok_or_else(|| Self::write_conflict(expected_versions))?,
+            (None, None) => unreachable!("before or after is specified"),
+        };
+
+        self.response(todo).await
+    }
+
+    /// ボードの列へ移動する
+    /// 移動先の列をロックし、WIP制限を超える場合は移動しない
+    async fn move_to_column(
+        &self,
+        before: &Todo,
+        column_id: Uuid,
+        position: Option<&str>,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<Todo> {
+        let (id, user_id) = (before.id, before.user_id);
+
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        // ステータスの変更と同じ順序（ユーザー→ToDo→列）でロックする
+        Self::lock_todo(&mut tx, id, user_id).await?;
+        let column = BoardColumnRepository::lock_by_id(&mut tx, column_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Column not found".into()))?;
+
+        if let Some(wip_limit) = column.wip_limit {
+            let current = match before.column_id {
+                Some(current) => Some(current),
+                None => BoardColumnRepository::find_default_id(&mut tx, user_id, &before.status).await?,
+            };
+            let count = BoardColumnRepository::count_todos(&mut tx, user_id, column.id, id).await?;
+            if current != Some(column.id) && count >= i64::from(wip_limit) {
+                return Err(AppError::Conflict(format!(
+                    "WIP limit of column '{}' ({}) has been reached",
+                    column.name, wip_limit
+                )));
+            }
+        }
+
+        let todo = TodoRepository::move_to_column(
+            &mut tx,
+            id,
+            user_id,
+            column.id,
+            &column.category,
+            position,
+            expected_versions,
+        )
+        .await?
+        .ok_or_else(|| Self::write_conflict(expected_versions))?;
+        if todo.status != before.status {
+            Self::check_blockers(&mut tx, &[id], &todo.status).await?;
+        }
+
+        if let Some((changes, snapshot)) = Self::history_entry(Some(before), &todo)? {
+            TodoHistoryRepository::create_with(
+                &mut *tx,
+                todo.id,
+                user_id,
+                TodoHistoryAction::StatusChanged,
+                &changes,
+                &snapshot,
+            )
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(todo)
+    }
+
+    /// ランクキーが長くなりすぎたユーザーのキーを振り直す
+    /// 振り直したユーザー数を返す
+    pub async fn rebalance_positions(&self) -> AppResult<usize> {
+        let user_ids = self
             .todo_repo
-            .update_status(id, user_id, &req.status)
+            .find_user_ids_with_long_positions(rank::MAX_KEY_LENGTH)
+            .await?;
+
+        for user_id in &user_ids {
+            self.todo_repo.rebalance_positions(*user_id).await?;
+        }
+
+        Ok(user_ids.len())
+    }
+
+    /// ToDoの削除
+    pub async fn delete(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<()> {
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let before = Self::lock_todo(&mut tx, id, user_id).await?;
+        Self::check_version(&before, expected_versions)?;
+
+        let todo = TodoRepository::delete_with(&mut *tx, id, user_id, expected_versions)
             .await?
+            .ok_or_else(|| Self::write_conflict(expected_versions))?;
+        Self::record_history(&mut tx, user_id, TodoHistoryAction::Deleted, Some(&before), &todo)
+            .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// 複数のToDoに同じ操作を1トランザクションで適用
+    /// 存在しない・他ユーザーのToDoは失敗として結果に含める（重複したIDは1件として扱う）
+    pub async fn bulk(&self, user_id: Uuid, req: BulkTodoRequest) -> AppResult<BulkTodoResponse> {
+        let mut ids = match (req.ids, req.filter) {
+            (Some(ids), None) => ids,
+            (None, Some(filter)) => {
+                let expr = self.parse_filter(user_id, &filter).await?;
+                self.todo_repo
+                    .find_ids_by_filter(user_id, &filter, expr.as_ref())
+                    .await?
+            }
+            _ => return Err(AppError::Validation("Specify either ids or filter".into())),
+        };
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+        if ids.len() > MAX_BULK_ITEMS {
+            return Err(AppError::Validation(format!(
+                "At most {} todos can be processed at once",
+                MAX_BULK_ITEMS
+            )));
+        }
+        if let BulkTodoAction::AddTag { ref tag } | BulkTodoAction::RemoveTag { ref tag } =
+            req.action
+        {
+            if !is_valid_tag(tag) {
+                return Err(AppError::Validation(format!(
+                    "Tags must be between 1 and {} characters",
+                    MAX_TAG_LENGTH
+                )));
+            }
+        }
+
+        let history_action = match req.action {
+            BulkTodoAction::SetStatus { .. } => TodoHistoryAction::StatusChanged,
+            BulkTodoAction::Delete => TodoHistoryAction::Deleted,
+            _ => TodoHistoryAction::Updated,
+        };
+
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let before = TodoRepository::lock_by_ids(&mut tx, user_id, &ids).await?;
+        if let BulkTodoAction::SetDueDate { due_date } = req.action {
+            for todo in &before {
+                Self::check_schedule(todo.start_date, due_date)?;
+            }
+        }
+        let after = TodoRepository::bulk_update(&mut tx, user_id, &ids, &req.action).await?;
+        if let BulkTodoAction::SetStatus { ref status } = req.action {
+            let changed: Vec<Uuid> = before
+                .iter()
+                .filter(|b| b.status != *status)
+                .map(|b| b.id)
+                .collect();
+            if !changed.is_empty() {
+                Self::check_blockers(&mut tx, &changed, status).await?;
+                Self::check_wip_limit(&mut tx, user_id, status).await?;
+            }
+        }
+
+        for todo in &after {
+            let previous = before.iter().find(|b| b.id == todo.id);
+            if let Some((changes, snapshot)) = Self::history_entry(previous, todo)? {
+                TodoHistoryRepository::create_with(
+                    &mut *tx,
+                    todo.id,
+                    user_id,
+                    history_action,
+                    &changes,
+                    &snapshot,
+                )
+                .await?;
+            }
+        }
+        tx.commit().await?;
+
+        let mut after: HashMap<Uuid, TodoResponse> = self
+            .responses(after)
+            .await?
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect();
+        let results: Vec<BulkTodoResult> = ids
+            .into_iter()
+            .map(|id| match after.remove(&id) {
+                Some(todo) => BulkTodoResult {
+                    id,
+                    success: true,
+                    todo: Some(todo),
+                    error: None,
+                },
+                None => BulkTodoResult {
+                    id,
+                    success: false,
+                    todo: None,
+                    error: Some("Todo not found".into()),
+                },
+            })
+            .collect();
+        let succeeded = results.iter().filter(|r| r.success).count();
+
+        Ok(BulkTodoResponse {
+            failed: results.len() - succeeded,
+            succeeded,
+            results,
+        })
+    }
+
+    /// 依存先（ブロッカー）を追加
+    /// 自分自身や、依存関係が循環するToDoは指定できない
+    pub async fn add_blocker(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: AddBlockerRequest,
+    ) -> AppResult<TodoResponse> {
+        if req.blocker_id == id {
+            return Err(AppError::Validation("A todo cannot block itself".into()));
+        }
+
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        // 同時に逆向きの依存関係が追加されないよう両方のToDoをロックする
+        let locked = TodoRepository::lock_by_ids(&mut tx, user_id, &[id, req.blocker_id]).await?;
+        let todo = locked
+            .iter()
+            .find(|t| t.id == id)
+            .cloned()
             .ok_or_else(|| AppError::NotFound("Todo not found".into()))?;
-        
-        Ok(todo.into())
+        if !locked.iter().any(|t| t.id == req.blocker_id) {
+            return Err(AppError::NotFound("Blocker todo not found".into()));
+        }
+
+        if TodoDependencyRepository::creates_cycle(&mut tx, id, req.blocker_id).await? {
+            return Err(AppError::Conflict("Dependency would create a cycle".into()));
+        }
+        TodoDependencyRepository::create(&mut tx, id, req.blocker_id).await?;
+        tx.commit().await?;
+
+        self.response(todo).await
     }
-    
-    /// ToDoの削除
-    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
-        let deleted = self.todo_repo.delete(id, user_id).await?;
+
+    /// 依存先（ブロッカー）を削除
+    pub async fn remove_blocker(&self, id: Uuid, user_id: Uuid, blocker_id: Uuid) -> AppResult<()> {
+        self.find_todo(id, user_id).await?;
+
+        let deleted = self.dependency_repo.delete(id, blocker_id).await?;
         if !deleted {
-            return Err(AppError::NotFound("Todo not found".into()));
+            return Err(AppError::NotFound("Dependency not found".into()));
         }
         Ok(())
     }
+
+    /// ゴミ箱内のToDo一覧を取得
+    pub async fn list_trash(&self, user_id: Uuid, query: TrashQuery) -> AppResult<TodoListResponse> {
+        let per_page = query.per_page.clamp(1, 100);
+        let page = query.page.max(1);
+
+        let (todos, total) = self
+            .todo_repo
+            .find_deleted_by_user_id(user_id, page, per_page)
+            .await?;
+
+        Ok(TodoListResponse {
+            items: self.responses(todos).await?,
+            total,
+            page,
+            per_page,
+        })
+    }
+
+    /// ゴミ箱からToDoを復元
+    pub async fn restore(&self, id: Uuid, user_id: Uuid) -> AppResult<TodoResponse> {
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let before = TodoRepository::lock_by_id(&mut tx, id, user_id)
+            .await?
+            .filter(|t| t.deleted_at.is_some())
+            .ok_or_else(|| AppError::NotFound("Todo not found in trash".into()))?;
+
+        let todo = TodoRepository::restore_with(&mut *tx, id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Todo not found in trash".into()))?;
+        Self::record_history(&mut tx, user_id, TodoHistoryAction::Restored, Some(&before), &todo)
+            .await?;
+        tx.commit().await?;
+
+        self.response(todo).await
+    }
+
+    /// ゴミ箱内のToDoを完全に削除
+    pub async fn purge(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
+        let purged = self.todo_repo.purge(id, user_id).await?;
+        if !purged {
+            return Err(AppError::NotFound("Todo not found in trash".into()));
+        }
+        Ok(())
+    }
+
+    /// ゴミ箱を空にする
+    pub async fn empty_trash(&self, user_id: Uuid) -> AppResult<u64> {
+        self.todo_repo.purge_all_by_user_id(user_id).await
+    }
+
+    /// 保持期間を過ぎたゴミ箱内のToDoを完全に削除
+    /// ユーザーごとのトランザクションで削除する（ユーザーのロックの順序を揃えるため）
+    pub async fn purge_expired_trash(&self, retention_days: i64) -> AppResult<u64> {
+        let cutoff = Utc::now() - Duration::days(retention_days);
+        let mut purged = 0;
+        for user_id in self.todo_repo.find_user_ids_with_trash_before(cutoff).await? {
+            purged += self.todo_repo.purge_deleted_before(user_id, cutoff).await?;
+        }
+
+        Ok(purged)
+    }
+
+    /// ToDoの変更履歴を取得
+    pub async fn history(&self, id: Uuid, user_id: Uuid) -> AppResult<Vec<TodoHistoryResponse>> {
+        self.find_todo(id, user_id).await?;
+
+        let histories = self.history_repo.find_by_todo_id(id).await?;
+
+        Ok(histories.into_iter().map(|h| h.into()).collect())
+    }
+
+    /// ToDoを指定したバージョンの状態に戻す
+    pub async fn revert(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        version: i32,
+        expected_versions: Option<&[i32]>,
+    ) -> AppResult<TodoResponse> {
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let before = Self::lock_todo(&mut tx, id, user_id).await?;
+        Self::check_version(&before, expected_versions)?;
+
+        let history = self
+            .history_repo
+            .find_by_todo_id_and_version(id, version)
+            .await?
+            .ok_or_else(|| AppError::NotFound("History version not found".into()))?;
+        let snapshot: TodoSnapshot = serde_json::from_value(history.snapshot)
+            .map_err(|e| AppError::Internal(format!("Invalid history snapshot: {}", e)))?;
+
+        let todo = TodoRepository::replace_with(&mut *tx, id, user_id, &snapshot, expected_versions)
+            .await?
+            .ok_or_else(|| Self::write_conflict(expected_versions))?;
+        Self::check_status_change(&mut tx, &before, &todo, false).await?;
+        Self::record_history(&mut tx, user_id, TodoHistoryAction::Reverted, Some(&before), &todo)
+            .await?;
+        tx.commit().await?;
+
+        self.response(todo).await
+    }
+
+    /// 依存関係・作業時間を含めたレスポンスを作成
+    async fn response(&self, todo: Todo) -> AppResult<TodoResponse> {
+        let mut responses = self.responses(vec![todo]).await?;
+
+        responses
+            .pop()
+            .ok_or_else(|| AppError::Internal("Failed to build todo response".into()))
+    }
+
+    /// 依存関係・作業時間を含めたレスポンスを一括で作成
+    pub async fn responses(&self, todos: Vec<Todo>) -> AppResult<Vec<TodoResponse>> {
+        let ids: Vec<Uuid> = todos.iter().map(|t| t.id).collect();
+        let dependencies = self.dependency_repo.find_by_todo_ids(&ids).await?;
+        let tracked: HashMap<Uuid, i64> = self
+            .time_entry_repo
+            .total_seconds_by_todo_ids(&ids)
+            .await?
+            .into_iter()
+            .collect();
+
+        Ok(todos
+            .into_iter()
+            .map(|t| {
+                let tracked_seconds = tracked.get(&t.id).copied().unwrap_or_default();
+                TodoResponse {
+                    tracked_seconds,
+                    ..TodoResponse::from(t).with_dependencies(&dependencies)
+                }
+            })
+            .collect())
+    }
+
+    /// 認可チェック付きでToDoを取得
+    async fn find_todo(&self, id: Uuid, user_id: Uuid) -> AppResult<Todo> {
+        self.todo_repo
+            .find_by_id_and_user_id(id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Todo not found".into()))
+    }
+
+    /// ステータスを変更した場合に、未完了の依存先と表示先の列のWIP制限を確認
+    /// `force` を指定した場合は依存先を確認しない
+    async fn check_status_change(
+        conn: &mut PgConnection,
+        before: &Todo,
+        after: &Todo,
+        force: bool,
+    ) -> AppResult<()> {
+        if after.status == before.status {
+            return Ok(());
+        }
+        if !force {
+            Self::check_blockers(conn, &[after.id], &after.status).await?;
+        }
+        Self::check_wip_limit(conn, after.user_id, &after.status).await
+    }
+
+    /// 未完了の依存先がある場合は着手・完了にできない
+    async fn check_blockers(
+        conn: &mut PgConnection,
+        ids: &[Uuid],
+        status: &TodoStatus,
+    ) -> AppResult<()> {
+        if *status == TodoStatus::Pending {
+            return Ok(());
+        }
+
+        let blockers = TodoDependencyRepository::find_incomplete_blocker_ids(conn, ids).await?;
+        if !blockers.is_empty() {
+            let ids: Vec<String> = blockers.iter().map(|b| b.to_string()).collect();
+            return Err(AppError::Conflict(format!(
+                "Todo is blocked by incomplete todos: {}",
+                ids.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// ステータスの変更で表示先になった列（カテゴリの先頭の列）のWIP制限を確認
+    /// 変更後に同じトランザクション内で確認し、超える場合は409を返す（変更はロールバックされる）
+    async fn check_wip_limit(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        status: &TodoStatus,
+    ) -> AppResult<()> {
+        let Some(column_id) = BoardColumnRepository::find_default_id(conn, user_id, status).await?
+        else {
+            return Ok(());
+        };
+        let column = BoardColumnRepository::lock_by_id(conn, column_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Column not found".into()))?;
+
+        if let Some(wip_limit) = column.wip_limit {
+            let count = BoardColumnRepository::count_todos(conn, user_id, column.id, Uuid::nil()).await?;
+            if count > i64::from(wip_limit) {
+                return Err(AppError::Conflict(format!(
+                    "WIP limit of column '{}' ({}) has been reached",
+                    column.name, wip_limit
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 認可チェック付きでToDoをロックして取得（ゴミ箱内のToDoは対象外）
+    async fn lock_todo(conn: &mut PgConnection, id: Uuid, user_id: Uuid) -> AppResult<Todo> {
+        TodoRepository::lock_by_id(conn, id, user_id)
+            .await?
+            .filter(|t| t.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Todo not found".into()))
+    }
+
+    /// 末尾に追加するためのランクキーを生成
+    /// 同時に作成したToDoに同じキーを振らないよう、作成と同じトランザクションで呼び出す
+    async fn append_position(conn: &mut PgConnection, user_id: Uuid) -> AppResult<String> {
+        let last = TodoRepository::last_position_with(&mut *conn, user_id).await?;
+        if let Some(position) = rank::between(last.as_deref(), None) {
+            return Ok(position);
+        }
+
+        TodoRepository::rebalance_positions_with(conn, user_id).await?;
+        let last = TodoRepository::last_position_with(&mut *conn, user_id).await?;
+        rank::between(last.as_deref(), None)
+            .ok_or_else(|| AppError::Internal("Failed to compute position".into()))
+    }
+
+    /// 末尾に追加するためのランクキーを件数分生成（作成と同じトランザクションで呼び出す）
+    pub async fn append_positions(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        count: usize,
+    ) -> AppResult<Vec<String>> {
+        for _ in 0..2 {
+            let mut last = TodoRepository::last_position_with(&mut *conn, user_id).await?;
+            let mut positions = Vec::with_capacity(count);
+            while positions.len() < count {
+                match rank::between(last.as_deref(), None) {
+                    Some(position) => {
+                        last = Some(position.clone());
+                        positions.push(position);
+                    }
+                    None => break,
+                }
+            }
+            if positions.len() == count {
+                return Ok(positions);
+            }
+
+            TodoRepository::rebalance_positions_with(conn, user_id).await?;
+        }
+
+        Err(AppError::Internal("Failed to compute position".into()))
+    }
+
+    /// 移動先の前後のToDoからランクキーを生成
+    /// キーの間に空きがない場合は `None`
+    async fn position_between(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: &MoveTodoRequest,
+    ) -> AppResult<Option<String>> {
+        let after = match req.after {
+            Some(after) => Some(self.find_todo(after, user_id).await?.position),
+            None => None,
+        };
+        let before = match req.before {
+            Some(before) => Some(self.find_todo(before, user_id).await?.position),
+            None => None,
+        };
+
+        let (lower, upper) = match (after, before) {
+            (Some(after), Some(before)) => {
+                if after > before {
+                    return Err(AppError::Validation(
+                        "The after todo must be positioned before the before todo".into(),
+                    ));
+                }
+                (Some(after), Some(before))
+            }
+            (Some(after), None) => {
+                let next = self.todo_repo.next_position(user_id, &after, id).await?;
+                (Some(after), next)
+            }
+            (None, Some(before)) => {
+                let prev = self.todo_repo.prev_position(user_id, &before, id).await?;
+                (prev, Some(before))
+            }
+            (None, None) => (None, None),
+        };
+
+        Ok(rank::between(lower.as_deref(), upper.as_deref()))
+    }
+
+    /// 絞り込み式を解析する（相対的な日付はプロフィールのタイムゾーンでの現在日時で解決）
+    pub async fn parse_filter(&self, user_id: Uuid, query: &TodoQuery) -> AppResult<Option<FilterExpr>> {
+        let Some(input) = query.filter.as_deref().filter(|f| !f.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let now = self.profile_service.user(user_id).await?.now();
+
+        Ok(Some(filter::parse(input, now)?))
+    }
+
+    /// 現在のToDoにパッチを適用し、作成時と同じルールで検証する
+    fn apply_patch(todo: &Todo, patch: &TodoPatch) -> AppResult<TodoSnapshot> {
+        let mut doc = json!({
+            "title": todo.title,
+            "description": todo.description,
+            "startDate": todo.start_date,
+            "dueDate": todo.due_date,
+            "status": todo.status,
+            "priority": todo.priority,
+            "tags": todo.tags,
+            "estimateMinutes": todo.estimate_minutes,
+        });
+
+        match patch {
+            TodoPatch::Merge(merge_patch) => json_patch::merge(&mut doc, merge_patch),
+            TodoPatch::Json(json_patch) => {
+                json_patch::patch(&mut doc, json_patch).map_err(|e| match e.kind {
+                    PatchErrorKind::TestFailed => AppError::Conflict(e.to_string()),
+                    _ => AppError::Validation(e.to_string()),
+                })?
+            }
+        }
+
+        let fields = doc
+            .as_object()
+            .ok_or_else(|| AppError::Validation("Patched todo must be a JSON object".into()))?;
+        if let Some(unknown) = fields.keys().find(|k| !TodoPatch::FIELDS.contains(&k.as_str())) {
+            return Err(AppError::Validation(format!("Unknown field: {}", unknown)));
+        }
+
+        let req: CreateTodoRequest =
+            serde_json::from_value(doc).map_err(|e| AppError::Validation(e.to_string()))?;
+        req.validate()
+            .map_err(|e| AppError::Validation(e.to_string()))?;
+        Self::check_schedule(req.start_date, req.due_date)?;
+
+        Ok(TodoSnapshot {
+            title: req.title,
+            description: req.description,
+            start_date: req.start_date,
+            due_date: req.due_date,
+            status: req
+                .status
+                .ok_or_else(|| AppError::Validation("Status cannot be removed".into()))?,
+            priority: req
+                .priority
+                .ok_or_else(|| AppError::Validation("Priority cannot be removed".into()))?,
+            tags: normalize_tags(req.tags.as_deref().unwrap_or_default()),
+            estimate_minutes: req.estimate_minutes,
+            deleted_at: None,
+        })
+    }
+
+    /// 開始日が期限より後になっていないか確認
+    pub fn check_schedule(
+        start_date: Option<DateTime<Utc>>,
+        due_date: Option<DateTime<Utc>>,
+    ) -> AppResult<()> {
+        match (start_date, due_date) {
+            (Some(start), Some(due)) if start > due => Err(AppError::Validation(
+                "Start date must not be after due date".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// If-Match で指定されたバージョンと現在のバージョンを照合
+    fn check_version(todo: &Todo, expected_versions: Option<&[i32]>) -> AppResult<()> {
+        match expected_versions {
+            Some(versions) if !versions.contains(&todo.version) => Err(
+                AppError::PreconditionFailed("Todo has been modified by another request".into()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// 照合後の更新で対象行がなかった場合のエラー
+    /// バージョン指定がある場合は並行する更新との競合とみなす
+    fn write_conflict(expected_versions: Option<&[i32]>) -> AppError {
+        if expected_versions.is_some() {
+            AppError::PreconditionFailed("Todo has been modified by another request".into())
+        } else {
+            AppError::NotFound("Todo not found".into())
+        }
+    }
+
+    /// 変更前後の差分を、ToDoの更新と同じトランザクションで履歴として記録
+    async fn record_history(
+        conn: &mut PgConnection,
+        actor_id: Uuid,
+        action: TodoHistoryAction,
+        before: Option<&Todo>,
+        after: &Todo,
+    ) -> AppResult<()> {
+        if let Some((changes, snapshot)) = Self::history_entry(before, after)? {
+            TodoHistoryRepository::create_with(conn, after.id, actor_id, action, &changes, &snapshot)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// 履歴に保存する差分とスナップショットを作成
+    /// 作成以外で差分がない場合は `None`
+    pub fn history_entry(before: Option<&Todo>, after: &Todo) -> AppResult<Option<(Value, Value)>> {
+        let before = before.map(TodoSnapshot::from);
+        let after = TodoSnapshot::from(after);
+        let changes = TodoSnapshot::diff(before.as_ref(), &after);
+
+        if before.is_some() && changes.as_object().is_some_and(|c| c.is_empty()) {
+            return Ok(None);
+        }
+
+        let snapshot = serde_json::to_value(&after)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize snapshot: {}", e)))?;
+
+        Ok(Some((changes, snapshot)))
+    }
 }
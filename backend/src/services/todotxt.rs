@@ -0,0 +1,163 @@
+//! todo.txt 形式（https://github.com/todotxt/todo.txt）の1行の読み書き
+//!
+//! - `(A)` / `(B)` / `(C)` → 高 / 中 / 低（`D` 以降は低）、中は出力しない
+//! - 先頭の `x` → 完了、続く日付は完了日、その次の日付は作成日
+//! - `due:YYYY-MM-DD` → 期限、`t:YYYY-MM-DD` → 開始日
+//! - `@context` → タグ `context`、`+project` → タグ `+project`（`+` を残して区別する）
+//! - 完了したタスクの優先度は `pri:A` として出力する
+//! - タイトル中の語で上記として読み取られるもの（`@foo` や先頭の `x` など）は `\` を付けて出力し、読み取り時に取り除く
+
+use chrono::NaiveDate;
+
+use crate::models::todo::TodoPriority;
+
+/// todo.txt の1行分のタスク
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TodoTxtTask {
+    pub title: String,
+    pub priority: Option<TodoPriority>,
+    pub completed: bool,
+    pub completed_on: Option<NaiveDate>,
+    pub created_on: Option<NaiveDate>,
+    pub due_on: Option<NaiveDate>,
+    pub start_on: Option<NaiveDate>,
+    pub tags: Vec<String>,
+}
+
+/// 1行を読み取る（空行は `None`）
+pub fn parse_line(line: &str) -> Option<TodoTxtTask> {
+    let mut task = TodoTxtTask::default();
+    let mut words = line.split_whitespace().peekable();
+    words.peek()?;
+
+    if words.peek() == Some(&"x") {
+        task.completed = true;
+        words.next();
+        if let Some(date) = words.peek().and_then(|w| parse_date(w)) {
+            task.completed_on = Some(date);
+            words.next();
+        }
+    }
+    if !task.completed {
+        if let Some(priority) = words.peek().and_then(|w| parse_priority(w)) {
+            task.priority = Some(priority);
+            words.next();
+        }
+    }
+    if let Some(date) = words.peek().and_then(|w| parse_date(w)) {
+        task.created_on = Some(date);
+        words.next();
+    }
+
+    let mut title = Vec::new();
+    for word in words {
+        if let Some(escaped) = word.strip_prefix('\\') {
+            title.push(escaped);
+        } else if let Some(context) = word.strip_prefix('@').filter(|c| !c.is_empty()) {
+            push_tag(&mut task.tags, context);
+        } else if word.len() > 1 && word.starts_with('+') {
+            push_tag(&mut task.tags, word);
+        } else if let Some(date) = word.strip_prefix("due:").and_then(parse_date) {
+            task.due_on = Some(date);
+        } else if let Some(date) = word.strip_prefix("t:").and_then(parse_date) {
+            task.start_on = Some(date);
+        } else if let Some(priority) = word
+            .strip_prefix("pri:")
+            .and_then(|p| parse_priority(&format!("({})", p)))
+        {
+            task.priority = Some(priority);
+        } else {
+            title.push(word);
+        }
+    }
+    task.title = title.join(" ");
+
+    Some(task)
+}
+
+/// 1行を出力する
+pub fn format_line(task: &TodoTxtTask) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let priority = match task.priority {
+        Some(TodoPriority::High) => Some("A"),
+        Some(TodoPriority::Low) => Some("C"),
+        _ => None,
+    };
+
+    if task.completed {
+        words.push("x".into());
+        if let Some(date) = task.completed_on {
+            words.push(date.to_string());
+        }
+    } else if let Some(priority) = priority {
+        words.push(format!("({})", priority));
+    }
+    // 完了日がない場合に作成日を出力すると、読み取り時に完了日になる
+    if let Some(date) = task.created_on.filter(|_| !task.completed || task.completed_on.is_some()) {
+        words.push(date.to_string());
+    }
+    for (i, word) in task.title.split_whitespace().enumerate() {
+        if needs_escape(word, i == 0) {
+            words.push(format!("\\{}", word));
+        } else {
+            words.push(word.to_string());
+        }
+    }
+    for tag in &task.tags {
+        // 空白を含むタグは1語にならないため、空白を `_` に置き換える
+        let tag = tag.split_whitespace().collect::<Vec<_>>().join("_");
+        if tag.len() > 1 && tag.starts_with('+') {
+            words.push(tag);
+        } else {
+            words.push(format!("@{}", tag));
+        }
+    }
+    if let Some(date) = task.due_on {
+        words.push(format!("due:{}", date));
+    }
+    if let Some(date) = task.start_on {
+        words.push(format!("t:{}", date));
+    }
+    if task.completed {
+        if let Some(priority) = priority {
+            words.push(format!("pri:{}", priority));
+        }
+    }
+
+    words.join(" ")
+}
+
+/// タイトルの語がタイトル以外として読み取られるか
+/// 先頭の語は完了・優先度・日付としても読み取られる
+fn needs_escape(word: &str, first: bool) -> bool {
+    word.starts_with('\\')
+        || (word.len() > 1 && (word.starts_with('@') || word.starts_with('+')))
+        || word.strip_prefix("due:").and_then(parse_date).is_some()
+        || word.strip_prefix("t:").and_then(parse_date).is_some()
+        || word
+            .strip_prefix("pri:")
+            .and_then(|p| parse_priority(&format!("({})", p)))
+            .is_some()
+        || (first && (word == "x" || parse_priority(word).is_some() || parse_date(word).is_some()))
+}
+
+fn parse_date(word: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(word, "%Y-%m-%d").ok()
+}
+
+/// `(A)` 〜 `(Z)`
+fn parse_priority(word: &str) -> Option<TodoPriority> {
+    let letter = word.strip_prefix('(')?.strip_suffix(')')?;
+    match letter {
+        "A" => Some(TodoPriority::High),
+        "B" => Some(TodoPriority::Medium),
+        l if l.len() == 1 && l.chars().all(|c| c.is_ascii_uppercase()) => Some(TodoPriority::Low),
+        _ => None,
+    }
+}
+
+fn push_tag(tags: &mut Vec<String>, tag: &str) {
+    if !tags.iter().any(|t| t == tag) {
+        tags.push(tag.to_string());
+    }
+}
@@ -0,0 +1,679 @@
+//! ToDoのインポート・エクスポート（CSV / JSON / todo.txt）と他のツールからのインポート
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use futures::{stream, Stream};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        saved_view::start_of_day,
+        todo::{
+            normalize_tags, CreateTodoRequest, Todo, TodoPriority, TodoQuery, TodoResponse,
+            TodoStatus, MAX_TAGS, MAX_TAG_LENGTH,
+        },
+        todo_history::TodoHistoryAction,
+        transfer::{
+            ExternalImportRequest, ExternalImportResponse, ImportRowError, ImportSource,
+            ImportTodosRequest, ImportTodosResponse, TransferFormat,
+        },
+    },
+    repositories::{todo_history_repository::TodoHistoryRepository, todo_repository::TodoRepository},
+    services::{
+        importers::{self, ImportedTask, Notes},
+        profile_service::ProfileService,
+        todo_service::TodoService,
+        todotxt::{self, TodoTxtTask},
+    },
+};
+
+/// エクスポートで1回に読み込む件数
+const EXPORT_BATCH_SIZE: i64 = 200;
+/// 一度にインポートできる行数の上限
+pub const MAX_IMPORT_ROWS: usize = 5000;
+/// CSVの列（エクスポート時の順序）
+const CSV_COLUMNS: [&str; 12] = [
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "startDate",
+    "dueDate",
+    "completedAt",
+    "tags",
+    "estimateMinutes",
+    "createdAt",
+    "updatedAt",
+];
+/// インポートで読み取るフィールド（`CreateTodoRequest` のフィールド）
+const IMPORT_FIELDS: [&str; 8] = [
+    "title",
+    "description",
+    "startDate",
+    "dueDate",
+    "status",
+    "priority",
+    "tags",
+    "estimateMinutes",
+];
+/// CSVでのタグの区切り文字
+const TAG_SEPARATOR: char = ';';
+/// タイトルの最大文字数（`CreateTodoRequest` と同じ）
+const MAX_TITLE_LENGTH: usize = 255;
+/// 表計算ソフトで数式として解釈される先頭の文字
+const CSV_FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+#[derive(Clone)]
+pub struct TransferService {
+    todo_repo: TodoRepository,
+    todo_service: TodoService,
+    profile_service: ProfileService,
+}
+
+impl TransferService {
+    pub fn new(
+        todo_repo: TodoRepository,
+        todo_service: TodoService,
+        profile_service: ProfileService,
+    ) -> Self {
+        Self {
+            todo_repo,
+            todo_service,
+            profile_service,
+        }
+    }
+
+    /// 一覧と同じ条件に一致するToDoを全件エクスポート
+    /// 全件をメモリに載せないよう、一定件数ずつ読み込んで出力する
+    pub async fn export(
+        &self,
+        user_id: Uuid,
+        query: TodoQuery,
+        format: TransferFormat,
+    ) -> AppResult<impl Stream<Item = AppResult<String>> + Send + 'static> {
+        let filter = self.todo_service.parse_filter(user_id, &query).await?;
+        let tz = self.profile_service.user(user_id).await?.tz();
+        let service = self.clone();
+
+        Ok(stream::try_unfold(Some(0), move |offset| {
+            let service = service.clone();
+            let query = query.clone();
+            let filter = filter.clone();
+            async move {
+                let Some(offset) = offset else {
+                    return Ok(None);
+                };
+                let todos = service
+                    .todo_repo
+                    .find_page_by_user_id(user_id, &query, filter.as_ref(), EXPORT_BATCH_SIZE, offset)
+                    .await?;
+                let last = (todos.len() as i64) < EXPORT_BATCH_SIZE;
+                let todos = service.todo_service.responses(todos).await?;
+
+                let chunk = match format {
+                    TransferFormat::Csv => csv_chunk(&todos, offset == 0)?,
+                    TransferFormat::Json => json_chunk(&todos, offset == 0, last)?,
+                    TransferFormat::Todotxt => todotxt_chunk(&todos, &tz),
+                };
+                let next = (!last).then_some(offset + EXPORT_BATCH_SIZE);
+                Ok(Some((chunk, next)))
+            }
+        }))
+    }
+
+    /// CSV / JSON / todo.txtからToDoをインポート
+    /// 全行を `CreateTodoRequest` と同じルールで検証し、エラーがなければ1つのトランザクションで作成する
+    pub async fn import(
+        &self,
+        user_id: Uuid,
+        req: ImportTodosRequest,
+    ) -> AppResult<ImportTodosResponse> {
+        if let Some(field) = req.mapping.keys().find(|k| !IMPORT_FIELDS.contains(&k.as_str())) {
+            return Err(AppError::Validation(format!(
+                "Unknown field '{}' in mapping",
+                field
+            )));
+        }
+
+        let tz = self.profile_service.user(user_id).await?.tz();
+        let rows = match req.format {
+            TransferFormat::Csv => csv_rows(&req.content)?,
+            TransferFormat::Json => json_rows(&req.content)?,
+            TransferFormat::Todotxt => todotxt_rows(&req.content)?,
+        };
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::Validation(format!(
+                "At most {} rows can be imported at once",
+                MAX_IMPORT_ROWS
+            )));
+        }
+
+        let total = rows.len();
+        let mut requests = Vec::with_capacity(total);
+        let mut errors = Vec::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            let imported = row.and_then(|row| match row {
+                ImportRow::Fields(fields) => create_request(&fields, &req.mapping, &tz)
+                    .map(ImportedTodo::from),
+                ImportRow::Todotxt(task) => todotxt_request(task, &tz),
+            });
+            match imported {
+                Ok(request) => requests.push(request),
+                Err(message) => errors.push(ImportRowError {
+                    row: index + 1,
+                    message,
+                }),
+            }
+        }
+
+        let mut response = ImportTodosResponse {
+            dry_run: req.dry_run,
+            total,
+            imported: 0,
+            errors,
+            todos: Vec::new(),
+        };
+        if req.dry_run || !response.errors.is_empty() || requests.is_empty() {
+            return Ok(response);
+        }
+
+        let todos = self.create_all(user_id, requests).await?;
+        response.imported = todos.len();
+        response.todos = self.todo_service.responses(todos).await?;
+
+        Ok(response)
+    }
+
+    /// 他のツールのエクスポートファイルからインポート
+    /// 変換できない値は取り込まずに `notes` に記録し、サブタスクを含めて全件を検証してから作成する
+    pub async fn import_external(
+        &self,
+        user_id: Uuid,
+        source: ImportSource,
+        req: ExternalImportRequest,
+    ) -> AppResult<ExternalImportResponse> {
+        let tz = self.profile_service.user(user_id).await?.tz();
+        let mut notes = Notes::default();
+        let tasks = importers::parse(source, &req.content, &tz, &mut notes)
+            .map_err(AppError::Validation)?;
+
+        if tasks.iter().map(ImportedTask::count).sum::<usize>() > MAX_IMPORT_ROWS {
+            return Err(AppError::Validation(format!(
+                "At most {} tasks can be imported at once",
+                MAX_IMPORT_ROWS
+            )));
+        }
+
+        // 親の直後にサブタスクが続く順に並べる
+        let flattened = flatten(tasks);
+
+        let total = flattened.len();
+        let mut requests = Vec::with_capacity(total);
+        let mut errors = Vec::new();
+        for (index, (mut task, parent)) in flattened.into_iter().enumerate() {
+            sanitize(&mut task, &mut notes);
+            let title = task.title.clone();
+            match external_request(task, parent) {
+                Ok(request) => requests.push(request),
+                Err(message) => errors.push(ImportRowError {
+                    row: index + 1,
+                    message: format!("{} ({})", message, title),
+                }),
+            }
+        }
+
+        let mut response = ExternalImportResponse {
+            source,
+            dry_run: req.dry_run,
+            total,
+            imported: 0,
+            errors,
+            notes: notes.0,
+            todos: Vec::new(),
+        };
+        if req.dry_run || !response.errors.is_empty() || requests.is_empty() {
+            return Ok(response);
+        }
+
+        let todos = self.create_all(user_id, requests).await?;
+        response.imported = todos.len();
+        response.todos = self.todo_service.responses(todos).await?;
+
+        Ok(response)
+    }
+
+    /// 検証済みのリクエストから末尾に順番にToDoを作成
+    async fn create_all(&self, user_id: Uuid, requests: Vec<ImportedTodo>) -> AppResult<Vec<Todo>> {
+        let mut tx = self.todo_repo.begin(user_id).await?;
+        let mut positions = TodoService::append_positions(&mut tx, user_id, requests.len()).await?;
+        positions.reverse();
+        let mut todos: Vec<Todo> = Vec::with_capacity(requests.len());
+        for ImportedTodo {
+            request: req,
+            created_at,
+            completed_at,
+            parent,
+        } in requests
+        {
+            let parent_id = parent.and_then(|i| todos.get(i)).map(|t| t.id);
+            let mut todo = TodoRepository::create_with(
+                &mut *tx,
+                user_id,
+                &req.title,
+                req.description.as_deref(),
+                req.due_date,
+                &req.status.unwrap_or(TodoStatus::Pending),
+                &req.priority.unwrap_or(TodoPriority::Medium),
+                &normalize_tags(req.tags.as_deref().unwrap_or_default()),
+                &positions.pop().unwrap_or_default(),
+                req.estimate_minutes,
+                req.start_date,
+                parent_id,
+            )
+            .await?;
+            if created_at.is_some() || completed_at.is_some() {
+                todo = TodoRepository::set_imported_dates_with(
+                    &mut *tx,
+                    todo.id,
+                    created_at,
+                    completed_at,
+                )
+                .await?;
+            }
+
+            if let Some((changes, snapshot)) = TodoService::history_entry(None, &todo)? {
+                TodoHistoryRepository::create_with(
+                    &mut *tx,
+                    todo.id,
+                    user_id,
+                    TodoHistoryAction::Created,
+                    &changes,
+                    &snapshot,
+                )
+                .await?;
+            }
+            todos.push(todo);
+        }
+        tx.commit().await?;
+
+        Ok(todos)
+    }
+}
+
+// Export
+
+fn csv_chunk(todos: &[TodoResponse], header: bool) -> AppResult<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let csv_error = |e: csv::Error| AppError::Internal(format!("Failed to write CSV: {}", e));
+
+    if header {
+        writer.write_record(CSV_COLUMNS).map_err(csv_error)?;
+    }
+    for todo in todos {
+        let date = |d: Option<DateTime<Utc>>| d.map(|d| d.to_rfc3339()).unwrap_or_default();
+        writer
+            .write_record([
+                todo.id.to_string(),
+                csv_cell(&todo.title),
+                csv_cell(todo.description.as_deref().unwrap_or_default()),
+                enum_text(&todo.status),
+                enum_text(&todo.priority),
+                date(todo.start_date),
+                date(todo.due_date),
+                date(todo.completed_at),
+                csv_cell(&todo.tags.join(&TAG_SEPARATOR.to_string())),
+                todo.estimate_minutes.map(|m| m.to_string()).unwrap_or_default(),
+                todo.created_at.to_rfc3339(),
+                todo.updated_at.to_rfc3339(),
+            ])
+            .map_err(csv_error)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| AppError::Internal(format!("Failed to write CSV: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// 数式として解釈される文字で始まるセルは `'` を前に付ける（CSVインジェクション対策）
+fn csv_cell(text: &str) -> String {
+    if text.starts_with(CSV_FORMULA_PREFIXES) {
+        format!("'{}", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// JSONと同じ表記（`inProgress` など）
+fn enum_text<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn json_chunk(todos: &[TodoResponse], first: bool, last: bool) -> AppResult<String> {
+    let mut chunk = String::new();
+    if first {
+        chunk.push('[');
+    }
+    for (i, todo) in todos.iter().enumerate() {
+        if !(first && i == 0) {
+            chunk.push(',');
+        }
+        chunk.push_str(
+            &serde_json::to_string(todo).map_err(|e| AppError::Internal(e.to_string()))?,
+        );
+    }
+    if last {
+        chunk.push(']');
+    }
+    Ok(chunk)
+}
+
+fn todotxt_chunk(todos: &[TodoResponse], tz: &Tz) -> String {
+    let date = |d: DateTime<Utc>| d.with_timezone(tz).date_naive();
+
+    todos
+        .iter()
+        .map(|todo| {
+            let task = TodoTxtTask {
+                title: todo.title.clone(),
+                priority: Some(todo.priority.clone()),
+                completed: todo.status == TodoStatus::Completed,
+                completed_on: todo.completed_at.map(date),
+                created_on: Some(date(todo.created_at)),
+                due_on: todo.due_date.map(date),
+                start_on: todo.start_date.map(date),
+                tags: todo.tags.clone(),
+            };
+            format!("{}\n", todotxt::format_line(&task))
+        })
+        .collect()
+}
+
+// Import
+
+type Row = Map<String, Value>;
+
+/// 読み取った1行（CSV / JSONはフィールドの対応付け前の値）
+enum ImportRow {
+    Fields(Row),
+    Todotxt(TodoTxtTask),
+}
+
+/// 検証済みの作成内容と、インポート元の作成日時・完了日時
+/// `parent` は同じインポート内の親のインデックス（親より後に並ぶ）
+struct ImportedTodo {
+    request: CreateTodoRequest,
+    created_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    parent: Option<usize>,
+}
+
+impl From<CreateTodoRequest> for ImportedTodo {
+    fn from(request: CreateTodoRequest) -> Self {
+        Self {
+            request,
+            created_at: None,
+            completed_at: None,
+            parent: None,
+        }
+    }
+}
+
+/// CSVの各行を列名をキーにしたオブジェクトにする
+fn csv_rows(content: &Value) -> AppResult<Vec<Result<ImportRow, String>>> {
+    let Value::String(text) = content else {
+        return Err(AppError::Validation("CSV content must be a string".into()));
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.trim_start_matches('\u{feff}').as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::Validation(format!("Invalid CSV: {}", e)))?
+        .clone();
+
+    Ok(reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+            Ok(ImportRow::Fields(
+                headers
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(header, value)| {
+                        // エクスポート時に付けた `'` を取り除く
+                        let value = value
+                            .strip_prefix('\'')
+                            .filter(|v| v.starts_with(CSV_FORMULA_PREFIXES))
+                            .unwrap_or(value);
+                        (header.to_string(), Value::String(value.to_string()))
+                    })
+                    .collect(),
+            ))
+        })
+        .collect())
+}
+
+fn json_rows(content: &Value) -> AppResult<Vec<Result<ImportRow, String>>> {
+    let Value::Array(items) = content else {
+        return Err(AppError::Validation("JSON content must be an array".into()));
+    };
+
+    Ok(items
+        .iter()
+        .map(|item| match item {
+            Value::Object(row) => Ok(ImportRow::Fields(row.clone())),
+            _ => Err("Row must be an object".to_string()),
+        })
+        .collect())
+}
+
+/// 空行を除いた各行をタスクとして読み取る
+fn todotxt_rows(content: &Value) -> AppResult<Vec<Result<ImportRow, String>>> {
+    let Value::String(text) = content else {
+        return Err(AppError::Validation("todo.txt content must be a string".into()));
+    };
+
+    Ok(text
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .filter_map(todotxt::parse_line)
+        .map(|task| Ok(ImportRow::Todotxt(task)))
+        .collect())
+}
+
+/// todo.txt のタスクを `CreateTodoRequest` に変換して検証する
+/// 日付はタイムゾーンでのその日の0時として扱う
+fn todotxt_request(task: TodoTxtTask, tz: &Tz) -> Result<ImportedTodo, String> {
+    let date = |d: Option<NaiveDate>| d.and_then(|d| start_of_day(d, tz));
+    let req = CreateTodoRequest {
+        title: task.title,
+        description: None,
+        start_date: date(task.start_on),
+        due_date: date(task.due_on),
+        status: task.completed.then_some(TodoStatus::Completed),
+        priority: task.priority,
+        tags: Some(task.tags),
+        estimate_minutes: None,
+    };
+
+    Ok(ImportedTodo {
+        request: validate_request(req)?,
+        created_at: date(task.created_on),
+        completed_at: date(task.completed_on),
+        parent: None,
+    })
+}
+
+/// ツリーを親の直後にサブタスクが続く順に並べる（親のインデックス付き）
+fn flatten(tasks: Vec<ImportedTask>) -> Vec<(ImportedTask, Option<usize>)> {
+    let mut out = Vec::new();
+    let mut stack: Vec<(ImportedTask, Option<usize>)> =
+        tasks.into_iter().rev().map(|task| (task, None)).collect();
+    while let Some((mut task, parent)) = stack.pop() {
+        let subtasks = std::mem::take(&mut task.subtasks);
+        let index = out.len();
+        out.push((task, parent));
+        stack.extend(subtasks.into_iter().rev().map(|subtask| (subtask, Some(index))));
+    }
+    out
+}
+
+/// 制限を超える値を、取り込める形に変換する
+fn sanitize(task: &mut ImportedTask, notes: &mut Notes) {
+    task.title = task.title.trim().to_string();
+    if task.title.chars().count() > MAX_TITLE_LENGTH {
+        task.title = task.title.chars().take(MAX_TITLE_LENGTH).collect();
+        notes.transformed(&task.title, "title", format!("Truncated to {} characters", MAX_TITLE_LENGTH));
+    }
+    let title = task.title.clone();
+
+    let (tags, too_long): (Vec<String>, Vec<String>) = normalize_tags(&task.tags)
+        .into_iter()
+        .partition(|t| t.chars().count() <= MAX_TAG_LENGTH);
+    for tag in too_long {
+        notes.skipped(&title, "tags", format!("Tag '{}' is longer than {} characters", tag, MAX_TAG_LENGTH));
+    }
+    task.tags = tags;
+    if task.tags.len() > MAX_TAGS {
+        let dropped = task.tags.split_off(MAX_TAGS);
+        notes.skipped(&title, "tags", format!("Only {} tags are allowed: {}", MAX_TAGS, dropped.join(", ")));
+    }
+
+    if let (Some(start), Some(due)) = (task.start_date, task.due_date) {
+        if start > due {
+            task.start_date = None;
+            notes.skipped(&title, "startDate", "Start date after the due date");
+        }
+    }
+    if task.estimate_minutes.is_some_and(|m| m < 1) {
+        task.estimate_minutes = None;
+        notes.skipped(&title, "estimate", "Estimate shorter than 1 minute");
+    }
+}
+
+fn external_request(task: ImportedTask, parent: Option<usize>) -> Result<ImportedTodo, String> {
+    let req = CreateTodoRequest {
+        title: task.title,
+        description: task.description,
+        start_date: task.start_date,
+        due_date: task.due_date,
+        status: task.status,
+        priority: task.priority,
+        tags: Some(task.tags),
+        estimate_minutes: task.estimate_minutes,
+    };
+
+    Ok(ImportedTodo {
+        request: validate_request(req)?,
+        created_at: task.created_at,
+        completed_at: task.completed_at,
+        parent,
+    })
+}
+
+/// 1行を `CreateTodoRequest` に変換して検証する
+fn create_request(
+    row: &Row,
+    mapping: &HashMap<String, String>,
+    tz: &Tz,
+) -> Result<CreateTodoRequest, String> {
+    let mut doc = Map::new();
+    for field in IMPORT_FIELDS {
+        let source = mapping.get(field).map(String::as_str).unwrap_or(field);
+        let value = row.get(source).or_else(|| {
+            row.iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(source))
+                .map(|(_, value)| value)
+        });
+        let Some(value) = value.filter(|v| !is_blank(v)) else {
+            continue;
+        };
+        let value = convert(field, value, tz).map_err(|e| format!("{}: {}", field, e))?;
+        doc.insert(field.to_string(), value);
+    }
+
+    let req: CreateTodoRequest =
+        serde_json::from_value(Value::Object(doc)).map_err(|e| e.to_string())?;
+
+    validate_request(req)
+}
+
+/// 作成時と同じルールで検証する
+fn validate_request(req: CreateTodoRequest) -> Result<CreateTodoRequest, String> {
+    req.validate().map_err(|e| e.to_string())?;
+    TodoService::check_schedule(req.start_date, req.due_date).map_err(|e| match e {
+        AppError::Validation(message) => message,
+        e => e.to_string(),
+    })?;
+
+    Ok(req)
+}
+
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// CSVの文字列や表記の揺れを `CreateTodoRequest` の値に変換する
+fn convert(field: &str, value: &Value, tz: &Tz) -> Result<Value, String> {
+    let Value::String(text) = value else {
+        return Ok(value.clone());
+    };
+    let text = text.trim();
+
+    match field {
+        "tags" => Ok(Value::from(
+            text.split(TAG_SEPARATOR)
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .collect::<Vec<_>>(),
+        )),
+        "estimateMinutes" => text
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("Invalid number '{}'", text)),
+        "startDate" | "dueDate" => {
+            if DateTime::parse_from_rfc3339(text).is_ok() {
+                return Ok(Value::from(text));
+            }
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| start_of_day(date, tz))
+                .map(|date| Value::from(date.to_rfc3339()))
+                .ok_or_else(|| format!("Invalid date '{}'", text))
+        }
+        "status" => {
+            let normalized: String = text
+                .chars()
+                .filter(|c| !matches!(c, '_' | '-' | ' '))
+                .collect::<String>()
+                .to_lowercase();
+            match normalized.as_str() {
+                "pending" | "todo" => Ok(Value::from("pending")),
+                "inprogress" => Ok(Value::from("inProgress")),
+                "completed" | "done" => Ok(Value::from("completed")),
+                _ => Err(format!("Unknown status '{}'", text)),
+            }
+        }
+        "priority" => match text.to_lowercase().as_str() {
+            priority @ ("low" | "medium" | "high") => Ok(Value::from(priority)),
+            _ => Err(format!("Unknown priority '{}'", text)),
+        },
+        _ => Ok(Value::from(text)),
+    }
+}
@@ -0,0 +1,518 @@
+use std::{
+    collections::{HashMap, HashSet},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use futures::future::join_all;
+use hmac::{Hmac, Mac};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::CONTENT_TYPE,
+    Url,
+};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::webhook::{
+        CreatedWebhookResponse, Webhook, WebhookDelivery, WebhookDeliveryResponse,
+        WebhookDeliveryStatus, WebhookEvent, WebhookRequest, WebhookResponse,
+    },
+    repositories::webhook_repository::WebhookRepository,
+};
+
+/// 1つの配信を送信する最大回数（初回を含む）
+const MAX_ATTEMPTS: i32 = 6;
+/// 連続してこの回数失敗したWebhookは無効にする
+const DISABLE_AFTER_FAILURES: i32 = 10;
+/// 再試行の間隔の上限
+const MAX_RETRY_SECONDS: u64 = 60 * 60;
+/// 一度に配信を登録する履歴の件数
+const DISPATCH_BATCH: i64 = 500;
+/// 一度に送信する配信の件数
+const DELIVERY_BATCH: i64 = 50;
+/// 送信中の配信を他のジョブが取得しない時間
+const LEASE_SECONDS: f64 = 60.0;
+/// 送信のタイムアウト
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// 配信ログに保存するレスポンスボディの最大バイト数（これ以上は受信しない）
+const MAX_RESPONSE_BODY: usize = 1000;
+/// 配信ログの取得件数
+const DELIVERY_LOG_LIMIT: i64 = 100;
+
+#[derive(Clone)]
+pub struct WebhookService {
+    webhook_repo: WebhookRepository,
+    client: reqwest::Client,
+    retry_base: u64,
+    allow_private_urls: bool,
+}
+
+impl WebhookService {
+    /// `retry_base` は初回の再試行までの秒数（以降は倍にしていく）
+    /// `allow_private_urls` が `false` の場合、プライベート・ループバック・リンクローカルなどのアドレスには送信しない
+    pub fn new(webhook_repo: WebhookRepository, retry_base: u64, allow_private_urls: bool) -> Self {
+        let mut builder = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none());
+        if !allow_private_urls {
+            // 名前解決の結果で判定するため、プロキシを経由しない
+            builder = builder
+                .dns_resolver(Arc::new(PublicAddressResolver))
+                .no_proxy();
+        }
+        let client = builder.build().expect("Failed to build HTTP client");
+
+        Self {
+            webhook_repo,
+            client,
+            retry_base,
+            allow_private_urls,
+        }
+    }
+
+    /// Webhook一覧を取得
+    pub async fn list(&self, user_id: Uuid) -> AppResult<Vec<WebhookResponse>> {
+        let webhooks = self.webhook_repo.find_by_user_id(user_id).await?;
+
+        Ok(webhooks.into_iter().map(|w| w.into()).collect())
+    }
+
+    /// Webhook詳細を取得
+    pub async fn get_by_id(&self, id: Uuid, user_id: Uuid) -> AppResult<WebhookResponse> {
+        Ok(self.find(id, user_id).await?.into())
+    }
+
+    /// Webhookを登録（署名の秘密鍵を発行する）
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        req: WebhookRequest,
+    ) -> AppResult<CreatedWebhookResponse> {
+        self.check_url(&req.url).await?;
+        let secret = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let webhook = self
+            .webhook_repo
+            .create(
+                user_id,
+                req.url.trim(),
+                req.description.as_deref(),
+                &Self::event_names(&req.events),
+                &secret,
+                req.active.unwrap_or(true),
+            )
+            .await?;
+
+        Ok(CreatedWebhookResponse {
+            webhook: webhook.into(),
+            secret,
+        })
+    }
+
+    /// Webhookを更新
+    pub async fn update(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: WebhookRequest,
+    ) -> AppResult<WebhookResponse> {
+        self.check_url(&req.url).await?;
+        let webhook = self
+            .webhook_repo
+            .update(
+                id,
+                user_id,
+                req.url.trim(),
+                req.description.as_deref(),
+                &Self::event_names(&req.events),
+                req.active.unwrap_or(true),
+            )
+            .await?
+            .ok_or_else(|| AppError::NotFound("Webhook not found".into()))?;
+
+        Ok(webhook.into())
+    }
+
+    /// Webhookを削除（配信ログも削除される）
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
+        if !self.webhook_repo.delete(id, user_id).await? {
+            return Err(AppError::NotFound("Webhook not found".into()));
+        }
+        Ok(())
+    }
+
+    /// 配信ログを取得（新しい順）
+    pub async fn deliveries(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Vec<WebhookDeliveryResponse>> {
+        let webhook = self.find(id, user_id).await?;
+        let deliveries = self
+            .webhook_repo
+            .find_deliveries(webhook.id, DELIVERY_LOG_LIMIT)
+            .await?;
+
+        Ok(deliveries.into_iter().map(|d| d.into()).collect())
+    }
+
+    /// 同じ内容で配信し直す（新しい配信として登録する）
+    pub async fn redeliver(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        delivery_id: Uuid,
+    ) -> AppResult<WebhookDeliveryResponse> {
+        let webhook = self.find(id, user_id).await?;
+        if !webhook.active {
+            return Err(AppError::Conflict("Webhook is disabled".into()));
+        }
+        let delivery = self
+            .webhook_repo
+            .find_delivery(delivery_id, webhook.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Delivery not found".into()))?;
+
+        let mut tx = self.webhook_repo.begin().await?;
+        let redelivery = WebhookRepository::create_delivery_with(
+            &mut *tx,
+            webhook.id,
+            &delivery.event,
+            &delivery.payload,
+            Some(delivery.id),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(redelivery.into())
+    }
+
+    /// 配信を登録していない変更を配信に登録し、送信予定日時を過ぎた配信を送信する
+    /// 送信した配信の件数を返す
+    pub async fn process(&self) -> AppResult<usize> {
+        while self.dispatch().await? == DISPATCH_BATCH as usize {}
+        self.deliver_due().await
+    }
+
+    /// 送信するリクエストの署名（`{timestamp}.{body}` のHMAC-SHA256の16進数）
+    pub fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body);
+        format!("{:x}", mac.finalize().into_bytes())
+    }
+
+    async fn find(&self, id: Uuid, user_id: Uuid) -> AppResult<Webhook> {
+        self.webhook_repo
+            .find_by_id(id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Webhook not found".into()))
+    }
+
+    /// URLの形式と、送信先のアドレスを確認
+    async fn check_url(&self, url: &str) -> AppResult<()> {
+        let url = match Url::parse(url.trim()) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+            _ => return Err(AppError::Validation("URL must be an http or https URL".into())),
+        };
+        self.check_destination(&url).await.map_err(AppError::Validation)
+    }
+
+    /// 送信先のアドレス（ホスト名の場合は名前解決した全アドレス）が公開アドレスであることを確認
+    async fn check_destination(&self, url: &Url) -> Result<(), String> {
+        if self.allow_private_urls {
+            return Ok(());
+        }
+        let host = url
+            .host_str()
+            .ok_or("URL must have a host")?
+            .trim_start_matches('[')
+            .trim_end_matches(']');
+        match host.parse::<IpAddr>() {
+            Ok(ip) => check_public_ip(ip),
+            Err(_) => resolve_public(host, url.port_or_known_default().unwrap_or(80))
+                .await
+                .map(|_| ()),
+        }
+    }
+
+    fn event_names(events: &[WebhookEvent]) -> Vec<String> {
+        let mut names: Vec<String> = Vec::with_capacity(events.len());
+        for event in events {
+            if !names.iter().any(|n| n == event.as_str()) {
+                names.push(event.as_str().to_string());
+            }
+        }
+        names
+    }
+
+    /// 履歴を購読しているWebhookの配信として登録し、処理した履歴の件数を返す
+    async fn dispatch(&self) -> AppResult<usize> {
+        let mut tx = self.webhook_repo.begin().await?;
+        let mut events =
+            WebhookRepository::claim_pending_events_with(&mut *tx, DISPATCH_BATCH).await?;
+        if events.is_empty() {
+            tx.commit().await?;
+            return Ok(0);
+        }
+        events.sort_by_key(|e| e.seq);
+
+        let user_ids: Vec<Uuid> = events
+            .iter()
+            .map(|e| e.user_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let webhooks = WebhookRepository::find_active_by_user_ids_with(&mut *tx, &user_ids).await?;
+
+        for event in &events {
+            let payload = event.payload();
+            for webhook in webhooks.iter().filter(|w| {
+                w.user_id == event.user_id && w.events.iter().any(|e| e == event.name())
+            }) {
+                WebhookRepository::create_delivery_with(
+                    &mut *tx,
+                    webhook.id,
+                    event.name(),
+                    &payload,
+                    None,
+                )
+                .await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(events.len())
+    }
+
+    /// 送信予定日時を過ぎた配信を並行して送信
+    async fn deliver_due(&self) -> AppResult<usize> {
+        let deliveries = self
+            .webhook_repo
+            .claim_due_deliveries(DELIVERY_BATCH, LEASE_SECONDS)
+            .await?;
+        if deliveries.is_empty() {
+            return Ok(0);
+        }
+
+        let ids: Vec<Uuid> = deliveries.iter().map(|d| d.webhook_id).collect();
+        let webhooks: HashMap<Uuid, Webhook> = self
+            .webhook_repo
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|w| (w.id, w))
+            .collect();
+
+        let count = deliveries.len();
+        let results = join_all(deliveries.into_iter().filter_map(|delivery| {
+            let webhook = webhooks.get(&delivery.webhook_id)?;
+            Some(self.attempt(webhook, delivery))
+        }))
+        .await;
+        for result in results {
+            result?;
+        }
+
+        Ok(count)
+    }
+
+    /// 1回送信して結果を記録する
+    /// 失敗した場合は回数に応じて間隔を空けて再試行し、続けて失敗したWebhookは無効にする
+    async fn attempt(&self, webhook: &Webhook, delivery: WebhookDelivery) -> AppResult<()> {
+        let body = serde_json::to_vec(&delivery.payload)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize payload: {}", e)))?;
+        let timestamp = Utc::now().timestamp();
+        let signature = Self::signature(&webhook.secret, timestamp, &body);
+
+        let started = Instant::now();
+        let destination = match Url::parse(&webhook.url) {
+            Ok(url) => self.check_destination(&url).await,
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = destination {
+            return self
+                .record_result(webhook, delivery, (false, None, None, Some(e)), started)
+                .await;
+        }
+        let result = self
+            .client
+            .post(&webhook.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-Webhook-Id", webhook.id.to_string())
+            .header("X-Webhook-Event", &delivery.event)
+            .header("X-Webhook-Delivery", delivery.id.to_string())
+            .header("X-Webhook-Timestamp", timestamp.to_string())
+            .header("X-Webhook-Signature", format!("sha256={}", signature))
+            .body(body)
+            .send()
+            .await;
+
+        let outcome = match result {
+            Ok(response) => {
+                let status = response.status();
+                let text = read_body(response).await;
+                let error = (!status.is_success()).then(|| format!("HTTP {}", status));
+                (status.is_success(), Some(status.as_u16() as i32), Some(text), error)
+            }
+            Err(e) => (false, None, None, Some(e.to_string())),
+        };
+        self.record_result(webhook, delivery, outcome, started).await
+    }
+
+    /// 送信結果（成否・ステータス・レスポンスボディ・エラー）を記録する
+    async fn record_result(
+        &self,
+        webhook: &Webhook,
+        delivery: WebhookDelivery,
+        (succeeded, response_status, response_body, error): (bool, Option<i32>, Option<String>, Option<String>),
+        started: Instant,
+    ) -> AppResult<()> {
+        let duration_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+
+        let attempts = delivery.attempts + 1;
+        let (status, retry_after) = if succeeded {
+            (WebhookDeliveryStatus::Succeeded, 0)
+        } else if attempts >= MAX_ATTEMPTS {
+            (WebhookDeliveryStatus::Failed, 0)
+        } else {
+            (WebhookDeliveryStatus::Pending, self.retry_after(attempts))
+        };
+        self.webhook_repo
+            .record_attempt(
+                delivery.id,
+                status,
+                retry_after as f64,
+                response_status,
+                response_body.as_deref(),
+                error.as_deref(),
+                duration_ms,
+            )
+            .await?;
+
+        if succeeded {
+            self.webhook_repo.record_success(webhook.id).await?;
+            return Ok(());
+        }
+
+        let updated = self
+            .webhook_repo
+            .record_failure(webhook.id, DISABLE_AFTER_FAILURES)
+            .await?;
+        if let Some(updated) = updated.filter(|w| !w.active && w.disabled_at.is_some()) {
+            let failed = self
+                .webhook_repo
+                .fail_pending_deliveries(updated.id, "Webhook disabled after repeated failures")
+                .await?;
+            tracing::warn!(
+                "Disabled webhook {} after {} consecutive failures ({} pending deliveries dropped)",
+                updated.id,
+                updated.consecutive_failures,
+                failed
+            );
+        }
+
+        Ok(())
+    }
+
+    /// `attempts` 回目の失敗後、次に送信するまでの秒数（指数バックオフ）
+    fn retry_after(&self, attempts: i32) -> u64 {
+        let exponent = (attempts - 1).clamp(0, 20) as u32;
+        self.retry_base
+            .saturating_mul(2u64.saturating_pow(exponent))
+            .min(MAX_RETRY_SECONDS)
+    }
+}
+
+/// レスポンスボディを最大バイト数まで読み取る（残りは受信しない）
+async fn read_body(mut response: reqwest::Response) -> String {
+    let mut bytes = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        let remaining = MAX_RESPONSE_BODY - bytes.len();
+        bytes.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        if bytes.len() >= MAX_RESPONSE_BODY {
+            break;
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// プライベート・ループバック・リンクローカルなど、Webhookの送信先として許可しないアドレスか
+fn check_public_ip(ip: IpAddr) -> Result<(), String> {
+    let private = match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))
+        }
+        IpAddr::V6(v6) => match embedded_ipv4(v6) {
+            Some(v4) => return check_public_ip(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80
+            }
+        },
+    };
+    if private {
+        return Err(format!("Webhook URL must not point to a private address ({})", ip));
+    }
+    Ok(())
+}
+
+/// IPv4アドレスを埋め込んだIPv6アドレスから、転送先になるIPv4アドレスを取り出す
+/// IPv4射影（`::ffff:0:0/96`）・IPv4互換（`::/96`）・NAT64（`64:ff9b::/96`）・6to4（`2002::/16`）
+fn embedded_ipv4(v6: Ipv6Addr) -> Option<Ipv4Addr> {
+    let octets = v6.octets();
+    match v6.segments() {
+        [0, 0, 0, 0, 0, 0 | 0xffff, ..] => v6.to_ipv4(),
+        [0x64, 0xff9b, 0, 0, 0, 0, ..] => {
+            Some(Ipv4Addr::new(octets[12], octets[13], octets[14], octets[15]))
+        }
+        [0x2002, ..] => Some(Ipv4Addr::new(octets[2], octets[3], octets[4], octets[5])),
+        _ => None,
+    }
+}
+
+/// ホスト名を解決し、すべてのアドレスが公開アドレスの場合のみ返す
+async fn resolve_public(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve '{}': {}", host, e))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("Failed to resolve '{}'", host));
+    }
+    for addr in &addrs {
+        check_public_ip(addr.ip())?;
+    }
+    Ok(addrs)
+}
+
+/// 送信時の名前解決で公開アドレス以外を拒否する（登録後にDNSの応答が変わっても送信しない）
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs = resolve_public(&host, 0).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
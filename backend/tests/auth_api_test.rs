@@ -8,7 +8,7 @@ use todo_backend::{build_app_state, build_router};
 use tower::ServiceExt;
 
 mod helper;
-use helper::{post_json, response_json, test_config};
+use helper::{authed_request, post_json, register_and_login_user, response_json, test_config};
 
 // ////////////////////////////////////////////////////////////
 // テストケース
@@ -276,3 +276,65 @@ async fn test_me_with_valid_token(pool: PgPool) {
     let json = response_json(response.into_body()).await;
     assert_eq!(json["email"], "me@example.com");
 }
+
+// プロフィールの初期値を取得・更新でき、/api/auth/me にもプロフィールが含まれることを確認する
+#[sqlx::test]
+async fn test_profile(pool: PgPool) {
+    let config = test_config();
+    let state = build_app_state(pool, config);
+    let app = build_router(state);
+    let (app, token) = register_and_login_user(app, "profile@example.com").await;
+
+    let response = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/auth/me/profile", &token, None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response_json(response.into_body()).await;
+    assert_eq!(
+        json,
+        json!({"displayName": null, "timeZone": "UTC", "locale": "ja-JP", "weekStart": "monday"})
+    );
+
+    let profile = json!({
+        "displayName": "  Hanako  ",
+        "timeZone": "Asia/Tokyo",
+        "locale": "en-US",
+        "weekStart": "sunday",
+    });
+    let response = app
+        .clone()
+        .oneshot(authed_request(Method::PUT, "/api/auth/me/profile", &token, Some(&profile)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response_json(response.into_body()).await;
+    assert_eq!(json["displayName"], "Hanako");
+    assert_eq!(json["timeZone"], "Asia/Tokyo");
+
+    let response = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/auth/me", &token, None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response_json(response.into_body()).await;
+    assert_eq!(json["email"], "profile@example.com");
+    assert!(json["createdAt"].is_string());
+    assert_eq!(
+        json["profile"],
+        json!({"displayName": "Hanako", "timeZone": "Asia/Tokyo", "locale": "en-US", "weekStart": "sunday"})
+    );
+
+    // 不明なタイムゾーン・不正なロケールは400
+    for (time_zone, locale) in [("Mars/Olympus", "ja-JP"), ("UTC", "日本語"), ("UTC", "en_US")] {
+        let body = json!({"timeZone": time_zone, "locale": locale, "weekStart": "monday"});
+        let response = app
+            .clone()
+            .oneshot(authed_request(Method::PUT, "/api/auth/me/profile", &token, Some(&body)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{} {}", time_zone, locale);
+    }
+}
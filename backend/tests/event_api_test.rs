@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use todo_backend::{build_app_state, build_router};
+use tower::ServiceExt;
+
+mod helper;
+use helper::{authed_request, register_and_login, register_and_login_user, response_json, test_config};
+
+// ////////////////////////////////////////////////////////////
+// テストケース
+// ////////////////////////////////////////////////////////////
+
+const URI_EVENTS: &str = "/api/events";
+const URI_TODOS: &str = "/api/todos";
+
+/// SSEのイベント（ID・イベント名・データ）
+struct SseEvent {
+    id: String,
+    event: String,
+    data: Value,
+}
+
+/// ストリームから次のイベントを読む（キープアライブのコメントは読み飛ばす）
+async fn next_event(body: &mut Body, buffer: &mut String) -> SseEvent {
+    loop {
+        if let Some(end) = buffer.find("\n\n") {
+            let block: String = buffer.drain(..end + 2).collect();
+            let field = |name: &str| {
+                block.lines().find_map(|line| {
+                    line.strip_prefix(name)
+                        .and_then(|v| v.strip_prefix(':'))
+                        .map(|v| v.strip_prefix(' ').unwrap_or(v).to_string())
+                })
+            };
+            if let Some(event) = field("event") {
+                return SseEvent {
+                    id: field("id").unwrap_or_default(),
+                    event,
+                    data: serde_json::from_str(&field("data").unwrap_or_default()).unwrap(),
+                };
+            }
+            continue;
+        }
+
+        let frame = tokio::time::timeout(Duration::from_secs(10), body.frame())
+            .await
+            .expect("Timed out waiting for an event")
+            .expect("Stream ended")
+            .unwrap();
+        if let Ok(data) = frame.into_data() {
+            buffer.push_str(std::str::from_utf8(&data).unwrap());
+        }
+    }
+}
+
+fn events_request(token: &str, last_event_id: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(Method::GET)
+        .uri(URI_EVENTS)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token));
+    if let Some(id) = last_event_id {
+        builder = builder.header("Last-Event-ID", id);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+// 別のインスタンスでの変更が、自分のToDoの分だけ通知され、Last-Event-IDで再開できることを確認する
+#[sqlx::test]
+async fn test_event_stream(pool: PgPool) {
+    // 変更を受け付けるインスタンスと、ストリームを配信するインスタンス
+    let app = build_router(build_app_state(pool.clone(), test_config()));
+    let stream_app = build_router(build_app_state(pool, test_config()));
+    let (app, token) = register_and_login(app).await;
+    let (app, other_token) = register_and_login_user(app, "other@example.com").await;
+
+    let resp = stream_app
+        .clone()
+        .oneshot(events_request(&token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/event-stream");
+    let mut body = resp.into_body();
+    let mut buffer = String::new();
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::POST, URI_TODOS, &token, Some(&json!({"title": "Sync tabs"}))))
+        .await
+        .unwrap();
+    let todo_id = response_json(resp.into_body()).await["id"].clone();
+
+    let created = next_event(&mut body, &mut buffer).await;
+    assert_eq!(created.event, "todo.created");
+    assert_eq!(created.data["event"], "todo.created");
+    assert_eq!(created.data["data"]["todoId"], todo_id);
+    assert_eq!(created.data["data"]["todo"]["title"], "Sync tabs");
+
+    // 他のユーザーの変更は通知されない
+    app.clone()
+        .oneshot(authed_request(Method::POST, URI_TODOS, &other_token, Some(&json!({"title": "Other"}))))
+        .await
+        .unwrap();
+    app.clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &format!("{}/{}/status", URI_TODOS, todo_id.as_str().unwrap()),
+            &token,
+            Some(&json!({"status": "inProgress"})),
+        ))
+        .await
+        .unwrap();
+
+    let changed = next_event(&mut body, &mut buffer).await;
+    assert_eq!(changed.event, "todo.status_changed");
+    assert_eq!(changed.data["data"]["changes"]["status"]["new"], "inProgress");
+    assert!(changed.id.parse::<i64>().unwrap() > created.id.parse::<i64>().unwrap());
+    drop(body);
+
+    // 最後に受け取ったIDから再開すると、その後のイベントが再送される
+    let resp = stream_app
+        .clone()
+        .oneshot(events_request(&token, Some(&created.id)))
+        .await
+        .unwrap();
+    let mut body = resp.into_body();
+    let mut buffer = String::new();
+    let resumed = next_event(&mut body, &mut buffer).await;
+    assert_eq!(resumed.id, changed.id);
+    assert_eq!(resumed.event, "todo.status_changed");
+
+    let resp = stream_app
+        .oneshot(Request::builder().uri(URI_EVENTS).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// 同じユーザーの履歴は、先に書き込んだトランザクションがコミットするまで採番されないことを確認する
+#[sqlx::test]
+async fn test_event_seq_follows_commit_order(pool: PgPool) {
+    let app = build_router(build_app_state(pool.clone(), test_config()));
+    let (app, token) = register_and_login(app).await;
+    let mut todo_ids = Vec::new();
+    for title in ["First", "Second"] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(Method::POST, URI_TODOS, &token, Some(&json!({"title": title}))))
+            .await
+            .unwrap();
+        let id = response_json(resp.into_body()).await["id"].as_str().unwrap().to_string();
+        todo_ids.push(uuid::Uuid::parse_str(&id).unwrap());
+    }
+
+    const INSERT_HISTORY: &str = r#"
+        insert into todo_history (todo_id, version, action, snapshot)
+        values ($1, 100, 'updated', '{}'::jsonb)
+        returning seq
+    "#;
+
+    let mut first = pool.begin().await.unwrap();
+    let first_seq: i64 = sqlx::query_scalar(INSERT_HISTORY)
+        .bind(todo_ids[0])
+        .fetch_one(&mut *first)
+        .await
+        .unwrap();
+
+    // 後から始めたトランザクションは、先のトランザクションのコミットを待つ
+    let second_pool = pool.clone();
+    let second_id = todo_ids[1];
+    let second = tokio::spawn(async move {
+        let mut tx = second_pool.begin().await.unwrap();
+        let seq: i64 = sqlx::query_scalar(INSERT_HISTORY)
+            .bind(second_id)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        seq
+    });
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(!second.is_finished());
+
+    first.commit().await.unwrap();
+    let second_seq = tokio::time::timeout(Duration::from_secs(10), second)
+        .await
+        .expect("Timed out waiting for the second transaction")
+        .unwrap();
+    assert!(second_seq > first_seq);
+}
@@ -0,0 +1,121 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
+use todo_backend::models::todo::{TodoPriority, TodoStatus};
+use todo_backend::services::filter::{
+    parse, Comparison, Condition, DateField, FilterError, FilterExpr,
+};
+
+fn now() -> chrono::DateTime<Tz> {
+    Tz::UTC.with_ymd_and_hms(2026, 3, 16, 9, 30, 0).unwrap()
+}
+
+fn condition(condition: Condition) -> Box<FilterExpr> {
+    Box::new(FilterExpr::Condition(condition))
+}
+
+// and が or より優先され、括弧と not で組み合わせられることを確認する
+#[test]
+fn test_parse_precedence() {
+    let expr = parse(
+        "priority:high and (status:pending or due:<today) and not tag:someday",
+        now(),
+    )
+    .unwrap();
+
+    let today = Utc.with_ymd_and_hms(2026, 3, 16, 0, 0, 0).unwrap();
+    let end_of_today = Utc.with_ymd_and_hms(2026, 3, 17, 0, 0, 0).unwrap()
+        - chrono::Duration::microseconds(1);
+    let expected = FilterExpr::And(
+        Box::new(FilterExpr::And(
+            condition(Condition::Priority(Comparison::Eq, TodoPriority::High)),
+            Box::new(FilterExpr::Or(
+                condition(Condition::Status(TodoStatus::Pending)),
+                condition(Condition::Date {
+                    field: DateField::Due,
+                    comparison: Comparison::Lt,
+                    start: today,
+                    end: end_of_today,
+                }),
+            )),
+        )),
+        Box::new(FilterExpr::Not(condition(Condition::Tag("someday".into())))),
+    );
+    assert_eq!(expr, expected);
+
+    // and は省略でき、- は not と同じ
+    let expr = parse("a or b -c", now()).unwrap();
+    let expected = FilterExpr::Or(
+        condition(Condition::Text("a".into())),
+        Box::new(FilterExpr::And(
+            condition(Condition::Text("b".into())),
+            Box::new(FilterExpr::Not(condition(Condition::Text("c".into())))),
+        )),
+    );
+    assert_eq!(expr, expected);
+}
+
+// 比較演算子・相対日付・引用符付きの値を解析できることを確認する
+#[test]
+fn test_parse_values() {
+    let expr = parse("priority:>=medium", now()).unwrap();
+    assert_eq!(
+        expr,
+        FilterExpr::Condition(Condition::Priority(Comparison::Ge, TodoPriority::Medium))
+    );
+
+    let expr = parse("due:+7d", now()).unwrap();
+    let FilterExpr::Condition(Condition::Date { start, .. }) = expr else {
+        panic!("unexpected expression: {:?}", expr);
+    };
+    assert_eq!(start, Utc.with_ymd_and_hms(2026, 3, 23, 0, 0, 0).unwrap());
+
+    let expr = parse("completed:none", now()).unwrap();
+    assert_eq!(
+        expr,
+        FilterExpr::Condition(Condition::NoDate(DateField::Completed))
+    );
+
+    let expr = parse(r#"tag:"next week" "say \"hi\"" "OR""#, now()).unwrap();
+    let expected = FilterExpr::And(
+        Box::new(FilterExpr::And(
+            condition(Condition::Tag("next week".into())),
+            condition(Condition::Text("say \"hi\"".into())),
+        )),
+        condition(Condition::Text("OR".into())),
+    );
+    assert_eq!(expr, expected);
+}
+
+// 構文エラーの位置（1始まりの文字位置）が返ることを確認する
+#[test]
+fn test_parse_error_positions() {
+    let error = |input: &str| parse(input, now()).unwrap_err();
+
+    assert_eq!(
+        error("status:pending and foo:bar"),
+        FilterError {
+            position: 20,
+            message: "Unknown field 'foo'".into()
+        }
+    );
+    assert_eq!(error("priority:urgent").position, 10);
+    assert_eq!(error("due:<=someday").position, 7);
+    assert_eq!(error("(status:pending or tag:x").position, 1);
+    assert_eq!(error("status:pending)").position, 15);
+    assert_eq!(error("tag:x and").position, 10);
+    assert_eq!(error("tag:x or or tag:y").position, 10);
+    assert_eq!(error(r#"tag:"unterminated"#).position, 5);
+    assert_eq!(error("status:>pending").position, 9);
+    assert_eq!(error("").position, 1);
+}
+
+// 入れ子が深すぎる式と長すぎる式はエラーになることを確認する
+#[test]
+fn test_parse_limits() {
+    let nested = format!("{}tag:x{}", "(".repeat(40), ")".repeat(40));
+    let error = parse(&nested, now()).unwrap_err();
+    assert_eq!(error.position, 33);
+
+    let long = "tag:x ".repeat(200);
+    assert!(parse(&long, now()).is_err());
+}
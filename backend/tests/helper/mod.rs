@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use axum::{
     body::Body,
     http::{header, Method, Request},
@@ -29,6 +31,12 @@ pub fn post_json(uri: &str, body: &Value) -> Request<Body> {
         .unwrap()
 }
 
+/// クエリパラメータをURLエンコードしてURIを作成するヘルパー
+pub fn uri_with_query(path: &str, params: &[(&str, &str)]) -> String {
+    let url = reqwest::Url::parse_with_params(&format!("http://localhost{}", path), params).unwrap();
+    format!("{}?{}", url.path(), url.query().unwrap_or_default())
+}
+
 pub fn authed_request(
     method: Method,
     uri: &str,
@@ -0,0 +1,120 @@
+use chrono::{TimeZone, Utc};
+use todo_backend::models::calendar::CalendarComponent;
+use todo_backend::models::todo::{Todo, TodoPriority, TodoStatus};
+use todo_backend::services::ical::{escape_text, fold, parse_vtodo, render, ParsedVtodo};
+use uuid::Uuid;
+
+fn todo(title: &str, status: TodoStatus, priority: TodoPriority) -> Todo {
+    let created_at = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+    Todo {
+        id: Uuid::nil(),
+        user_id: Uuid::nil(),
+        title: title.to_string(),
+        description: None,
+        due_date: Some(Utc.with_ymd_and_hms(2026, 3, 20, 15, 30, 0).unwrap()),
+        status,
+        priority,
+        created_at,
+        updated_at: created_at,
+        deleted_at: None,
+        version: 2,
+        tags: vec!["work".to_string()],
+        position: "a".to_string(),
+        column_id: None,
+        estimate_minutes: None,
+        start_date: None,
+        completed_at: None,
+        parent_id: None,
+    }
+}
+
+// ステータス・優先度が対応付けられ、期限のないToDoは出力されないことを確認する
+#[test]
+fn test_render_components() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 16, 0, 0, 0).unwrap();
+    let mut done = todo("Done", TodoStatus::Completed, TodoPriority::Low);
+    done.completed_at = Some(Utc.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap());
+    let mut undated = todo("Undated", TodoStatus::Pending, TodoPriority::Medium);
+    undated.due_date = None;
+    let todos = [
+        todo("Report", TodoStatus::InProgress, TodoPriority::High),
+        done,
+        undated,
+    ];
+
+    let ics = render(&todos, &[CalendarComponent::Vtodo], "ToDo", now);
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VTODO").count(), 2);
+    assert!(!ics.contains("BEGIN:VEVENT"));
+    assert!(!ics.contains("Undated"));
+    assert!(ics.contains("SUMMARY:Report\r\nPRIORITY:1\r\nCATEGORIES:work\r\nDUE:20260320T153000Z\r\nSTATUS:IN-PROCESS\r\n"));
+    assert!(ics.contains("STATUS:COMPLETED\r\nCOMPLETED:20260315T120000Z\r\nPERCENT-COMPLETE:100\r\n"));
+    assert!(ics.contains("PRIORITY:9\r\n"));
+    assert!(ics.contains("DTSTAMP:20260316T000000Z\r\n"));
+
+    let ics = render(&todos, &[CalendarComponent::Vevent], "ToDo", now);
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+    assert!(ics.contains("UID:00000000-0000-0000-0000-000000000000-due@todo-web\r\n"));
+    assert!(ics.contains("DTSTART:20260320T153000Z\r\nTRANSP:TRANSPARENT\r\n"));
+}
+
+// TEXT値のエスケープと、75オクテットでの折り返しがUTF-8の文字を分割しないことを確認する
+#[test]
+fn test_escape_and_fold() {
+    assert_eq!(escape_text("a,b;c\\d\r\ne"), "a\\,b\\;c\\\\d\\ne");
+
+    assert_eq!(fold("SUMMARY:short"), "SUMMARY:short\r\n");
+
+    let line = format!("SUMMARY:{}", "あ".repeat(40));
+    let folded = fold(&line);
+    for part in folded.split("\r\n").filter(|p| !p.is_empty()) {
+        assert!(part.len() <= 75, "{} octets", part.len());
+    }
+    assert_eq!(folded.replace("\r\n ", ""), format!("{}\r\n", line));
+}
+
+// VTODOの各項目を読み取り、TZIDのない日時・日付はユーザーのタイムゾーンで解釈されることを確認する
+#[test]
+fn test_parse_vtodo() {
+    let tz: chrono_tz::Tz = "Asia/Tokyo".parse().unwrap();
+    let ics = concat!(
+        "BEGIN:VCALENDAR\r\n",
+        "VERSION:2.0\r\n",
+        "BEGIN:VTODO\r\n",
+        "UID:abc@example.com\r\n",
+        "SUMMARY:Call\\, then write \r\n",
+        " report\r\n",
+        "DESCRIPTION:line1\\nline2\r\n",
+        "DTSTART;VALUE=DATE:20260320\r\n",
+        "DUE;TZID=America/New_York:20260321T090000\r\n",
+        "PRIORITY:2\r\n",
+        "CATEGORIES:work,home\r\n",
+        "STATUS:IN-PROCESS\r\n",
+        "BEGIN:VALARM\r\n",
+        "DESCRIPTION:alarm\r\n",
+        "END:VALARM\r\n",
+        "END:VTODO\r\n",
+        "END:VCALENDAR\r\n",
+    );
+
+    let parsed = parse_vtodo(ics, &tz).unwrap();
+    assert_eq!(
+        parsed,
+        ParsedVtodo {
+            uid: Some("abc@example.com".to_string()),
+            title: Some("Call, then write report".to_string()),
+            description: Some("line1\nline2".to_string()),
+            start_date: Some(Utc.with_ymd_and_hms(2026, 3, 19, 15, 0, 0).unwrap()),
+            due_date: Some(Utc.with_ymd_and_hms(2026, 3, 21, 13, 0, 0).unwrap()),
+            status: Some(TodoStatus::InProgress),
+            priority: Some(TodoPriority::High),
+            tags: vec!["work".to_string(), "home".to_string()],
+        }
+    );
+
+    let completed = "BEGIN:VTODO\nSUMMARY:x\nCOMPLETED:20260301T000000Z\nEND:VTODO\n";
+    assert_eq!(parse_vtodo(completed, &tz).unwrap().status, Some(TodoStatus::Completed));
+
+    assert!(parse_vtodo("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n", &tz).is_err());
+}
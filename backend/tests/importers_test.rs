@@ -0,0 +1,132 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use todo_backend::models::todo::{TodoPriority, TodoStatus};
+use todo_backend::models::transfer::{ImportNoteKind, ImportSource};
+use todo_backend::services::importers::{parse, Notes};
+
+const TZ: Tz = chrono_tz::Asia::Tokyo;
+
+// Todoistのバックアップ（CSV）のインデントがサブタスク、ラベルがタグになることを確認する
+#[test]
+fn test_parse_todoist_csv() {
+    let csv = concat!(
+        "TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE,DURATION,DURATION_UNIT\n",
+        "section,Errands,,,,,,,,,,\n",
+        "task,Buy milk @shop,2 liters,4,1,,,2026-03-20,en,Asia/Tokyo,15,minute\n",
+        "note,Organic if possible,,,,,,,,,,\n",
+        "task,Check expiry,,2,2,,,,,,,\n",
+        "task,Call mom,,1,1,,,,,,,\n",
+    );
+    let mut notes = Notes::default();
+    let tasks = parse(ImportSource::Todoist, &json!(csv), &TZ, &mut notes).unwrap();
+
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].title, "Buy milk");
+    assert_eq!(tasks[0].tags, vec!["shop"]);
+    assert_eq!(tasks[0].priority, Some(TodoPriority::High));
+    assert_eq!(tasks[0].description.as_deref(), Some("2 liters\n\nOrganic if possible"));
+    assert_eq!(tasks[0].due_date, Some(Utc.with_ymd_and_hms(2026, 3, 19, 15, 0, 0).unwrap()));
+    assert_eq!(tasks[0].estimate_minutes, Some(15));
+    assert_eq!(tasks[0].subtasks.len(), 1);
+    assert_eq!(tasks[0].subtasks[0].title, "Check expiry");
+    assert_eq!(tasks[0].subtasks[0].priority, Some(TodoPriority::Medium));
+    assert_eq!(tasks[1].priority, None);
+
+    let fields: Vec<&str> = notes.0.iter().map(|n| n.field.as_str()).collect();
+    assert_eq!(fields, vec!["section", "note", "priority"]);
+}
+
+// Taskwarriorの状態・優先度・プロジェクトの変換と、削除済みタスクの除外を確認する
+#[test]
+fn test_parse_taskwarrior() {
+    let content = json!([
+        {"description": "Write report", "status": "pending", "priority": "H", "project": "work",
+         "tags": ["office"], "due": "20260320T000000Z", "entry": "20260301T090000Z",
+         "start": "20260302T090000Z", "annotations": [{"entry": "20260302T090000Z", "description": "Draft in docs"}]},
+        {"description": "Pay rent", "status": "completed", "end": "20260305T120000Z", "recur": "monthly"},
+        {"description": "Old task", "status": "deleted"}
+    ]);
+    let mut notes = Notes::default();
+    let tasks = parse(ImportSource::Taskwarrior, &content, &TZ, &mut notes).unwrap();
+
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].status, Some(TodoStatus::InProgress));
+    assert_eq!(tasks[0].priority, Some(TodoPriority::High));
+    assert_eq!(tasks[0].tags, vec!["+work", "office"]);
+    assert_eq!(tasks[0].description.as_deref(), Some("Draft in docs"));
+    assert_eq!(tasks[0].due_date, Some(Utc.with_ymd_and_hms(2026, 3, 20, 0, 0, 0).unwrap()));
+    assert_eq!(tasks[1].status, Some(TodoStatus::Completed));
+    assert_eq!(tasks[1].completed_at, Some(Utc.with_ymd_and_hms(2026, 3, 5, 12, 0, 0).unwrap()));
+
+    assert_eq!(notes.0.len(), 2);
+    assert!(notes.0.iter().all(|n| n.kind == ImportNoteKind::Skipped));
+}
+
+// Microsoft To Doのリスト・チェックリスト・HTMLの本文・タイムゾーンの変換を確認する
+#[test]
+fn test_parse_microsoft_todo() {
+    let content = json!({"lists": [{
+        "displayName": "Home",
+        "wellknownListName": "none",
+        "tasks": [{
+            "title": "Clean kitchen",
+            "importance": "low",
+            "status": "waitingOnOthers",
+            "body": {"content": "<p>Use the <b>new</b> sponge</p><p>Then mop</p>", "contentType": "html"},
+            "dueDateTime": {"dateTime": "2026-03-20T09:00:00.0000000", "timeZone": "Tokyo Standard Time"},
+            "categories": ["chores"],
+            "checklistItems": [
+                {"displayName": "Dishes", "isChecked": true, "checkedDateTime": "2026-03-18T10:00:00Z"},
+                {"displayName": "Floor", "isChecked": false}
+            ],
+            "recurrence": {"pattern": {"type": "weekly"}}
+        }]
+    }]});
+    let mut notes = Notes::default();
+    let tasks = parse(ImportSource::MicrosoftTodo, &content, &TZ, &mut notes).unwrap();
+
+    assert_eq!(tasks.len(), 1);
+    let task = &tasks[0];
+    assert_eq!(task.priority, Some(TodoPriority::Low));
+    assert_eq!(task.status, None);
+    assert_eq!(task.description.as_deref(), Some("Use the new sponge\nThen mop"));
+    assert_eq!(task.due_date, Some(Utc.with_ymd_and_hms(2026, 3, 20, 0, 0, 0).unwrap()));
+    assert_eq!(task.tags, vec!["chores", "+Home"]);
+    assert_eq!(task.subtasks.len(), 2);
+    assert_eq!(task.subtasks[0].status, Some(TodoStatus::Completed));
+    assert_eq!(task.subtasks[1].status, None);
+
+    let fields: Vec<&str> = notes.0.iter().map(|n| n.field.as_str()).collect();
+    assert_eq!(fields, vec!["dueDateTime", "body", "status", "recurrence"]);
+}
+
+// 4階層より深いサブタスクは4階層目に取り込み、件数が上限を超える場合はツリーを組み立てずにエラーになることを確認する
+#[test]
+fn test_parse_todoist_deeply_nested() {
+    let chain = |count: usize| {
+        let items: Vec<_> = (0..count)
+            .map(|i| json!({"id": i.to_string(), "content": format!("Task {}", i), "parent_id": i.checked_sub(1).map(|p| p.to_string())}))
+            .collect();
+        json!({"items": items})
+    };
+
+    let mut notes = Notes::default();
+    let tasks = parse(ImportSource::Todoist, &chain(6), &TZ, &mut notes).unwrap();
+    assert_eq!(tasks.len(), 1);
+    let level3 = &tasks[0].subtasks[0].subtasks[0];
+    assert_eq!(level3.title, "Task 2");
+    let titles: Vec<&str> = level3.subtasks.iter().map(|t| t.title.as_str()).collect();
+    assert_eq!(titles, vec!["Task 3", "Task 4", "Task 5"]);
+    assert!(level3.subtasks.iter().all(|t| t.subtasks.is_empty()));
+    assert_eq!(notes.0.len(), 2);
+    assert!(notes.0.iter().all(|n| n.field == "parent" && n.kind == ImportNoteKind::Transformed));
+
+    // 上限の件数まで深くネストしてもスタックを使い切らない
+    let mut notes = Notes::default();
+    let tasks = parse(ImportSource::Todoist, &chain(5000), &TZ, &mut notes).unwrap();
+    assert_eq!(tasks[0].count(), 5000);
+
+    let mut notes = Notes::default();
+    assert!(parse(ImportSource::Todoist, &chain(5001), &TZ, &mut notes).is_err());
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, TimeZone, Utc, Weekday};
+use chrono_tz::{Asia::Tokyo, Tz};
+use todo_backend::models::todo::TodoPriority;
+use todo_backend::services::quick_add::parse;
+
+/// 2026-03-16（月）10:00 JST
+fn now() -> DateTime<Tz> {
+    Tokyo.with_ymd_and_hms(2026, 3, 16, 10, 0, 0).unwrap()
+}
+
+fn jst(month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Tokyo
+        .with_ymd_and_hms(2026, month, day, hour, minute, 0)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+// タイトル・期限・優先度・タグに分解され、期限がタイムゾーンで解釈されることを確認する
+#[test]
+fn test_parse_english() {
+    let parsed = parse("Pay rent tomorrow 9am !high #finance", now(), Weekday::Mon);
+    assert_eq!(parsed.title, "Pay rent");
+    assert_eq!(parsed.due_date, Some(jst(3, 17, 9, 0)));
+    assert_eq!(parsed.priority, Some(TodoPriority::High));
+    assert_eq!(parsed.tags, vec!["finance"]);
+
+    let cases = [
+        ("Lunch fri at noon", "Lunch", Some(jst(3, 20, 12, 0))),
+        ("Call mom in 3 days", "Call mom", Some(jst(3, 19, 0, 0))),
+        ("Review next monday 9:30pm", "Review", Some(jst(3, 23, 21, 30))),
+        ("Standup 9 am", "Standup", Some(jst(3, 16, 9, 0))),
+        ("Renew 2026-04-01", "Renew", Some(jst(4, 1, 0, 0))),
+        ("Check in with team", "Check in with team", None),
+        ("Read 2 books today today", "Read 2 books today", Some(jst(3, 16, 0, 0))),
+    ];
+    for (input, title, due_date) in cases {
+        let parsed = parse(input, now(), Weekday::Mon);
+        assert_eq!(parsed.title, title, "{}", input);
+        assert_eq!(parsed.due_date, due_date, "{}", input);
+    }
+
+    // 前の日付は翌年になる
+    let parsed = parse("Plan 1/5", now(), Weekday::Mon);
+    assert_eq!(
+        parsed.due_date,
+        Some(Tokyo.with_ymd_and_hms(2027, 1, 5, 0, 0, 0).unwrap().with_timezone(&Utc))
+    );
+}
+
+// 日本語の日付・時刻と助詞が取り除かれることを確認する
+#[test]
+fn test_parse_japanese() {
+    let parsed = parse("来週金曜 歯医者 !高 #健康", now(), Weekday::Mon);
+    assert_eq!(parsed.title, "歯医者");
+    assert_eq!(parsed.due_date, Some(jst(3, 27, 0, 0)));
+    assert_eq!(parsed.priority, Some(TodoPriority::High));
+    assert_eq!(parsed.tags, vec!["健康"]);
+
+    let cases = [
+        ("明日の15時に会議", "会議", Some(jst(3, 17, 15, 0))),
+        ("会議 午後3時半", "会議", Some(jst(3, 16, 15, 30))),
+        ("3月20日までに提出", "提出", Some(jst(3, 20, 0, 0))),
+        ("明後日 買い物", "買い物", Some(jst(3, 18, 0, 0))),
+        ("金曜日 掃除", "掃除", Some(jst(3, 20, 0, 0))),
+        ("３日後 返却", "返却", Some(jst(3, 19, 0, 0))),
+        ("毎日の運動", "毎日の運動", None),
+    ];
+    for (input, title, due_date) in cases {
+        let parsed = parse(input, now(), Weekday::Mon);
+        assert_eq!(parsed.title, title, "{}", input);
+        assert_eq!(parsed.due_date, due_date, "{}", input);
+    }
+}
+
+// 優先度として解釈できない語とタグのない # はタイトルに残ることを確認する
+#[test]
+fn test_parse_leaves_unknown_markers() {
+    let parsed = parse("Fix bug !urgent # #work #work !low !high", now(), Weekday::Mon);
+    assert_eq!(parsed.title, "Fix bug !urgent # !high");
+    assert_eq!(parsed.priority, Some(TodoPriority::Low));
+    assert_eq!(parsed.tags, vec!["work"]);
+    assert_eq!(parsed.due_date, None);
+}
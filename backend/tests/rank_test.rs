@@ -0,0 +1,65 @@
+use todo_backend::services::rank::{between, is_valid, spread};
+
+// 前後のキーの間に入るキーが生成されることを確認する
+#[test]
+fn test_between() {
+    let first = between(None, None).unwrap();
+    assert!(is_valid(&first));
+
+    let last = between(Some(&first), None).unwrap();
+    assert!(first < last);
+
+    let head = between(None, Some(&first)).unwrap();
+    assert!(head < first);
+
+    let middle = between(Some(&first), Some(&last)).unwrap();
+    assert!(first < middle && middle < last);
+
+    // 隣接する桁の間でも生成できる
+    let key = between(Some("1"), Some("2")).unwrap();
+    assert!("1" < key.as_str() && key.as_str() < "2");
+    let key = between(Some("0V"), Some("1")).unwrap();
+    assert!("0V" < key.as_str() && key.as_str() < "1");
+}
+
+// 同じ位置への挿入を繰り返しても順序が保たれることを確認する
+#[test]
+fn test_between_repeated() {
+    let upper = between(None, None).unwrap();
+    let mut lower = between(None, Some(&upper)).unwrap();
+    for _ in 0..100 {
+        let key = between(Some(&lower), Some(&upper)).unwrap();
+        assert!(is_valid(&key));
+        assert!(lower < key && key < upper);
+        lower = key;
+    }
+
+    // 末尾への追加ではキーがほとんど伸びない
+    let mut last = between(None, None).unwrap();
+    for _ in 0..100 {
+        let key = between(Some(&last), None).unwrap();
+        assert!(last < key);
+        last = key;
+    }
+    assert!(last.len() <= 5);
+}
+
+// 不正なキーや順序が逆の場合はNoneになることを確認する
+#[test]
+fn test_between_invalid() {
+    assert_eq!(between(Some("b"), Some("a")), None);
+    assert_eq!(between(Some("a"), Some("a")), None);
+    assert_eq!(between(Some("a0"), None), None);
+    assert_eq!(between(Some("a-"), None), None);
+}
+
+// 振り直し用のキーが昇順かつ有効であることを確認する
+#[test]
+fn test_spread() {
+    for count in [0, 1, 2, 61, 62, 1000] {
+        let keys = spread(count);
+        assert_eq!(keys.len(), count);
+        assert!(keys.iter().all(|k| is_valid(k)));
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -0,0 +1,213 @@
+use axum::{
+    http::{Method, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use todo_backend::{build_app_state, build_router};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod helper;
+use helper::{authed_request, register_and_login, register_and_login_user, test_config, uri_with_query};
+
+// ////////////////////////////////////////////////////////////
+// テストケース
+// ////////////////////////////////////////////////////////////
+
+const URI_SYNC: &str = "/api/sync";
+const URI_TODOS: &str = "/api/todos";
+
+async fn send(app: &Router, method: Method, uri: &str, token: &str, body: Option<&Value>) -> (StatusCode, Value) {
+    let resp = app
+        .clone()
+        .oneshot(authed_request(method, uri, token, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = http_body_util::BodyExt::collect(resp.into_body())
+        .await
+        .unwrap()
+        .to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn pull(app: &Router, token: &str, since: &str, limit: &str) -> Value {
+    let uri = uri_with_query(URI_SYNC, &[("since", since), ("limit", limit)]);
+    let (status, body) = send(app, Method::GET, &uri, token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    body
+}
+
+fn ids(items: &Value) -> Vec<&str> {
+    items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap())
+        .collect()
+}
+
+// カーソル以降の作成・更新・削除（ゴミ箱への移動・完全な削除）を順に取得できることを確認する
+#[sqlx::test]
+async fn test_sync_pull(pool: PgPool) {
+    let app = build_router(build_app_state(pool, test_config()));
+    let (app, token) = register_and_login(app).await;
+    let (app, other_token) = register_and_login_user(app, "other@example.com").await;
+
+    let mut todo_ids = Vec::new();
+    for title in ["Milk", "Eggs", "Bread"] {
+        let (_, todo) = send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": title}))).await;
+        todo_ids.push(todo["id"].as_str().unwrap().to_string());
+    }
+    send(&app, Method::POST, URI_TODOS, &other_token, Some(&json!({"title": "Other"}))).await;
+
+    // 初回は全件を件数ごとに取得する
+    let first = pull(&app, &token, "", "2").await;
+    assert_eq!(ids(&first["todos"]), vec![todo_ids[0].as_str(), todo_ids[1].as_str()]);
+    assert_eq!(first["hasMore"], true);
+    let second = pull(&app, &token, first["cursor"].as_str().unwrap(), "2").await;
+    assert_eq!(ids(&second["todos"]), vec![todo_ids[2].as_str()]);
+    assert_eq!(second["hasMore"], false);
+    let cursor = second["cursor"].as_str().unwrap().to_string();
+
+    // 変更がなければカーソルは変わらない
+    let unchanged = pull(&app, &token, &cursor, "100").await;
+    assert!(unchanged["todos"].as_array().unwrap().is_empty());
+    assert_eq!(unchanged["cursor"], cursor.as_str());
+
+    send(&app, Method::PUT, &format!("{}/{}", URI_TODOS, todo_ids[0]), &token, Some(&json!({"title": "Oat milk"}))).await;
+    send(&app, Method::DELETE, &format!("{}/{}", URI_TODOS, todo_ids[1]), &token, None).await;
+    send(&app, Method::DELETE, &format!("{}/{}", URI_TODOS, todo_ids[2]), &token, None).await;
+    let (status, _) = send(&app, Method::DELETE, &format!("{}/trash/{}", URI_TODOS, todo_ids[2]), &token, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let delta = pull(&app, &token, &cursor, "100").await;
+    assert_eq!(ids(&delta["todos"]), vec![todo_ids[0].as_str()]);
+    assert_eq!(delta["todos"][0]["title"], "Oat milk");
+    assert_eq!(ids(&delta["deleted"]), vec![todo_ids[1].as_str(), todo_ids[2].as_str()]);
+    assert!(delta["cursor"].as_str().unwrap().parse::<i64>().unwrap() > cursor.parse::<i64>().unwrap());
+
+    // 初回の同期には削除済みのToDoを含めない
+    let full = pull(&app, &token, "", "100").await;
+    assert_eq!(ids(&full["todos"]), vec![todo_ids[0].as_str()]);
+    assert!(full["deleted"].as_array().unwrap().is_empty());
+
+    let (status, _) = send(&app, Method::GET, &uri_with_query(URI_SYNC, &[("since", "abc")]), &token, None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// クライアントの変更を適用し、元のバージョン以降のサーバーの変更と競合したフィールドはサーバーを優先することを確認する
+#[sqlx::test]
+async fn test_sync_push(pool: PgPool) {
+    let app = build_router(build_app_state(pool, test_config()));
+    let (app, token) = register_and_login(app).await;
+    let (app, other_token) = register_and_login_user(app, "other@example.com").await;
+
+    let cursor = pull(&app, &token, "", "100").await["cursor"].as_str().unwrap().to_string();
+    let id = Uuid::new_v4().to_string();
+    let create = json!({
+        "mutationId": "m1",
+        "op": "create",
+        "id": id,
+        "fields": {"title": "Write report", "priority": "high", "tags": ["work"]},
+    });
+    let (status, body) = send(
+        &app,
+        Method::POST,
+        URI_SYNC,
+        &token,
+        Some(&json!({"cursor": cursor, "mutations": [create, {"op": "create", "id": Uuid::new_v4(), "fields": {}}]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["mutationId"], "m1");
+    assert_eq!(body["results"][0]["status"], "applied");
+    assert_eq!(body["results"][0]["todo"]["id"], id.as_str());
+    assert_eq!(body["results"][1]["status"], "rejected");
+    assert!(body["results"][1]["error"].is_string());
+    assert_eq!(ids(&body["todos"]), vec![id.as_str()]);
+    let version = body["todos"][0]["version"].as_i64().unwrap();
+    let cursor = body["cursor"].as_str().unwrap().to_string();
+
+    // 作成の再送は重複しない（他のユーザーは同じIDを使えない）
+    let (_, body) = send(&app, Method::POST, URI_SYNC, &token, Some(&json!({"cursor": cursor, "mutations": [create]}))).await;
+    assert_eq!(body["results"][0]["status"], "applied");
+    assert!(body["todos"].as_array().unwrap().is_empty());
+    let (_, body) = send(&app, Method::POST, URI_SYNC, &other_token, Some(&json!({"mutations": [create]}))).await;
+    assert_eq!(body["results"][0]["status"], "rejected");
+
+    // オフライン中にサーバーで優先度とタイトルが変更された
+    let todo_uri = format!("{}/{}", URI_TODOS, id);
+    send(&app, Method::PUT, &todo_uri, &token, Some(&json!({"title": "Write Q3 report", "priority": "low"}))).await;
+
+    let update = json!({
+        "op": "update",
+        "id": id,
+        "baseVersion": version,
+        "fields": {"title": "Write final report", "priority": "low", "description": "Due Friday"},
+    });
+    let (_, body) = send(&app, Method::POST, URI_SYNC, &token, Some(&json!({"cursor": cursor, "mutations": [update]}))).await;
+    let result = &body["results"][0];
+    assert_eq!(result["status"], "conflict");
+    assert_eq!(result["conflicts"], json!([{"field": "title", "clientValue": "Write final report", "serverValue": "Write Q3 report"}]));
+    assert_eq!(result["todo"]["title"], "Write Q3 report");
+    assert_eq!(result["todo"]["description"], "Due Friday");
+    assert_eq!(body["todos"][0]["description"], "Due Friday");
+    let version = result["todo"]["version"].as_i64().unwrap();
+    let cursor = body["cursor"].as_str().unwrap().to_string();
+
+    // 元のバージョン以降に変更されたToDoは削除しない
+    let (_, body) = send(
+        &app,
+        Method::POST,
+        URI_SYNC,
+        &token,
+        Some(&json!({"cursor": cursor, "mutations": [{"op": "delete", "id": id, "baseVersion": version - 1}]})),
+    )
+    .await;
+    assert_eq!(body["results"][0]["status"], "conflict");
+    assert_eq!(body["results"][0]["conflicts"][0]["field"], "description");
+    assert!(body["deleted"].as_array().unwrap().is_empty());
+
+    let delete = json!({"op": "delete", "id": id, "baseVersion": version});
+    let (_, body) = send(&app, Method::POST, URI_SYNC, &token, Some(&json!({"cursor": cursor, "mutations": [delete, delete]}))).await;
+    assert_eq!(body["results"][0]["status"], "applied");
+    assert_eq!(body["results"][1]["status"], "applied");
+    assert_eq!(ids(&body["deleted"]), vec![id.as_str()]);
+
+    // 削除済みのToDoへの更新は競合になる
+    let (_, body) = send(&app, Method::POST, URI_SYNC, &token, Some(&json!({"mutations": [update]}))).await;
+    assert_eq!(body["results"][0]["status"], "conflict");
+    assert_eq!(body["results"][0]["conflicts"][0]["field"], "deletedAt");
+    let (status, _) = send(&app, Method::GET, &todo_uri, &token, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// 未完了の依存先があるToDoのステータス変更は適用されないことを確認する
+#[sqlx::test]
+async fn test_sync_push_blocked_status(pool: PgPool) {
+    let app = build_router(build_app_state(pool, test_config()));
+    let (app, token) = register_and_login(app).await;
+
+    let (_, design) = send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": "Design"}))).await;
+    let (_, implement) = send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": "Implement"}))).await;
+    let id = implement["id"].as_str().unwrap();
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        &format!("{}/{}/blockers", URI_TODOS, id),
+        &token,
+        Some(&json!({"blockerId": design["id"]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let update = json!({"op": "update", "id": id, "baseVersion": 1, "fields": {"status": "completed"}});
+    let (_, body) = send(&app, Method::POST, URI_SYNC, &token, Some(&json!({"mutations": [update]}))).await;
+    assert_eq!(body["results"][0]["status"], "rejected");
+    assert!(body["results"][0]["error"].as_str().unwrap().contains("blocked"));
+
+    let (_, todo) = send(&app, Method::GET, &format!("{}/{}", URI_TODOS, id), &token, None).await;
+    assert_eq!(todo["status"], "pending");
+}
@@ -9,6 +9,7 @@ use sqlx::PgPool;
 mod helper;
 use helper::{
     authed_request, register_and_login, register_and_login_user, response_json, test_config,
+    uri_with_query,
 };
 use todo_backend::{build_app_state, build_router};
 use tower::ServiceExt;
@@ -406,3 +407,3604 @@ async fn test_cannot_access_other_user_todo(pool: PgPool) {
         .unwrap();
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
+
+// 削除したToDoがゴミ箱に入り、復元できることを確認する
+#[sqlx::test]
+async fn test_trash_and_restore_todo(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 作成
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({PROP_TITLE: "Trash me"})),
+        ))
+        .await
+        .unwrap();
+    let created = response_json(resp.into_body()).await;
+    let id = created[PROP_ID].as_str().unwrap();
+
+    // 削除 -> ゴミ箱へ
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("{}/{}", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // 通常の一覧には表示されない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 0);
+
+    // ゴミ箱の一覧に表示される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/trash", BASE_URI),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 1);
+    assert_eq!(json[PROP_ITEMS][0][PROP_ID], id);
+    assert!(json[PROP_ITEMS][0]["deletedAt"].is_string());
+
+    // 復元
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/restore", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert!(json["deletedAt"].is_null());
+
+    // 復元後は取得できる
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+// ゴミ箱内のToDoを完全に削除すると復元できなくなることを確認する
+#[sqlx::test]
+async fn test_purge_todo_from_trash(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 作成
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({PROP_TITLE: "Purge me"})),
+        ))
+        .await
+        .unwrap();
+    let created = response_json(resp.into_body()).await;
+    let id = created[PROP_ID].as_str().unwrap();
+
+    // ゴミ箱に入っていないToDoは完全削除できない -> 404
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("{}/trash/{}", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // 削除 -> ゴミ箱へ
+    let _ = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("{}/{}", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+
+    // 完全削除
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("{}/trash/{}", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // 完全削除後は復元できない -> 404
+    let resp = app
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/restore", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// 保持期間を過ぎたゴミ箱内のToDoが定期削除で消えることを確認する
+#[sqlx::test]
+async fn test_purge_expired_trash(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let todo_service = state.todo_service.clone();
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    for title in ["Expired", "Active"] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: title})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+
+        if title == "Expired" {
+            let id = created[PROP_ID].as_str().unwrap();
+            let _ = app
+                .clone()
+                .oneshot(authed_request(
+                    Method::DELETE,
+                    &format!("{}/{}", BASE_URI, id),
+                    &token,
+                    None,
+                ))
+                .await
+                .unwrap();
+        }
+    }
+
+    // 保持期間30日ではまだ削除されない
+    assert_eq!(todo_service.purge_expired_trash(30).await.unwrap(), 0);
+    // 保持期間を-1日にすると即座に削除対象になる
+    assert_eq!(todo_service.purge_expired_trash(-1).await.unwrap(), 1);
+
+    // ゴミ箱は空で、通常のToDoは残っている
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/trash", BASE_URI),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 0);
+
+    let resp = app
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 1);
+}
+
+// 作成・更新・ステータス変更が履歴に記録され、過去のバージョンに戻せることを確認する
+#[sqlx::test]
+async fn test_todo_history_and_revert(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 作成
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({PROP_TITLE: "Original"})),
+        ))
+        .await
+        .unwrap();
+    let created = response_json(resp.into_body()).await;
+    let id = created[PROP_ID].as_str().unwrap();
+
+    // タイトルを更新
+    let _ = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PUT,
+            &format!("{}/{}", BASE_URI, id),
+            &token,
+            Some(&json!({PROP_TITLE: "Renamed"})),
+        ))
+        .await
+        .unwrap();
+
+    // ステータスを変更
+    let _ = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &format!("{}/{}/status", BASE_URI, id),
+            &token,
+            Some(&json!({PROP_STATUS: "completed"})),
+        ))
+        .await
+        .unwrap();
+
+    // 履歴を取得（新しい順）
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}/history", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    let histories = json.as_array().unwrap();
+    assert_eq!(histories.len(), 3);
+    assert_eq!(histories[0]["version"], 3);
+    assert_eq!(histories[0]["action"], "statusChanged");
+    assert_eq!(histories[0]["changes"]["status"]["old"], "pending");
+    assert_eq!(histories[0]["changes"]["status"]["new"], "completed");
+    assert_eq!(histories[1]["action"], "updated");
+    assert_eq!(histories[1]["changes"][PROP_TITLE]["old"], "Original");
+    assert_eq!(histories[1]["changes"][PROP_TITLE]["new"], "Renamed");
+    assert!(histories[1]["changes"].get(PROP_STATUS).is_none());
+    assert_eq!(histories[2]["action"], "created");
+    assert!(histories[2]["actorId"].is_string());
+
+    // バージョン1に戻す
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/history/1/revert", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TITLE], "Original");
+    assert_eq!(json[PROP_STATUS], "pending");
+
+    // 復帰自体も履歴に記録される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}/history", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[0]["version"], 4);
+    assert_eq!(json[0]["action"], "reverted");
+
+    // 存在しないバージョン -> 404
+    let resp = app
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/history/99/revert", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// 同じToDoへの並行する更新がすべて成功し、履歴が欠けずに連番で記録されることを確認する
+#[sqlx::test]
+async fn test_concurrent_updates_record_history(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::POST, BASE_URI, &token, Some(&json!({PROP_TITLE: "Original"}))))
+        .await
+        .unwrap();
+    let id = response_json(resp.into_body()).await[PROP_ID].as_str().unwrap().to_string();
+    let uri = format!("{}/{}", BASE_URI, id);
+
+    let updates = (0..10).map(|i| {
+        let body = json!({PROP_TITLE: format!("Title {}", i)});
+        app.clone().oneshot(authed_request(Method::PUT, &uri, &token, Some(&body)))
+    });
+    for resp in futures::future::join_all(updates).await {
+        assert_eq!(resp.unwrap().status(), StatusCode::OK);
+    }
+
+    let resp = app
+        .oneshot(authed_request(Method::GET, &format!("{}/history", uri), &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let versions: Vec<i64> = json.as_array().unwrap().iter().map(|h| h["version"].as_i64().unwrap()).collect();
+    assert_eq!(versions, (1..=11).rev().collect::<Vec<i64>>());
+}
+
+// 同時に作成したToDoにも異なるランクキーが振られることを確認する
+#[sqlx::test]
+async fn test_concurrent_creates_get_distinct_positions(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let creates = (0..10).map(|i| {
+        let body = json!({PROP_TITLE: format!("Task {}", i)});
+        tokio::spawn(app.clone().oneshot(authed_request(Method::POST, BASE_URI, &token, Some(&body))))
+    });
+    for resp in futures::future::join_all(creates).await {
+        assert_eq!(resp.unwrap().unwrap().status(), StatusCode::CREATED);
+    }
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}?sort=position&perPage=100", BASE_URI),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let mut positions: Vec<&str> = json[PROP_ITEMS]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["position"].as_str().unwrap())
+        .collect();
+    assert_eq!(positions.len(), 10);
+    positions.dedup();
+    assert_eq!(positions.len(), 10);
+}
+
+// ETagが返却され、If-None-Matchで304、古いIf-Matchで412になることを確認する
+#[sqlx::test]
+async fn test_todo_etag_and_conditional_requests(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 作成
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({PROP_TITLE: "Versioned"})),
+        ))
+        .await
+        .unwrap();
+    let created = response_json(resp.into_body()).await;
+    let id = created[PROP_ID].as_str().unwrap();
+    assert_eq!(created["version"], 1);
+
+    // 詳細取得でETagが返る
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::ETAG], "\"1\"");
+
+    // If-None-Match が一致 -> 304
+    let mut req = authed_request(Method::GET, &format!("{}/{}", BASE_URI, id), &token, None);
+    req.headers_mut()
+        .insert(header::IF_NONE_MATCH, "\"1\"".parse().unwrap());
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+    // If-Match が一致 -> 更新成功、ETagが進む
+    let mut req = authed_request(
+        Method::PUT,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!({PROP_TITLE: "First tab"})),
+    );
+    req.headers_mut()
+        .insert(header::IF_MATCH, "\"1\"".parse().unwrap());
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::ETAG], "\"2\"");
+
+    // 古いバージョンでの更新 -> 412
+    let mut req = authed_request(
+        Method::PUT,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!({PROP_TITLE: "Second tab"})),
+    );
+    req.headers_mut()
+        .insert(header::IF_MATCH, "\"1\"".parse().unwrap());
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+    // 古いバージョンでのステータス変更・削除 -> 412
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}/status", BASE_URI, id),
+        &token,
+        Some(&json!({PROP_STATUS: "completed"})),
+    );
+    req.headers_mut()
+        .insert(header::IF_MATCH, "\"1\"".parse().unwrap());
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+    let mut req = authed_request(Method::DELETE, &format!("{}/{}", BASE_URI, id), &token, None);
+    req.headers_mut()
+        .insert(header::IF_MATCH, "\"1\"".parse().unwrap());
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+    // 更新は上書きされていない
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TITLE], "First tab");
+    assert_eq!(json["version"], 2);
+}
+
+// JSON Merge Patchで説明・期限をnullにして削除でき、必須項目はnullにできないことを確認する
+#[sqlx::test]
+async fn test_merge_patch_todo(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 作成
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({
+                PROP_TITLE: "Patch me",
+                PROP_DESCRIPTION: "To be cleared",
+                "dueDate": "2030-01-01T00:00:00Z"
+            })),
+        ))
+        .await
+        .unwrap();
+    let created = response_json(resp.into_body()).await;
+    let id = created[PROP_ID].as_str().unwrap();
+
+    // 説明・期限を削除し、優先度を変更
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!({PROP_DESCRIPTION: null, "dueDate": null, PROP_PRIORITY: "high"})),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/merge-patch+json".parse().unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TITLE], "Patch me");
+    assert!(json[PROP_DESCRIPTION].is_null());
+    assert!(json["dueDate"].is_null());
+    assert_eq!(json[PROP_PRIORITY], "high");
+
+    // タイトルはnullにできない -> 400
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!({PROP_TITLE: null})),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/merge-patch+json".parse().unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // 作成時と同じバリデーション（空タイトル） -> 400
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!({PROP_TITLE: ""})),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/merge-patch+json".parse().unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // 編集できないフィールド -> 400
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!({"userId": uuid::Uuid::new_v4()})),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/merge-patch+json".parse().unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // 未対応のContent-Type -> 415
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!({PROP_TITLE: "x"})),
+    );
+    req.headers_mut()
+        .insert(header::CONTENT_TYPE, "text/plain".parse().unwrap());
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+// JSON Patchで項目を削除でき、testオペレーションの失敗で409になることを確認する
+#[sqlx::test]
+async fn test_json_patch_todo(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 作成
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({PROP_TITLE: "Json patch", PROP_DESCRIPTION: "Remove me"})),
+        ))
+        .await
+        .unwrap();
+    let created = response_json(resp.into_body()).await;
+    let id = created[PROP_ID].as_str().unwrap();
+
+    // 説明を削除し、タイトルを置換
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!([
+            {"op": "test", "path": "/title", "value": "Json patch"},
+            {"op": "remove", "path": "/description"},
+            {"op": "replace", "path": "/title", "value": "Patched"}
+        ])),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/json-patch+json".parse().unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TITLE], "Patched");
+    assert!(json[PROP_DESCRIPTION].is_null());
+
+    // testオペレーションが失敗 -> 409
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, id),
+        &token,
+        Some(&json!([
+            {"op": "test", "path": "/title", "value": "Json patch"},
+            {"op": "replace", "path": "/title", "value": "Overwritten"}
+        ])),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/json-patch+json".parse().unwrap(),
+    );
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+// 一括操作がID指定・フィルタ指定の両方で動作し、結果が項目ごとに返ることを確認する
+#[sqlx::test]
+async fn test_bulk_todo_operations(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 3件作成
+    let mut ids = Vec::new();
+    for i in 1..=3 {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: format!("Bulk {}", i), "tags": ["work"]})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        assert_eq!(created["tags"], json!(["work"]));
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+    let unknown_id = uuid::Uuid::new_v4().to_string();
+
+    // ID指定でステータスを一括変更（存在しないIDは失敗として返る）
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "ids": [ids[0], ids[1], unknown_id],
+                "action": {"type": "setStatus", "status": "completed"}
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["succeeded"], 2);
+    assert_eq!(json["failed"], 1);
+    assert_eq!(json["results"][0]["todo"][PROP_STATUS], "completed");
+    assert_eq!(json["results"][2]["id"], unknown_id);
+    assert_eq!(json["results"][2]["success"], false);
+
+    // 重複したIDは1件として処理される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "ids": [ids[2], ids[2]],
+                "action": {"type": "setPriority", "priority": "high"}
+            })),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["succeeded"], 1);
+    assert_eq!(json["failed"], 0);
+    assert_eq!(json["results"].as_array().unwrap().len(), 1);
+
+    // フィルタ指定でタグを一括追加
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "filter": {"status": "completed"},
+                "action": {"type": "addTag", "tag": "done"}
+            })),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["succeeded"], 2);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}?tag=done", BASE_URI),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 2);
+    assert_eq!(json[PROP_ITEMS][0]["tags"], json!(["work", "done"]));
+
+    // ID・フィルタの両方を指定 -> 400
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "ids": [ids[0]],
+                "filter": {"status": "completed"},
+                "action": {"type": "delete"}
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // 一括削除
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({"ids": ids, "action": {"type": "delete"}})),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["succeeded"], 3);
+
+    let resp = app
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 0);
+}
+
+// 開始日より前の期限は一括操作でも設定できず、全件がロールバックされることを確認する
+#[sqlx::test]
+async fn test_bulk_due_date_before_start_date(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let mut ids = Vec::new();
+    for body in [
+        json!({PROP_TITLE: "No start"}),
+        json!({PROP_TITLE: "Starts later", "startDate": "2026-05-10T00:00:00Z"}),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(Method::POST, BASE_URI, &token, Some(&body)))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "ids": ids,
+                "action": {"type": "setDueDate", "dueDate": "2026-05-01T00:00:00Z"}
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    for id in &ids {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::GET,
+                &format!("{}/{}", BASE_URI, id),
+                &token,
+                None,
+            ))
+            .await
+            .unwrap();
+        let json = response_json(resp.into_body()).await;
+        assert!(json["dueDate"].is_null());
+        assert_eq!(json["version"], 1);
+    }
+
+    // 開始日以降の期限は設定できる
+    let resp = app
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "ids": ids,
+                "action": {"type": "setDueDate", "dueDate": "2026-05-20T00:00:00Z"}
+            })),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["succeeded"], 2);
+}
+
+// 他のユーザーのToDoは一括操作の対象にならないことを確認する
+#[sqlx::test]
+async fn test_bulk_cannot_modify_other_user_todo(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+
+    let (app, token_a) = register_and_login_user(app, "userA@example.com").await;
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token_a,
+            Some(&json!({PROP_TITLE: "User A's todo"})),
+        ))
+        .await
+        .unwrap();
+    let todo_a = response_json(resp.into_body()).await;
+    let todo_a_id = todo_a[PROP_ID].as_str().unwrap();
+
+    let (app, token_b) = register_and_login_user(app, "userB@example.com").await;
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token_b,
+            Some(&json!({"ids": [todo_a_id], "action": {"type": "delete"}})),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["succeeded"], 0);
+    assert_eq!(json["failed"], 1);
+
+    // ユーザーAのToDoは残っている
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, todo_a_id),
+            &token_a,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+// 前後のToDoを指定して並び替えができ、sort=positionで並び順が反映されることを確認する
+#[sqlx::test]
+async fn test_move_todo(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let todo_service = state.todo_service.clone();
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let mut ids = Vec::new();
+    for title in ["First", "Second", "Third"] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: title})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+
+    let list_titles = |app: axum::Router| {
+        let token = token.clone();
+        async move {
+            let resp = app
+                .oneshot(authed_request(
+                    Method::GET,
+                    &format!("{}?sort=position", BASE_URI),
+                    &token,
+                    None,
+                ))
+                .await
+                .unwrap();
+            let json = response_json(resp.into_body()).await;
+            json[PROP_ITEMS]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t[PROP_TITLE].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // 作成順に並ぶ
+    assert_eq!(list_titles(app.clone()).await, ["First", "Second", "Third"]);
+
+    // Thirdを First と Second の間へ移動
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/move", BASE_URI, ids[2]),
+            &token,
+            Some(&json!({"after": ids[0], "before": ids[1]})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().contains_key(header::ETAG));
+    assert_eq!(list_titles(app.clone()).await, ["First", "Third", "Second"]);
+
+    // afterのみ指定してFirstを末尾へ移動
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/move", BASE_URI, ids[0]),
+            &token,
+            Some(&json!({"after": ids[1]})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(list_titles(app.clone()).await, ["Third", "Second", "First"]);
+
+    // Thirdの直後への移動を繰り返すとキーが長くなる
+    let mut position = String::new();
+    for i in 0..120 {
+        let (target, before) = if i % 2 == 0 { (&ids[0], &ids[1]) } else { (&ids[1], &ids[0]) };
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                &format!("{}/{}/move", BASE_URI, target),
+                &token,
+                Some(&json!({"after": ids[2], "before": before})),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json = response_json(resp.into_body()).await;
+        position = json["position"].as_str().unwrap().to_string();
+    }
+    assert!(position.len() > 16);
+
+    // 振り直しても並び順は変わらない
+    let before_rebalance = list_titles(app.clone()).await;
+    assert_eq!(before_rebalance, ["Third", "Second", "First"]);
+    assert!(todo_service.rebalance_positions().await.unwrap() >= 1);
+    assert_eq!(list_titles(app.clone()).await, before_rebalance);
+
+    // 自分自身を基準にした移動はバリデーションエラー
+    let resp = app
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/move", BASE_URI, ids[0]),
+            &token,
+            Some(&json!({"after": ids[0]})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// ボードの列へ移動するとステータスが列のカテゴリになり、WIP制限を超える移動は409になることを確認する
+#[sqlx::test]
+async fn test_board_columns_and_wip_limit(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 初回取得時にデフォルト列が作成される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/board", &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    let columns = json["columns"].as_array().unwrap();
+    assert_eq!(columns.len(), 3);
+    assert_eq!(columns[0]["category"], "pending");
+    assert_eq!(columns[2]["category"], "completed");
+
+    // WIP制限1の「Review」列を追加
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/board/columns",
+            &token,
+            Some(&json!({"name": "Review", "category": "inProgress", "wipLimit": 1})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let review = response_json(resp.into_body()).await;
+    let review_id = review[PROP_ID].as_str().unwrap().to_string();
+
+    let mut ids = Vec::new();
+    for title in ["Task A", "Task B"] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: title})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+
+    // Task A を Review 列へ移動するとステータスが inProgress になる
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/move", BASE_URI, ids[0]),
+            &token,
+            Some(&json!({"columnId": review_id})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_STATUS], "inProgress");
+    assert_eq!(json["columnId"], review_id.as_str());
+
+    // WIP制限に達しているため Task B は移動できない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/move", BASE_URI, ids[1]),
+            &token,
+            Some(&json!({"columnId": review_id})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    // ボード上では Review 列に Task A、先頭列に Task B が表示される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/board", &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let columns = json["columns"].as_array().unwrap();
+    let titles = |name: &str| {
+        columns
+            .iter()
+            .find(|c| c["name"] == name)
+            .unwrap()["todos"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t[PROP_TITLE].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(titles("Review"), ["Task A"]);
+    assert_eq!(titles("To Do"), ["Task B"]);
+    assert!(titles("In Progress").is_empty());
+
+    // ステータスを変更すると対応する先頭の列に移る
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &format!("{}/{}/status", BASE_URI, ids[0]),
+            &token,
+            Some(&json!({PROP_STATUS: "completed"})),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert!(json["columnId"].is_null());
+
+    // カテゴリの最後の列は削除できないが、追加した列は削除できる
+    let done_id = columns.iter().find(|c| c["name"] == "Done").unwrap()[PROP_ID]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("/api/board/columns/{}", done_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("/api/board/columns/{}", review_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+// ステータスの変更（PUT・PATCH・ステータス変更・一括変更）でも表示先の列のWIP制限を超える場合は409になることを確認する
+#[sqlx::test]
+async fn test_status_change_respects_wip_limit(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 「In Progress」列にWIP制限1を設定
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/board", &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let in_progress_id = json["columns"][1][PROP_ID].as_str().unwrap().to_string();
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PUT,
+            &format!("/api/board/columns/{}", in_progress_id),
+            &token,
+            Some(&json!({"name": "In Progress", "wipLimit": 1})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let mut ids = Vec::new();
+    for title in ["Task A", "Task B"] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: title})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &format!("{}/{}/status", BASE_URI, ids[0]),
+            &token,
+            Some(&json!({PROP_STATUS: "inProgress"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Task B はどの方法でも inProgress にできない
+    let todo_uri = format!("{}/{}", BASE_URI, ids[1]);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &format!("{}/status", todo_uri),
+            &token,
+            Some(&json!({PROP_STATUS: "inProgress"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PUT,
+            &todo_uri,
+            &token,
+            Some(&json!({PROP_STATUS: "inProgress"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let mut req = authed_request(
+        Method::PATCH,
+        &todo_uri,
+        &token,
+        Some(&json!({PROP_STATUS: "inProgress"})),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/merge-patch+json".parse().unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "ids": [ids[1]],
+                "action": {"type": "setStatus", "status": "inProgress"}
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    // 変更はロールバックされている
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, &todo_uri, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_STATUS], "pending");
+    assert_eq!(json["version"], 1);
+
+    // 同じ列に留まる変更（Task A の再設定）はWIP制限に関係なく成功する
+    let resp = app
+        .oneshot(authed_request(
+            Method::PUT,
+            &format!("{}/{}", BASE_URI, ids[0]),
+            &token,
+            Some(&json!({PROP_STATUS: "inProgress", PROP_PRIORITY: "high"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+// 列への移動と、同じ列を表示先とするステータスの変更を同時に行ってもデッドロックしないことを確認する
+#[sqlx::test]
+async fn test_concurrent_move_and_status_change(pool: PgPool) {
+    let state = build_app_state(pool.clone(), test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/board", &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let in_progress_id = json["columns"][1][PROP_ID].as_str().unwrap().to_string();
+
+    let mut ids = Vec::new();
+    for title in ["Task A", "Task B"] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: title})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+
+    // 列をロックしておき、移動とステータスの変更をその列のロック待ちで重ねる
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("select id from board_columns where id = $1::uuid for update")
+        .bind(&in_progress_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    let move_request = tokio::spawn(app.clone().oneshot(authed_request(
+        Method::POST,
+        &format!("{}/{}/move", BASE_URI, ids[0]),
+        &token,
+        Some(&json!({"columnId": in_progress_id})),
+    )));
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let status_request = tokio::spawn(app.clone().oneshot(authed_request(
+        Method::PATCH,
+        &format!("{}/{}/status", BASE_URI, ids[1]),
+        &token,
+        Some(&json!({PROP_STATUS: "inProgress"})),
+    )));
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    tx.commit().await.unwrap();
+
+    for request in [move_request, status_request] {
+        let resp = tokio::time::timeout(std::time::Duration::from_secs(10), request)
+            .await
+            .expect("Timed out waiting for the request")
+            .unwrap()
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+    assert_status(&app, &token, &ids[0], "inProgress").await;
+    assert_status(&app, &token, &ids[1], "inProgress").await;
+}
+
+// Implement が Design に依存するToDoを作成する（Design, Implement の順にIDを返す）
+async fn create_blocked_todo(app: &axum::Router, token: &str) -> (String, String) {
+    let mut ids = Vec::new();
+    for title in ["Design", "Implement"] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                token,
+                Some(&json!({PROP_TITLE: title})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/blockers", BASE_URI, ids[1]),
+            token,
+            Some(&json!({"blockerId": ids[0]})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let implement = ids.pop().unwrap();
+    (ids.pop().unwrap(), implement)
+}
+
+// ToDoのステータスが変わっていないことを確認する
+async fn assert_status(app: &axum::Router, token: &str, id: &str, status: &str) {
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, id),
+            token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_STATUS], status);
+}
+
+// 依存関係を追加・削除でき、blockedBy・blocking に反映されることを確認する
+#[sqlx::test]
+async fn test_todo_dependencies(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let mut ids = Vec::new();
+    for title in ["Design", "Implement", "Release"] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: title})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+
+    // Implement は Design に、Release は Implement に依存する
+    for (todo, blocker) in [(1, 0), (2, 1)] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                &format!("{}/{}/blockers", BASE_URI, ids[todo]),
+                &token,
+                Some(&json!({"blockerId": ids[blocker]})),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json = response_json(resp.into_body()).await;
+        assert_eq!(json["blockedBy"], json!([ids[blocker]]));
+    }
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, ids[1]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["blockedBy"], json!([ids[0]]));
+    assert_eq!(json["blocking"], json!([ids[2]]));
+
+    // 依存関係を削除
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("{}/{}/blockers/{}", BASE_URI, ids[2], ids[1]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, ids[2]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["blockedBy"], json!([]));
+}
+
+// 循環する依存関係は409になることを確認する
+#[sqlx::test]
+async fn test_todo_dependency_cycle(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+    let (design, implement) = create_blocked_todo(&app, &token).await;
+
+    // Design が Implement に依存すると循環する
+    let resp = app
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/blockers", BASE_URI, design),
+            &token,
+            Some(&json!({"blockerId": implement})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+// 未完了の依存先があるとステータス変更が409になり、force を指定すると変更できることを確認する
+#[sqlx::test]
+async fn test_blocked_todo_status_change(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+    let (design, implement) = create_blocked_todo(&app, &token).await;
+
+    let status_uri = format!("{}/{}/status", BASE_URI, implement);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &status_uri,
+            &token,
+            Some(&json!({PROP_STATUS: "inProgress"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_status(&app, &token, &implement, "pending").await;
+
+    // force を指定すると着手できる
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &status_uri,
+            &token,
+            Some(&json!({PROP_STATUS: "inProgress", "force": true})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Design を完了すると Implement を完了できる
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &format!("{}/{}/status", BASE_URI, design),
+            &token,
+            Some(&json!({PROP_STATUS: "completed"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = app
+        .oneshot(authed_request(
+            Method::PATCH,
+            &status_uri,
+            &token,
+            Some(&json!({PROP_STATUS: "completed"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+// 未完了の依存先があるとPUTでのステータス変更が409になることを確認する
+#[sqlx::test]
+async fn test_blocked_todo_update(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+    let (_, implement) = create_blocked_todo(&app, &token).await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PUT,
+            &format!("{}/{}", BASE_URI, implement),
+            &token,
+            Some(&json!({PROP_STATUS: "completed"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_status(&app, &token, &implement, "pending").await;
+
+    // ステータス以外の更新はできる
+    let resp = app
+        .oneshot(authed_request(
+            Method::PUT,
+            &format!("{}/{}", BASE_URI, implement),
+            &token,
+            Some(&json!({PROP_PRIORITY: "high"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+// 未完了の依存先があるとPATCHでのステータス変更が409になることを確認する
+#[sqlx::test]
+async fn test_blocked_todo_patch(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+    let (_, implement) = create_blocked_todo(&app, &token).await;
+
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, implement),
+        &token,
+        Some(&json!({PROP_STATUS: "inProgress"})),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/merge-patch+json".parse().unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let mut req = authed_request(
+        Method::PATCH,
+        &format!("{}/{}", BASE_URI, implement),
+        &token,
+        Some(&json!([{"op": "replace", "path": "/status", "value": "completed"}])),
+    );
+    req.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "application/json-patch+json".parse().unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_status(&app, &token, &implement, "pending").await;
+}
+
+// 未完了の依存先があると一括でのステータス変更が409になり、依存先も同時に完了する場合は変更できることを確認する
+#[sqlx::test]
+async fn test_blocked_todo_bulk_status(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+    let (design, implement) = create_blocked_todo(&app, &token).await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "ids": [implement],
+                "action": {"type": "setStatus", "status": "completed"}
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_status(&app, &token, &implement, "pending").await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/bulk", BASE_URI),
+            &token,
+            Some(&json!({
+                "ids": [implement, design],
+                "action": {"type": "setStatus", "status": "completed"}
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_status(&app, &token, &implement, "completed").await;
+}
+
+// 未完了の依存先があるとボードの列への移動でのステータス変更が409になることを確認する
+#[sqlx::test]
+async fn test_blocked_todo_move_to_column(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+    let (_, implement) = create_blocked_todo(&app, &token).await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/board", &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let done_id = json["columns"][2][PROP_ID].as_str().unwrap().to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/move", BASE_URI, implement),
+            &token,
+            Some(&json!({"columnId": done_id})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_status(&app, &token, &implement, "pending").await;
+}
+
+// CalDAV用のアプリパスワードを発行し、Basic認証ヘッダーとユーザーIDを返す
+async fn caldav_login(app: &axum::Router, token: &str, email: &str) -> (String, String) {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/auth/app-passwords",
+            token,
+            Some(&json!({"name": "Phone"})),
+        ))
+        .await
+        .unwrap();
+    let password = response_json(resp.into_body()).await["password"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/auth/me", token, None))
+        .await
+        .unwrap();
+    let user_id = response_json(resp.into_body()).await[PROP_ID]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let basic = format!(
+        "Basic {}",
+        STANDARD.encode(format!("{}:{}", email, password))
+    );
+    (basic, user_id)
+}
+
+// 未完了の依存先があるとCalDAVのPUTでのステータス変更が409になることを確認する
+#[sqlx::test]
+async fn test_blocked_todo_caldav_put(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login_user(app, "caldav@example.com").await;
+    let (basic, user_id) = caldav_login(&app, &token, "caldav@example.com").await;
+    let object = format!("/caldav/{}/todos/implement.ics", user_id);
+    let put = |status: &str| {
+        Request::builder()
+            .method(Method::PUT)
+            .uri(&object)
+            .header(header::AUTHORIZATION, basic.as_str())
+            .body(Body::from(format!(
+                concat!(
+                    "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTODO\r\n",
+                    "UID:implement@client\r\nSUMMARY:Implement\r\nSTATUS:{}\r\n",
+                    "END:VTODO\r\nEND:VCALENDAR\r\n",
+                ),
+                status
+            )))
+            .unwrap()
+    };
+
+    let resp = app.clone().oneshot(put("NEEDS-ACTION")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    let implement = response_json(resp.into_body()).await[PROP_ITEMS][0][PROP_ID]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({PROP_TITLE: "Design"})),
+        ))
+        .await
+        .unwrap();
+    let design = response_json(resp.into_body()).await[PROP_ID]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/blockers", BASE_URI, implement),
+            &token,
+            Some(&json!({"blockerId": design})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app.clone().oneshot(put("COMPLETED")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_status(&app, &token, &implement, "pending").await;
+}
+
+// タイマーはユーザーごとに1つまでで、手動登録した作業時間がToDoとレポートに集計されることを確認する
+#[sqlx::test]
+async fn test_time_tracking(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let mut ids = Vec::new();
+    for (title, tags) in [("Write report", json!(["client-a"])), ("Review", json!([]))] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: title, "tags": tags, "estimateMinutes": 90})),
+            ))
+            .await
+            .unwrap();
+        let created = response_json(resp.into_body()).await;
+        assert_eq!(created["estimateMinutes"], 90);
+        assert_eq!(created["trackedSeconds"], 0);
+        ids.push(created[PROP_ID].as_str().unwrap().to_string());
+    }
+
+    // タイマーを開始すると、別のToDoのタイマーは開始できない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/timer/start", BASE_URI, ids[0]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/timer/start", BASE_URI, ids[1]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    // 停止すると終了日時が記録される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/timer/stop", BASE_URI, ids[0]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert!(json["endedAt"].is_string());
+
+    // 停止済みのタイマーは再度停止できない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/{}/timer/stop", BASE_URI, ids[0]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // 手動登録（終了が開始より前の場合はバリデーションエラー）
+    let entries_uri = format!("{}/{}/time-entries", BASE_URI, ids[0]);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &entries_uri,
+            &token,
+            Some(&json!({"startedAt": "2026-03-02T10:00:00Z", "endedAt": "2026-03-02T09:00:00Z"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    for (todo, started_at, ended_at) in [
+        (0, "2026-03-02T09:00:00Z", "2026-03-02T10:30:00Z"),
+        (0, "2026-03-03T09:00:00Z", "2026-03-03T09:30:00Z"),
+        (1, "2026-03-03T13:00:00Z", "2026-03-03T14:00:00Z"),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(
+                Method::POST,
+                &format!("{}/{}/time-entries", BASE_URI, ids[todo]),
+                &token,
+                Some(&json!({"startedAt": started_at, "endedAt": ended_at, "note": "meeting"})),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, &entries_uri, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json.as_array().unwrap().len(), 3);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, ids[0]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert!(json["trackedSeconds"].as_i64().unwrap() >= 2 * 3600);
+
+    // 期間内の作業時間を日別・ToDo別・タグ別に集計
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            "/api/time/report?from=2026-03-01&to=2026-03-03",
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["totalSeconds"], 3 * 3600);
+    assert_eq!(
+        json["byDay"],
+        json!([
+            {"date": "2026-03-02", "seconds": 5400},
+            {"date": "2026-03-03", "seconds": 5400}
+        ])
+    );
+    assert_eq!(json["byTodo"][0]["todoId"], ids[0].as_str());
+    assert_eq!(json["byTodo"][0]["seconds"], 7200);
+    assert_eq!(json["byTag"], json!([{"tag": "client-a", "seconds": 7200}]));
+}
+
+// 完了にすると完了日時が設定され、未完了に戻すと解除され、完了日時で絞り込めることを確認する
+#[sqlx::test]
+async fn test_todo_completed_at_and_start_date(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 開始日が期限より後の場合はバリデーションエラー
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({
+                PROP_TITLE: "Invalid",
+                "startDate": "2026-03-10T00:00:00Z",
+                "dueDate": "2026-03-01T00:00:00Z"
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({PROP_TITLE: "Task", "startDate": "2026-03-01T00:00:00Z"})),
+        ))
+        .await
+        .unwrap();
+    let created = response_json(resp.into_body()).await;
+    assert_eq!(created["startDate"], "2026-03-01T00:00:00Z");
+    assert!(created["completedAt"].is_null());
+    let id = created[PROP_ID].as_str().unwrap().to_string();
+    let status_uri = format!("{}/{}/status", BASE_URI, id);
+
+    // 完了にすると完了日時が設定される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &status_uri,
+            &token,
+            Some(&json!({PROP_STATUS: "completed"})),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let completed_at = json["completedAt"].as_str().unwrap().to_string();
+
+    // 完了日時で絞り込み
+    let list = |query: String| {
+        let app = app.clone();
+        let token = token.clone();
+        async move {
+            let resp = app
+                .oneshot(authed_request(
+                    Method::GET,
+                    &format!("{}?{}", BASE_URI, query),
+                    &token,
+                    None,
+                ))
+                .await
+                .unwrap();
+            response_json(resp.into_body()).await[PROP_TOTAL].clone()
+        }
+    };
+    assert_eq!(list("completedAfter=2000-01-01T00:00:00Z".into()).await, 1);
+    assert_eq!(list("completedBefore=2000-01-01T00:00:00Z".into()).await, 0);
+
+    // 他の項目を更新しても完了日時は変わらない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PUT,
+            &format!("{}/{}", BASE_URI, id),
+            &token,
+            Some(&json!({PROP_TITLE: "Renamed", PROP_STATUS: "completed"})),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["completedAt"], completed_at.as_str());
+
+    // 未完了に戻すと完了日時は解除される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &status_uri,
+            &token,
+            Some(&json!({PROP_STATUS: "inProgress"})),
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert!(json["completedAt"].is_null());
+    assert_eq!(list("completedAfter=2000-01-01T00:00:00Z".into()).await, 0);
+}
+
+// テンプレートから親ToDoとサブタスクが作成され、プレースホルダーと期限が基準日で計算されることを確認する
+#[sqlx::test]
+async fn test_todo_templates(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let template = json!({
+        "name": "Weekly report",
+        "title": "Report {{date}}",
+        "priority": "high",
+        "dueOffsetDays": 3,
+        "tags": ["work"],
+        "subtasks": [
+            {PROP_TITLE: "Collect numbers for {{week}}", "dueOffsetDays": 1},
+            {PROP_TITLE: "Write summary"},
+        ],
+    });
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/templates",
+            &token,
+            Some(&template),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created = response_json(resp.into_body()).await;
+    assert_eq!(created["subtasks"].as_array().unwrap().len(), 2);
+    let id = created[PROP_ID].as_str().unwrap().to_string();
+
+    // 同じ名前のテンプレートは作成できない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/templates",
+            &token,
+            Some(&template),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    // 基準日を指定してToDoを作成
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("/api/templates/{}/instantiate", id),
+            &token,
+            Some(&json!({"date": "2026-03-16"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = response_json(resp.into_body()).await;
+    let todos = json["todos"].as_array().unwrap();
+    assert_eq!(todos.len(), 3);
+
+    let parent_id = todos[0][PROP_ID].as_str().unwrap();
+    assert_eq!(todos[0][PROP_TITLE], "Report 2026-03-16");
+    assert_eq!(todos[0]["dueDate"], "2026-03-19T00:00:00Z");
+    assert_eq!(todos[0]["priority"], "high");
+    assert_eq!(todos[0]["tags"], json!(["work"]));
+    assert!(todos[0]["parentId"].is_null());
+
+    assert_eq!(todos[1][PROP_TITLE], "Collect numbers for 2026-W12");
+    assert_eq!(todos[1]["dueDate"], "2026-03-17T00:00:00Z");
+    assert_eq!(todos[1]["parentId"], parent_id);
+    assert_eq!(todos[2][PROP_TITLE], "Write summary");
+    assert!(todos[2]["dueDate"].is_null());
+    assert_eq!(todos[2]["parentId"], parent_id);
+
+    // 作成順に末尾へ追加される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}?sort=position", BASE_URI),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 3);
+    let titles: Vec<&str> = json[PROP_ITEMS]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t[PROP_TITLE].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        titles,
+        vec!["Report 2026-03-16", "Collect numbers for 2026-W12", "Write summary"]
+    );
+
+    // テンプレートを削除しても作成済みのToDoは残る
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("/api/templates/{}", id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, parent_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+// プレースホルダーの置換後にタイトルが255文字を超える場合は400になり、ToDoは作成されないことを確認する
+#[sqlx::test]
+async fn test_instantiate_template_title_too_long(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    // 248文字のタイトルが置換後に310文字になる
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/templates",
+            &token,
+            Some(&json!({
+                "name": "Daily",
+                "title": "Daily",
+                "subtasks": [{PROP_TITLE: "{{date}}".repeat(31)}],
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let id = response_json(resp.into_body()).await[PROP_ID]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("/api/templates/{}/instantiate", id),
+            &token,
+            Some(&json!({"date": "2026-03-16"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 0);
+}
+
+// 親ToDoをゴミ箱から完全に削除してもサブタスクは残り、親との関係が外れることを確認する
+#[sqlx::test]
+async fn test_purge_parent_todo_keeps_subtasks(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/templates",
+            &token,
+            Some(&json!({
+                "name": "Onboarding",
+                "title": "Onboarding",
+                "subtasks": [{PROP_TITLE: "Create account"}],
+            })),
+        ))
+        .await
+        .unwrap();
+    let template_id = response_json(resp.into_body()).await[PROP_ID]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("/api/templates/{}/instantiate", template_id),
+            &token,
+            Some(&json!({})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = response_json(resp.into_body()).await;
+    let parent_id = json["todos"][0][PROP_ID].as_str().unwrap().to_string();
+    let subtask_id = json["todos"][1][PROP_ID].as_str().unwrap().to_string();
+
+    // 親をゴミ箱へ移動して完全に削除
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("{}/{}", BASE_URI, parent_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("{}/trash/{}", BASE_URI, parent_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}/{}", BASE_URI, subtask_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TITLE], "Create account");
+    assert!(json["parentId"].is_null());
+}
+
+// 保存したビューの相対的な日付が実行時に解決され、テキスト検索と組み合わせて絞り込めることを確認する
+#[sqlx::test]
+async fn test_saved_views(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let now = chrono::Utc::now();
+    for (title, days) in [
+        ("Write report", 3),
+        ("Read report", 20),
+        ("Buy milk", 1),
+        ("Old report", -2),
+    ] {
+        let due_date = (now + chrono::Duration::days(days)).to_rfc3339();
+        app.clone()
+            .oneshot(authed_request(
+                Method::POST,
+                BASE_URI,
+                &token,
+                Some(&json!({PROP_TITLE: title, "dueDate": due_date})),
+            ))
+            .await
+            .unwrap();
+    }
+
+    // 不正な日付の指定はバリデーションエラー
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/views",
+            &token,
+            Some(&json!({"name": "Broken", "filters": {"dueBefore": "next week"}})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/views",
+            &token,
+            Some(&json!({
+                "name": "Reports this week",
+                "filters": {"dueAfter": "today", "dueBefore": "+7d", "q": "REPORT"},
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let view = response_json(resp.into_body()).await;
+    // 日付は解決せずに保存される
+    assert_eq!(view["filters"]["dueBefore"], "+7d");
+    let view_id = view[PROP_ID].as_str().unwrap().to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("/api/views/{}/todos", view_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 1);
+    assert_eq!(json[PROP_ITEMS][0][PROP_TITLE], "Write report");
+
+    // ワイルドカードは文字として検索される
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("{}?q=%25", BASE_URI),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 0);
+
+    // 2つ目のビューを先頭に移動
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/views",
+            &token,
+            Some(&json!({"name": "Everything"})),
+        ))
+        .await
+        .unwrap();
+    let second = response_json(resp.into_body()).await;
+    let second_id = second[PROP_ID].as_str().unwrap().to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("/api/views/{}/move", second_id),
+            &token,
+            Some(&json!({"before": view_id})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/views", &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let names: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Everything", "Reports this week"]);
+
+    // 削除したビューは実行できない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("/api/views/{}", view_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &format!("/api/views/{}/todos", view_id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// 絞り込み式で OR・否定・優先度の範囲を指定でき、構文エラーは位置付きの400になることを確認する
+#[sqlx::test]
+async fn test_todo_filter_expression(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let yesterday = (chrono::Utc::now() - chrono::Duration::days(1)).to_rfc3339();
+    for todo in [
+        json!({PROP_TITLE: "Urgent pending", "priority": "high"}),
+        json!({PROP_TITLE: "Overdue", "priority": "high", PROP_STATUS: "inProgress", "dueDate": yesterday}),
+        json!({PROP_TITLE: "Someday", "priority": "high", "tags": ["someday"]}),
+        json!({PROP_TITLE: "Medium done", "priority": "medium", PROP_STATUS: "completed"}),
+        json!({PROP_TITLE: "Low", "priority": "low"}),
+    ] {
+        app.clone()
+            .oneshot(authed_request(Method::POST, BASE_URI, &token, Some(&todo)))
+            .await
+            .unwrap();
+    }
+
+    let titles = |filter: &str| {
+        let app = app.clone();
+        let token = token.clone();
+        let uri = uri_with_query(BASE_URI, &[("sort", "position"), ("filter", filter)]);
+        async move {
+            let resp = app
+                .oneshot(authed_request(Method::GET, &uri, &token, None))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            let json = response_json(resp.into_body()).await;
+            json[PROP_ITEMS]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t[PROP_TITLE].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(
+        titles("priority:high and (status:pending or due:<today) and not tag:someday").await,
+        vec!["Urgent pending", "Overdue"]
+    );
+    assert_eq!(
+        titles("priority:>=medium -status:completed due:none").await,
+        vec!["Urgent pending", "Someday"]
+    );
+    assert_eq!(titles("low or \"medium done\"").await, vec!["Medium done", "Low"]);
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::GET,
+            &uri_with_query(BASE_URI, &[("filter", "status:pending or")]),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(
+        json["message"],
+        "Invalid filter at position 18: Unexpected end of filter"
+    );
+}
+
+// クイック追加で解析結果と作成したToDoが返り、不正なタイムゾーンは400になることを確認する
+#[sqlx::test]
+async fn test_quick_add_todo(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/quick", BASE_URI),
+            &token,
+            Some(&json!({
+                "text": "Pay rent 2030-01-15 9am !high #finance",
+                "timeZone": "Asia/Tokyo",
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["parsed"][PROP_TITLE], "Pay rent");
+    assert_eq!(json["parsed"]["dueDate"], "2030-01-15T00:00:00Z");
+    assert_eq!(json["todo"][PROP_TITLE], "Pay rent");
+    assert_eq!(json["todo"]["dueDate"], "2030-01-15T00:00:00Z");
+    assert_eq!(json["todo"]["priority"], "high");
+    assert_eq!(json["todo"]["tags"], json!(["finance"]));
+
+    // 日付・優先度・タグだけではタイトルが空になる
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/quick", BASE_URI),
+            &token,
+            Some(&json!({"text": "明日 !高 #家計"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/quick", BASE_URI),
+            &token,
+            Some(&json!({"text": "Pay rent", "timeZone": "Mars/Olympus"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// タイムゾーンを省略したクイック追加と相対日付の絞り込みがプロフィールのタイムゾーン・週の始まりで解釈されることを確認する
+#[sqlx::test]
+async fn test_profile_time_zone(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let profile = json!({"timeZone": "Asia/Tokyo", "locale": "ja-JP", "weekStart": "sunday"});
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::PUT, "/api/auth/me/profile", &token, Some(&profile)))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/quick", BASE_URI),
+            &token,
+            Some(&json!({"text": "Pay rent 2030-01-15 8am"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["todo"]["dueDate"], "2030-01-14T23:00:00Z");
+
+    // 日曜始まりの週では「来週」は次の日曜
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/quick", BASE_URI),
+            &token,
+            Some(&json!({"text": "Plan next week"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = response_json(resp.into_body()).await;
+    let due: chrono::DateTime<chrono::FixedOffset> =
+        json["todo"]["dueDate"].as_str().unwrap().parse().unwrap();
+    let local = due.with_timezone(&chrono_tz::Asia::Tokyo);
+    assert_eq!(chrono::Datelike::weekday(&local), chrono::Weekday::Sun);
+    assert_eq!(local.time(), chrono::NaiveTime::MIN);
+
+    // 日付はプロフィールのタイムゾーンの0時で区切る（UTCでは前日）
+    let uri = uri_with_query(BASE_URI, &[("filter", "due:2030-01-15")]);
+    let resp = app
+        .oneshot(authed_request(Method::GET, &uri, &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 1);
+    assert_eq!(json[PROP_ITEMS][0][PROP_TITLE], "Pay rent");
+}
+
+// 未完了のToDoが期限切れ・今日・明日・今週・それ以降に区分され、期限のないToDoは未来の開始日に表示されることを確認する
+#[sqlx::test]
+async fn test_agenda(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let today = chrono::Utc::now().date_naive();
+    let noon = |days: i64| {
+        (today + chrono::Duration::days(days))
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+            .to_rfc3339()
+    };
+    let todos = [
+        json!({PROP_TITLE: "Overdue", "dueDate": noon(-3)}),
+        json!({PROP_TITLE: "Today", "dueDate": noon(0)}),
+        json!({PROP_TITLE: "Tomorrow", "dueDate": noon(1)}),
+        json!({PROP_TITLE: "Later", "dueDate": noon(30)}),
+        json!({PROP_TITLE: "Snoozed", "startDate": noon(1)}),
+        json!({PROP_TITLE: "Started", "startDate": noon(-1)}),
+        json!({PROP_TITLE: "Done", "dueDate": noon(-1), "status": "completed"}),
+        json!({PROP_TITLE: "Someday"}),
+    ];
+    for body in &todos {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(Method::POST, BASE_URI, &token, Some(body)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    let resp = app
+        .oneshot(authed_request(Method::GET, "/api/agenda", &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["date"], today.to_string());
+    assert_eq!(json["timeZone"], "UTC");
+
+    let titles = |bucket: &str| -> Vec<String> {
+        json[bucket]["todos"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t[PROP_TITLE].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(titles("overdue"), ["Overdue"]);
+    assert_eq!(titles("today"), ["Today"]);
+    assert_eq!(titles("tomorrow"), ["Tomorrow", "Snoozed"]);
+    assert_eq!(titles("thisWeek"), Vec::<String>::new());
+    assert_eq!(titles("later"), ["Later"]);
+    assert_eq!(json["tomorrow"]["count"], 2);
+    assert_eq!(json["later"]["count"], 1);
+}
+
+// ステータス別・優先度別の件数、期限切れ件数、作成数・完了数の推移、平均リードタイム、連続完了日数を集計できることを確認する
+#[sqlx::test]
+async fn test_stats(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let yesterday = (chrono::Utc::now() - chrono::Duration::days(1)).to_rfc3339();
+    let todos = [
+        json!({PROP_TITLE: "High", PROP_PRIORITY: "high", "dueDate": yesterday}),
+        json!({PROP_TITLE: "Low", PROP_PRIORITY: "low"}),
+        json!({PROP_TITLE: "Medium"}),
+    ];
+    let mut ids = Vec::new();
+    for body in &todos {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(Method::POST, BASE_URI, &token, Some(body)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let json = response_json(resp.into_body()).await;
+        ids.push(json[PROP_ID].as_str().unwrap().to_string());
+    }
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::PATCH,
+            &format!("{}/{}/status", BASE_URI, ids[1]),
+            &token,
+            Some(&json!({PROP_STATUS: "completed"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/stats", &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(
+        json["byStatus"],
+        json!([
+            {"status": "pending", "count": 2},
+            {"status": "inProgress", "count": 0},
+            {"status": "completed", "count": 1},
+        ])
+    );
+    assert_eq!(
+        json["byPriority"],
+        json!([
+            {"priority": "low", "count": 1},
+            {"priority": "medium", "count": 1},
+            {"priority": "high", "count": 1},
+        ])
+    );
+    assert_eq!(json["overdue"], 1);
+    let series = json["series"].as_array().unwrap();
+    assert_eq!(series.len(), 30);
+    assert_eq!(series[29]["date"], json["to"]);
+    assert_eq!(series[29]["created"], 3);
+    assert_eq!(series[29]["completed"], 1);
+    assert!(json["averageLeadTimeSeconds"].as_i64().unwrap() >= 0);
+    assert_eq!(json["streak"], json!({"currentDays": 1, "longestDays": 1}));
+
+    // 週単位では週の始まりに揃えた期間ごとに集計する
+    let uri = uri_with_query("/api/stats", &[("interval", "week")]);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, &uri, &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    let series = json["series"].as_array().unwrap();
+    let last = series.last().unwrap();
+    assert_eq!(last["created"], 3);
+    assert_eq!(last["completed"], 1);
+    let first: chrono::NaiveDate = series[0]["date"].as_str().unwrap().parse().unwrap();
+    assert_eq!(chrono::Datelike::weekday(&first), chrono::Weekday::Mon);
+
+    let uri = uri_with_query("/api/stats", &[("from", "2026-01-10"), ("to", "2026-01-01")]);
+    let resp = app
+        .oneshot(authed_request(Method::GET, &uri, &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// 期限のあるToDoをiCalendarでエクスポートでき、購読トークンで取得・再発行・無効化できることを確認する
+#[sqlx::test]
+async fn test_calendar_export_and_feed(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    for body in [
+        json!({PROP_TITLE: "Dentist, 3F", "dueDate": "2026-03-20T06:00:00Z", PROP_PRIORITY: "high"}),
+        json!({PROP_TITLE: "Someday"}),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(Method::POST, BASE_URI, &token, Some(&body)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    let read_body = |resp: axum::response::Response| async move {
+        let bytes = http_body_util::BodyExt::collect(resp.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    };
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, &format!("{}/export.ics", BASE_URI), &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/calendar; charset=utf-8");
+    let ics = read_body(resp).await;
+    assert!(ics.contains("BEGIN:VTODO"));
+    assert!(ics.contains("BEGIN:VEVENT"));
+    assert!(ics.contains("SUMMARY:Dentist\\, 3F\r\n"));
+    assert!(ics.contains("DUE:20260320T060000Z\r\n"));
+    assert!(ics.contains("PRIORITY:1\r\n"));
+    assert!(!ics.contains("Someday"));
+
+    // 購読トークンの発行
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::POST, "/api/calendar/token", &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let issued = response_json(resp.into_body()).await;
+    let feed_url = issued["url"].as_str().unwrap().to_string();
+
+    let feed = |uri: String| {
+        Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let resp = app
+        .clone()
+        .oneshot(feed(format!("{}?component=vtodo", feed_url)))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let ics = read_body(resp).await;
+    assert!(ics.contains("BEGIN:VTODO"));
+    assert!(!ics.contains("BEGIN:VEVENT"));
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/calendar/token", &token, None))
+        .await
+        .unwrap();
+    let status = response_json(resp.into_body()).await;
+    assert_eq!(status["enabled"], true);
+
+    // 再発行すると以前のURLは使えない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::POST, "/api/calendar/token", &token, None))
+        .await
+        .unwrap();
+    let reissued = response_json(resp.into_body()).await;
+    let new_url = reissued["url"].as_str().unwrap().to_string();
+    assert_ne!(new_url, feed_url);
+    let resp = app.clone().oneshot(feed(feed_url)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let resp = app.clone().oneshot(feed(new_url.clone())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // 無効化
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::DELETE, "/api/calendar/token", &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = app.clone().oneshot(feed(new_url)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let resp = app
+        .oneshot(authed_request(Method::GET, "/api/calendar/token", &token, None))
+        .await
+        .unwrap();
+    let status = response_json(resp.into_body()).await;
+    assert_eq!(status["enabled"], false);
+}
+
+// アプリパスワードでCalDAVに接続し、PROPFIND・PUT・GET・REPORT・DELETEでToDoを同期できることを確認する
+#[sqlx::test]
+async fn test_caldav_sync(pool: PgPool) {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login_user(app, "caldav@example.com").await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            "/api/auth/app-passwords",
+            &token,
+            Some(&json!({"name": "Phone"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let issued = response_json(resp.into_body()).await;
+    let password = issued["password"].as_str().unwrap().to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, "/api/auth/me", &token, None))
+        .await
+        .unwrap();
+    let user_id = response_json(resp.into_body()).await[PROP_ID]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let basic = format!(
+        "Basic {}",
+        STANDARD.encode(format!("caldav@example.com:{}", password))
+    );
+    let dav = |method: &[u8], uri: &str, headers: &[(&str, &str)], body: &str| {
+        let mut builder = Request::builder()
+            .method(Method::from_bytes(method).unwrap())
+            .uri(uri)
+            .header(header::AUTHORIZATION, basic.as_str());
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    };
+    let read_body = |resp: axum::response::Response| async move {
+        let bytes = http_body_util::BodyExt::collect(resp.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    };
+    let collection = format!("/caldav/{}/todos/", user_id);
+    let object = format!("{}new-task.ics", collection);
+
+    // パスワードが違う場合はBasic認証を要求する
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::from_bytes(b"PROPFIND").unwrap())
+                .uri(&collection)
+                .header(
+                    header::AUTHORIZATION,
+                    format!("Basic {}", STANDARD.encode("caldav@example.com:wrong")),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert!(resp.headers().contains_key(header::WWW_AUTHENTICATE));
+
+    // ディスカバリー
+    let resp = app
+        .clone()
+        .oneshot(dav(
+            b"PROPFIND",
+            "/caldav/",
+            &[("Depth", "0")],
+            r#"<d:propfind xmlns:d="DAV:"><d:prop><d:current-user-principal/></d:prop></d:propfind>"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::MULTI_STATUS);
+    let xml = read_body(resp).await;
+    assert!(xml.contains(&format!("<d:href>/caldav/{}/</d:href>", user_id)));
+
+    // VTODOの作成
+    let vtodo = concat!(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTODO\r\n",
+        "UID:new-task@client\r\nSUMMARY:Pay rent\r\nDUE:20260401T000000Z\r\nPRIORITY:1\r\n",
+        "END:VTODO\r\nEND:VCALENDAR\r\n",
+    );
+    let resp = app
+        .clone()
+        .oneshot(dav(b"PUT", &object, &[("If-None-Match", "*")], vtodo))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let etag = resp.headers()[header::ETAG].to_str().unwrap().to_string();
+
+    // REST APIからも同じToDoが見える
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 1);
+    assert_eq!(json[PROP_ITEMS][0][PROP_TITLE], "Pay rent");
+    assert_eq!(json[PROP_ITEMS][0][PROP_PRIORITY], "high");
+
+    // 同じ名前で `If-None-Match: *` の場合は412
+    let resp = app
+        .clone()
+        .oneshot(dav(b"PUT", &object, &[("If-None-Match", "*")], vtodo))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+    // コレクションの一覧（Depth: 1）
+    let resp = app
+        .clone()
+        .oneshot(dav(
+            b"PROPFIND",
+            &collection,
+            &[("Depth", "1")],
+            r#"<d:propfind xmlns:d="DAV:" xmlns:cs="http://calendarserver.org/ns/"><d:prop><d:resourcetype/><d:getetag/><cs:getctag/></d:prop></d:propfind>"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::MULTI_STATUS);
+    let xml = read_body(resp).await;
+    assert!(xml.contains("<c:calendar/>"));
+    assert!(xml.contains("<cs:getctag>"));
+    assert!(xml.contains(&format!("<d:href>{}</d:href>", object)));
+    assert!(xml.contains(&format!("<d:getetag>{}</d:getetag>", etag.replace('"', "&quot;"))));
+
+    // GET
+    let resp = app.clone().oneshot(dav(b"GET", &object, &[], "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::ETAG].to_str().unwrap(), etag);
+    let ics = read_body(resp).await;
+    assert!(ics.contains("UID:new-task@client\r\n"));
+    assert!(ics.contains("SUMMARY:Pay rent\r\n"));
+
+    // calendar-multiget
+    let multiget = format!(
+        concat!(
+            r#"<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">"#,
+            "<d:prop><d:getetag/><c:calendar-data/></d:prop>",
+            "<d:href>{}</d:href><d:href>{}missing.ics</d:href>",
+            "</c:calendar-multiget>",
+        ),
+        object, collection
+    );
+    let resp = app
+        .clone()
+        .oneshot(dav(b"REPORT", &collection, &[("Depth", "1")], &multiget))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::MULTI_STATUS);
+    let xml = read_body(resp).await;
+    assert!(xml.contains("SUMMARY:Pay rent"));
+    assert!(xml.contains("HTTP/1.1 404 Not Found"));
+
+    // 更新はTodoServiceのバリデーションを通る
+    let invalid = vtodo.replace("SUMMARY:Pay rent", "SUMMARY:");
+    let resp = app
+        .clone()
+        .oneshot(dav(b"PUT", &object, &[("If-Match", &etag)], &invalid))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let updated = vtodo
+        .replace("SUMMARY:Pay rent", "SUMMARY:Pay rent and bills")
+        .replace("PRIORITY:1\r\n", "STATUS:COMPLETED\r\n");
+    let resp = app
+        .clone()
+        .oneshot(dav(b"PUT", &object, &[("If-Match", &etag)], &updated))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let new_etag = resp.headers()[header::ETAG].to_str().unwrap().to_string();
+    assert_ne!(new_etag, etag);
+
+    // 古いETagでの更新は412
+    let resp = app
+        .clone()
+        .oneshot(dav(b"PUT", &object, &[("If-Match", &etag)], &updated))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_ITEMS][0][PROP_TITLE], "Pay rent and bills");
+    assert_eq!(json[PROP_ITEMS][0][PROP_STATUS], "completed");
+
+    // DELETE
+    let resp = app
+        .clone()
+        .oneshot(dav(b"DELETE", &object, &[("If-Match", &new_etag)], ""))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = app.clone().oneshot(dav(b"GET", &object, &[], "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // 他のユーザーのパスは見えない
+    let resp = app
+        .clone()
+        .oneshot(dav(
+            b"PROPFIND",
+            &format!("/caldav/{}/todos/", uuid::Uuid::new_v4()),
+            &[("Depth", "0")],
+            "",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // アプリパスワードを無効化すると接続できなくなる
+    let id = issued[PROP_ID].as_str().unwrap();
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::DELETE,
+            &format!("/api/auth/app-passwords/{}", id),
+            &token,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = app
+        .oneshot(dav(b"PROPFIND", &collection, &[("Depth", "0")], ""))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// CalDAVのPUTでリソース名・UIDが255文字を超える場合は400になり、ToDoは作成されないことを確認する
+#[sqlx::test]
+async fn test_caldav_put_name_too_long(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login_user(app, "caldav@example.com").await;
+    let (basic, user_id) = caldav_login(&app, &token, "caldav@example.com").await;
+
+    let put = |name: &str, uid: &str| {
+        Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/caldav/{}/todos/{}.ics", user_id, name))
+            .header(header::AUTHORIZATION, basic.as_str())
+            .body(Body::from(format!(
+                concat!(
+                    "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTODO\r\n",
+                    "UID:{}\r\nSUMMARY:Pay rent\r\n",
+                    "END:VTODO\r\nEND:VCALENDAR\r\n",
+                ),
+                uid
+            )))
+            .unwrap()
+    };
+
+    let long = "a".repeat(256);
+    let resp = app.clone().oneshot(put(&long, "task@client")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app.clone().oneshot(put("task", &long)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json[PROP_TOTAL], 0);
+
+    let resp = app.oneshot(put("task", "task@client")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+// 絞り込み条件付きでCSV / JSONにエクスポートし、列の対応付け・ドライラン・行ごとのエラーを含めてインポートできることを確認する
+#[sqlx::test]
+async fn test_todo_export_and_import(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    for body in [
+        json!({PROP_TITLE: "Write, report", PROP_PRIORITY: "high", "tags": ["work", "q1"]}),
+        json!({PROP_TITLE: "Walk", "dueDate": "2026-03-20T06:00:00Z"}),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(authed_request(Method::POST, BASE_URI, &token, Some(&body)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    let read_body = |resp: axum::response::Response| async move {
+        let bytes = http_body_util::BodyExt::collect(resp.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    };
+
+    // JSON（既定）
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, &format!("{}/export", BASE_URI), &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+    let exported: serde_json::Value = serde_json::from_str(&read_body(resp).await).unwrap();
+    assert_eq!(exported.as_array().unwrap().len(), 2);
+
+    // CSV（一覧と同じ絞り込み条件）
+    let uri = uri_with_query(
+        &format!("{}/export", BASE_URI),
+        &[("format", "csv"), (PROP_PRIORITY, "high")],
+    );
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, &uri, &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+    let csv = read_body(resp).await;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("id,title,description,status,priority,"));
+    assert!(lines[1].contains(",\"Write, report\",,pending,high,"));
+    assert!(lines[1].contains(",work;q1,"));
+
+    // エクスポートしたCSVはそのまま取り込める
+    let import_uri = format!("{}/import", BASE_URI);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &import_uri,
+            &token,
+            Some(&json!({"format": "csv", "content": csv, "dryRun": true})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["total"], 1);
+    assert_eq!(json["imported"], 0);
+    assert_eq!(json["errors"].as_array().unwrap().len(), 0);
+
+    // 1行でもエラーがあれば何も保存しない
+    let content = "Task,Deadline,Priority\nPay rent,2026-04-01,High\n,2026-04-02,low\nCall,someday,urgent\n";
+    let mapping = json!({PROP_TITLE: "Task", "dueDate": "Deadline"});
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &import_uri,
+            &token,
+            Some(&json!({"format": "csv", "content": content, "mapping": mapping})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["imported"], 0);
+    let errors = json["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0]["row"], 2);
+    assert_eq!(errors[1]["row"], 3);
+    assert!(errors[1]["message"].as_str().unwrap().starts_with("dueDate:"));
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    assert_eq!(response_json(resp.into_body()).await[PROP_TOTAL], 2);
+
+    // CSV（列の対応付けあり）
+    let content = "Task,Deadline,Priority\nPay rent,2026-04-01,High\n";
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &import_uri,
+            &token,
+            Some(&json!({"format": "csv", "content": content, "mapping": mapping})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["imported"], 1);
+    assert_eq!(json["todos"][0][PROP_TITLE], "Pay rent");
+    assert_eq!(json["todos"][0][PROP_PRIORITY], "high");
+    assert_eq!(json["todos"][0]["dueDate"], "2026-04-01T00:00:00Z");
+
+    // JSON（エクスポートしたものを取り込む）
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &import_uri,
+            &token,
+            Some(&json!({"format": "json", "content": exported})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(response_json(resp.into_body()).await["imported"], 2);
+
+    let resp = app
+        .oneshot(authed_request(Method::GET, BASE_URI, &token, None))
+        .await
+        .unwrap();
+    assert_eq!(response_json(resp.into_body()).await[PROP_TOTAL], 5);
+}
+
+// CSVのエクスポートで数式として解釈される値の前に `'` を付け、取り込み時に元に戻すことを確認する
+#[sqlx::test]
+async fn test_todo_export_csv_escapes_formulas(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+    let (app, other_token) = register_and_login_user(app, "other@example.com").await;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            BASE_URI,
+            &token,
+            Some(&json!({
+                PROP_TITLE: "=HYPERLINK(\"http://example.com\")",
+                PROP_DESCRIPTION: "-2+3",
+                "tags": ["@home"],
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let uri = uri_with_query(&format!("{}/export", BASE_URI), &[("format", "csv")]);
+    let resp = app
+        .clone()
+        .oneshot(authed_request(Method::GET, &uri, &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = http_body_util::BodyExt::collect(resp.into_body())
+        .await
+        .unwrap()
+        .to_bytes();
+    let csv = String::from_utf8(bytes.to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert!(lines[1].contains(",\"'=HYPERLINK(\"\"http://example.com\"\")\",'-2+3,pending,"));
+    assert!(lines[1].contains(",'@home,"));
+
+    // 取り込むと元の値になる
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/import", BASE_URI),
+            &other_token,
+            Some(&json!({"format": "csv", "content": csv})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = app
+        .oneshot(authed_request(Method::GET, BASE_URI, &other_token, None))
+        .await
+        .unwrap();
+    let json = response_json(resp.into_body()).await;
+    let todo = &json[PROP_ITEMS][0];
+    assert_eq!(todo[PROP_TITLE], "=HYPERLINK(\"http://example.com\")");
+    assert_eq!(todo[PROP_DESCRIPTION], "-2+3");
+    assert_eq!(todo["tags"], json!(["@home"]));
+}
+
+// todo.txt 形式でインポートし、エクスポートすると同じ行に戻ることを確認する
+#[sqlx::test]
+async fn test_todotxt_import_export(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let content = concat!(
+        "(A) 2026-03-01 Write report +Work @office due:2026-03-20\n",
+        "\n",
+        "x 2026-03-15 2026-03-02 Pay rent t:2026-03-10 pri:C\n",
+        "2026-03-03 Stretch\n",
+    );
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/import", BASE_URI),
+            &token,
+            Some(&json!({"format": "todotxt", "content": content})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["imported"], 3);
+    let todos = json["todos"].as_array().unwrap();
+    assert_eq!(todos[0][PROP_PRIORITY], "high");
+    assert_eq!(todos[0]["tags"], json!(["+Work", "office"]));
+    assert_eq!(todos[0]["dueDate"], "2026-03-20T00:00:00Z");
+    assert_eq!(todos[0]["createdAt"], "2026-03-01T00:00:00Z");
+    assert_eq!(todos[1][PROP_STATUS], "completed");
+    assert_eq!(todos[1][PROP_PRIORITY], "low");
+    assert_eq!(todos[1]["completedAt"], "2026-03-15T00:00:00Z");
+    assert_eq!(todos[2][PROP_PRIORITY], "medium");
+
+    // 不正な行があれば何も保存しない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/import", BASE_URI),
+            &token,
+            Some(&json!({"format": "todotxt", "content": "Valid\n(A) +OnlyProject\n"})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json(resp.into_body()).await["errors"][0]["row"], 2);
+
+    let uri = uri_with_query(
+        &format!("{}/export", BASE_URI),
+        &[("format", "todotxt"), ("sort", "created_at"), ("order", "asc")],
+    );
+    let resp = app
+        .oneshot(authed_request(Method::GET, &uri, &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/plain; charset=utf-8");
+    let bytes = http_body_util::BodyExt::collect(resp.into_body())
+        .await
+        .unwrap()
+        .to_bytes();
+    assert_eq!(
+        String::from_utf8(bytes.to_vec()).unwrap(),
+        content.replace("\n\n", "\n")
+    );
+}
+
+// 他のツールからのインポートで、サブタスク・変換内容の記録・ドライランを確認する
+#[sqlx::test]
+async fn test_external_import(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let content = json!({
+        "projects": [{"id": "p1", "name": "Home"}],
+        "items": [
+            {"id": "1", "content": "Move out", "priority": 3, "project_id": "p1", "labels": ["family"],
+             "due": {"date": "2026-04-01", "is_recurring": false}},
+            {"id": "2", "content": "Pack books", "parent_id": "1", "checked": true,
+             "completed_at": "2026-03-10T09:00:00Z"},
+            {"id": "3", "content": "x".repeat(300), "due": {"date": "2026-03-01"}}
+        ]
+    });
+
+    // ドライランでは保存しない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/import/todoist", BASE_URI),
+            &token,
+            Some(&json!({"content": content, "dryRun": true})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["source"], "todoist");
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["imported"], 0);
+    let kinds: Vec<(&str, &str)> = json["notes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| (n["field"].as_str().unwrap(), n["kind"].as_str().unwrap()))
+        .collect();
+    assert_eq!(kinds, vec![("priority", "transformed"), ("title", "transformed")]);
+
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/import/todoist", BASE_URI),
+            &token,
+            Some(&json!({"content": content})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["imported"], 3);
+    let todos = json["todos"].as_array().unwrap();
+    assert_eq!(todos[0][PROP_PRIORITY], "high");
+    assert_eq!(todos[0]["tags"], json!(["+Home", "family"]));
+    assert_eq!(todos[1]["parentId"], todos[0][PROP_ID]);
+    assert_eq!(todos[1][PROP_STATUS], "completed");
+    assert_eq!(todos[1]["completedAt"], "2026-03-10T09:00:00Z");
+    assert_eq!(todos[2][PROP_TITLE].as_str().unwrap().chars().count(), 255);
+
+    // 取り込めないタスクがあれば何も保存しない
+    let resp = app
+        .clone()
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/import/taskwarrior", BASE_URI),
+            &token,
+            Some(&json!({"content": [{"description": "Valid"}, {"description": "  "}]})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let json = response_json(resp.into_body()).await;
+    assert_eq!(json["errors"][0]["row"], 2);
+    assert_eq!(json["imported"], 0);
+
+    let resp = app
+        .oneshot(authed_request(
+            Method::POST,
+            &format!("{}/import/things", BASE_URI),
+            &token,
+            Some(&json!({"content": []})),
+        ))
+        .await
+        .unwrap();
+    assert!(resp.status().is_client_error());
+}
@@ -0,0 +1,141 @@
+use chrono::NaiveDate;
+use todo_backend::models::todo::TodoPriority;
+use todo_backend::services::todotxt::{format_line, parse_line, TodoTxtTask};
+
+fn date(month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(2026, month, day)
+}
+
+// 優先度・作成日・期限・開始日・プロジェクト・コンテキストに分解されることを確認する
+#[test]
+fn test_parse_line() {
+    let task = parse_line("(A) 2026-03-01 Call mom +Family @phone due:2026-03-20 t:2026-03-10 note:keep").unwrap();
+    assert_eq!(
+        task,
+        TodoTxtTask {
+            title: "Call mom note:keep".to_string(),
+            priority: Some(TodoPriority::High),
+            completed: false,
+            completed_on: None,
+            created_on: date(3, 1),
+            due_on: date(3, 20),
+            start_on: date(3, 10),
+            tags: vec!["+Family".to_string(), "phone".to_string()],
+        }
+    );
+
+    let done = parse_line("x 2026-03-15 2026-03-01 Pay rent pri:C").unwrap();
+    assert!(done.completed);
+    assert_eq!(done.completed_on, date(3, 15));
+    assert_eq!(done.created_on, date(3, 1));
+    assert_eq!(done.priority, Some(TodoPriority::Low));
+    assert_eq!(done.title, "Pay rent");
+
+    assert_eq!(parse_line("(B) Review").unwrap().priority, Some(TodoPriority::Medium));
+    assert_eq!(parse_line("(D) Someday").unwrap().priority, Some(TodoPriority::Low));
+    // 先頭以外の `(A)` や小文字の `x` 以外はタイトルの一部
+    assert_eq!(parse_line("Read (A) book").unwrap().title, "Read (A) book");
+    assert!(!parse_line("xylophone lesson").unwrap().completed);
+    assert_eq!(parse_line("   "), None);
+}
+
+// 出力した行を読み直すと同じ内容になることを確認する
+#[test]
+fn test_round_trip() {
+    let tasks = [
+        TodoTxtTask {
+            title: "Write report".to_string(),
+            priority: Some(TodoPriority::High),
+            created_on: date(3, 1),
+            due_on: date(3, 20),
+            tags: vec!["+Work".to_string(), "office".to_string()],
+            ..Default::default()
+        },
+        TodoTxtTask {
+            title: "Pay rent".to_string(),
+            priority: Some(TodoPriority::Low),
+            completed: true,
+            completed_on: date(3, 15),
+            created_on: date(3, 1),
+            start_on: date(3, 10),
+            ..Default::default()
+        },
+        TodoTxtTask {
+            title: "Stretch".to_string(),
+            priority: Some(TodoPriority::Medium),
+            created_on: date(3, 2),
+            ..Default::default()
+        },
+    ];
+
+    assert_eq!(
+        format_line(&tasks[0]),
+        "(A) 2026-03-01 Write report +Work @office due:2026-03-20"
+    );
+    assert_eq!(
+        format_line(&tasks[1]),
+        "x 2026-03-15 2026-03-01 Pay rent t:2026-03-10 pri:C"
+    );
+    assert_eq!(format_line(&tasks[2]), "2026-03-02 Stretch");
+
+    for task in tasks {
+        let mut parsed = parse_line(&format_line(&task)).unwrap();
+        // 中の優先度は出力しないため、読み直すと未指定（作成時の既定値は中）
+        if parsed.priority.is_none() {
+            parsed.priority = Some(TodoPriority::Medium);
+        }
+        assert_eq!(parsed, task);
+    }
+}
+
+// タイトルの語が優先度・完了・タグ・日付として読み取られず、完了日のない完了タスクも同じ内容に読み直せることを確認する
+#[test]
+fn test_round_trip_ambiguous_titles() {
+    let tasks = [
+        TodoTxtTask {
+            title: "x marks the spot".to_string(),
+            created_on: date(3, 1),
+            ..Default::default()
+        },
+        TodoTxtTask {
+            title: "(A) is not a priority".to_string(),
+            ..Default::default()
+        },
+        TodoTxtTask {
+            title: "2026-03-05 meeting notes".to_string(),
+            ..Default::default()
+        },
+        TodoTxtTask {
+            title: "Email @alice about +launch due:2026-04-01 t:2026-03-01 pri:A \\n".to_string(),
+            tags: vec!["work".to_string()],
+            ..Default::default()
+        },
+        TodoTxtTask {
+            title: "Archive".to_string(),
+            completed: true,
+            ..Default::default()
+        },
+    ];
+
+    assert_eq!(format_line(&tasks[0]), "2026-03-01 \\x marks the spot");
+    assert_eq!(
+        format_line(&tasks[3]),
+        "Email \\@alice about \\+launch \\due:2026-04-01 \\t:2026-03-01 \\pri:A \\\\n @work"
+    );
+    // 完了日のない完了タスクの作成日は出力しない（読み取り時に完了日になるため）
+    let undated = TodoTxtTask {
+        title: "Archive".to_string(),
+        completed: true,
+        created_on: date(3, 1),
+        ..Default::default()
+    };
+    assert_eq!(format_line(&undated), "x Archive");
+
+    for task in tasks {
+        let mut parsed = parse_line(&format_line(&task)).unwrap();
+        if parsed.priority.is_none() {
+            parsed.priority = task.priority.clone();
+        }
+        assert_eq!(parsed, task);
+    }
+}
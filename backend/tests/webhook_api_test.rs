@@ -0,0 +1,344 @@
+use std::sync::{
+    atomic::{AtomicU16, Ordering},
+    Arc, Mutex,
+};
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, Method, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use todo_backend::{
+    build_app_state, build_router, config::Config, services::webhook_service::WebhookService,
+};
+use tower::ServiceExt;
+
+mod helper;
+use helper::{authed_request, register_and_login, register_and_login_user, test_config};
+
+// ////////////////////////////////////////////////////////////
+// テストケース
+// ////////////////////////////////////////////////////////////
+
+const URI_WEBHOOKS: &str = "/api/webhooks";
+const URI_TODOS: &str = "/api/todos";
+
+/// 受信したリクエストを記録し、指定したステータスを返すローカルの受信サーバー
+#[derive(Clone)]
+struct Receiver {
+    requests: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+    status: Arc<AtomicU16>,
+}
+
+impl Receiver {
+    async fn start() -> (Self, String) {
+        let receiver = Self {
+            requests: Arc::new(Mutex::new(Vec::new())),
+            status: Arc::new(AtomicU16::new(200)),
+        };
+        let app = Router::new()
+            .route("/hook", post(Self::receive))
+            .route("/large", post(|| async { "a".repeat(100_000) }))
+            .with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (receiver, url)
+    }
+
+    async fn receive(State(receiver): State<Self>, headers: HeaderMap, body: Bytes) -> StatusCode {
+        receiver.requests.lock().unwrap().push((headers, body));
+        StatusCode::from_u16(receiver.status.load(Ordering::SeqCst)).unwrap()
+    }
+
+    fn respond_with(&self, status: u16) {
+        self.status.store(status, Ordering::SeqCst);
+    }
+
+    fn take(&self) -> Vec<(HeaderMap, Bytes)> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
+
+/// ローカルの受信サーバーに送信できる設定
+fn local_config() -> Config {
+    let mut config = test_config();
+    config.webhook_allow_private_urls = true;
+    config
+}
+
+async fn send(app: &Router, method: Method, uri: &str, token: &str, body: Option<&Value>) -> (StatusCode, Value) {
+    let resp = app
+        .clone()
+        .oneshot(authed_request(method, uri, token, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = http_body_util::BodyExt::collect(resp.into_body())
+        .await
+        .unwrap()
+        .to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+// 購読したイベントだけが署名付きで配信され、配信ログに残ることを確認する
+#[sqlx::test]
+async fn test_webhook_delivery(pool: PgPool) {
+    let (receiver, url) = Receiver::start().await;
+    let state = build_app_state(pool, local_config());
+    let webhook_service = state.webhook_service.clone();
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let (status, webhook) = send(
+        &app,
+        Method::POST,
+        URI_WEBHOOKS,
+        &token,
+        Some(&json!({"url": url, "events": ["todo.created", "todo.status_changed"]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let secret = webhook["secret"].as_str().unwrap().to_string();
+    let webhook_id = webhook["id"].as_str().unwrap().to_string();
+
+    let (_, todo) = send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": "Deploy"}))).await;
+    let todo_id = todo["id"].as_str().unwrap();
+    let todo_uri = format!("{}/{}", URI_TODOS, todo_id);
+    send(&app, Method::PUT, &todo_uri, &token, Some(&json!({"title": "Deploy v2"}))).await;
+    send(&app, Method::PATCH, &format!("{}/status", todo_uri), &token, Some(&json!({"status": "completed"}))).await;
+
+    assert_eq!(webhook_service.process().await.unwrap(), 2);
+    let mut requests = receiver.take();
+    assert_eq!(requests.len(), 2);
+
+    let mut events = Vec::new();
+    for (headers, body) in &requests {
+        let timestamp: i64 = headers["x-webhook-timestamp"].to_str().unwrap().parse().unwrap();
+        assert_eq!(
+            headers["x-webhook-signature"].to_str().unwrap(),
+            format!("sha256={}", WebhookService::signature(&secret, timestamp, body))
+        );
+        assert_eq!(headers["x-webhook-id"].to_str().unwrap(), webhook_id);
+        let payload: Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload["event"], headers["x-webhook-event"].to_str().unwrap());
+        assert_eq!(payload["data"]["todoId"], todo_id);
+        events.push(payload);
+    }
+    events.sort_by_key(|p| p["data"]["version"].as_i64());
+    assert_eq!(events[0]["event"], "todo.created");
+    assert_eq!(events[1]["event"], "todo.status_changed");
+    assert_eq!(events[1]["data"]["changes"]["status"]["new"], "completed");
+    assert_eq!(events[1]["data"]["todo"]["title"], "Deploy v2");
+
+    // 送信済みの配信は再送しない
+    assert_eq!(webhook_service.process().await.unwrap(), 0);
+
+    let deliveries_uri = format!("{}/{}/deliveries", URI_WEBHOOKS, webhook_id);
+    let (status, deliveries) = send(&app, Method::GET, &deliveries_uri, &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let deliveries = deliveries.as_array().unwrap();
+    assert_eq!(deliveries.len(), 2);
+    assert!(deliveries.iter().all(|d| d["status"] == "succeeded" && d["attempts"] == 1));
+    assert_eq!(deliveries[0]["responseStatus"], 200);
+
+    // 配信のやり直し
+    let delivery_id = deliveries[1]["id"].as_str().unwrap();
+    let (status, redelivery) = send(
+        &app,
+        Method::POST,
+        &format!("{}/{}/redeliver", deliveries_uri, delivery_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(redelivery["redeliveryOf"], delivery_id);
+    assert_eq!(webhook_service.process().await.unwrap(), 1);
+    requests = receiver.take();
+    assert_eq!(requests.len(), 1);
+    let payload: Value = serde_json::from_slice(&requests[0].1).unwrap();
+    assert_eq!(payload["id"], deliveries[1]["payload"]["id"]);
+}
+
+// 失敗した配信を再試行し、続けて失敗したWebhookは無効になることを確認する
+#[sqlx::test]
+async fn test_webhook_retry_and_disable(pool: PgPool) {
+    let (receiver, url) = Receiver::start().await;
+    let mut config = local_config();
+    config.webhook_retry_base = 0;
+    let state = build_app_state(pool, config);
+    let webhook_service = state.webhook_service.clone();
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let request = json!({"url": url, "events": ["todo.created"], "description": "CI"});
+    let (_, webhook) = send(&app, Method::POST, URI_WEBHOOKS, &token, Some(&request)).await;
+    let webhook_uri = format!("{}/{}", URI_WEBHOOKS, webhook["id"].as_str().unwrap());
+
+    receiver.respond_with(500);
+    send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": "First"}))).await;
+    assert_eq!(webhook_service.process().await.unwrap(), 1);
+
+    let (_, deliveries) = send(&app, Method::GET, &format!("{}/deliveries", webhook_uri), &token, None).await;
+    assert_eq!(deliveries[0]["status"], "pending");
+    assert_eq!(deliveries[0]["attempts"], 1);
+    assert_eq!(deliveries[0]["responseStatus"], 500);
+    assert_eq!(deliveries[0]["error"], "HTTP 500 Internal Server Error");
+
+    // 2回目で成功すると連続失敗回数がリセットされる
+    receiver.respond_with(204);
+    assert_eq!(webhook_service.process().await.unwrap(), 1);
+    let (_, deliveries) = send(&app, Method::GET, &format!("{}/deliveries", webhook_uri), &token, None).await;
+    assert_eq!(deliveries[0]["status"], "succeeded");
+    assert_eq!(deliveries[0]["attempts"], 2);
+    let (_, webhook) = send(&app, Method::GET, &webhook_uri, &token, None).await;
+    assert_eq!(webhook["consecutiveFailures"], 0);
+
+    // 再試行の上限に達した配信は失敗になり、失敗が続くと無効になる
+    receiver.respond_with(500);
+    send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": "Second"}))).await;
+    send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": "Third"}))).await;
+    while webhook_service.process().await.unwrap() > 0 {}
+
+    let (_, webhook) = send(&app, Method::GET, &webhook_uri, &token, None).await;
+    assert_eq!(webhook["active"], false);
+    assert!(webhook["disabledAt"].is_string());
+    assert_eq!(webhook["consecutiveFailures"], 10);
+    let (_, deliveries) = send(&app, Method::GET, &format!("{}/deliveries", webhook_uri), &token, None).await;
+    let deliveries = deliveries.as_array().unwrap();
+    assert_eq!(deliveries.len(), 3);
+    assert!(deliveries[..2].iter().all(|d| d["status"] == "failed"));
+    assert_eq!(deliveries[..2].iter().map(|d| d["attempts"].as_i64().unwrap()).sum::<i64>(), 10);
+
+    // 無効なWebhookは配信し直せない
+    let failed_id = deliveries[0]["id"].as_str().unwrap();
+    let redeliver_uri = format!("{}/deliveries/{}/redeliver", webhook_uri, failed_id);
+    let (status, _) = send(&app, Method::POST, &redeliver_uri, &token, None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // 有効に戻すと配信し直せる
+    receiver.respond_with(200);
+    let (status, webhook) = send(&app, Method::PUT, &webhook_uri, &token, Some(&request)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(webhook["active"], true);
+    assert!(webhook["disabledAt"].is_null());
+    receiver.take();
+    let (status, _) = send(&app, Method::POST, &redeliver_uri, &token, None).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(webhook_service.process().await.unwrap(), 1);
+    assert_eq!(receiver.take().len(), 1);
+}
+
+// URLとイベントの検証と、他のユーザーのWebhookにアクセスできないことを確認する
+#[sqlx::test]
+async fn test_webhook_validation(pool: PgPool) {
+    let state = build_app_state(pool, test_config());
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    for body in [
+        json!({"url": "not a url", "events": ["todo.created"]}),
+        json!({"url": "ftp://example.com/hook", "events": ["todo.created"]}),
+        json!({"url": "https://203.0.113.10/hook", "events": []}),
+        json!({"url": "https://203.0.113.10/hook", "events": ["todo.archived"]}),
+    ] {
+        let (status, _) = send(&app, Method::POST, URI_WEBHOOKS, &token, Some(&body)).await;
+        assert!(status.is_client_error(), "{}", body);
+    }
+
+    let (status, webhook) = send(
+        &app,
+        Method::POST,
+        URI_WEBHOOKS,
+        &token,
+        Some(&json!({"url": "https://203.0.113.10/hook", "events": ["todo.deleted", "todo.deleted"]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(webhook["events"], json!(["todo.deleted"]));
+    let webhook_uri = format!("{}/{}", URI_WEBHOOKS, webhook["id"].as_str().unwrap());
+
+    let (_, list) = send(&app, Method::GET, URI_WEBHOOKS, &token, None).await;
+    assert_eq!(list.as_array().unwrap().len(), 1);
+    assert!(list[0].get("secret").is_none());
+
+    let (app, other) = register_and_login_user(app, "other@example.com").await;
+    let (status, _) = send(&app, Method::GET, &webhook_uri, &other, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&app, Method::DELETE, &webhook_uri, &other, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&app, Method::DELETE, &webhook_uri, &token, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, Method::GET, &webhook_uri, &token, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// プライベート・ループバック・リンクローカルのアドレスには登録・送信できないことを確認する
+#[sqlx::test]
+async fn test_webhook_private_address(pool: PgPool) {
+    let app = build_router(build_app_state(pool.clone(), test_config()));
+    let (app, token) = register_and_login(app).await;
+
+    for url in [
+        "http://127.0.0.1:8080/hook",
+        "http://localhost/hook",
+        "http://10.0.0.1/hook",
+        "http://192.168.1.10/hook",
+        "http://169.254.169.254/latest/meta-data",
+        "http://[::1]/hook",
+        "http://[::ffff:127.0.0.1]/hook",
+        "http://[fd00::1]/hook",
+        "http://[::10.0.0.1]/hook",
+        "http://[64:ff9b::7f00:1]/hook",
+        "http://[64:ff9b::a9fe:a9fe]/latest/meta-data",
+        "http://[2002:a00:1::1]/hook",
+        "http://[2002:7f00:1::]/hook",
+    ] {
+        let body = json!({"url": url, "events": ["todo.created"]});
+        let (status, _) = send(&app, Method::POST, URI_WEBHOOKS, &token, Some(&body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", url);
+    }
+
+    // 許可されていた間に登録したローカルのURLにも送信しない
+    let (receiver, url) = Receiver::start().await;
+    let local_app = build_router(build_app_state(pool.clone(), local_config()));
+    let body = json!({"url": url, "events": ["todo.created"]});
+    let (status, webhook) = send(&local_app, Method::POST, URI_WEBHOOKS, &token, Some(&body)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let webhook_uri = format!("{}/{}", URI_WEBHOOKS, webhook["id"].as_str().unwrap());
+
+    let webhook_service = build_app_state(pool, test_config()).webhook_service;
+    send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": "Secret"}))).await;
+    assert_eq!(webhook_service.process().await.unwrap(), 1);
+    assert!(receiver.take().is_empty());
+    let (_, deliveries) = send(&app, Method::GET, &format!("{}/deliveries", webhook_uri), &token, None).await;
+    assert_eq!(deliveries[0]["status"], "pending");
+    assert!(deliveries[0]["error"].as_str().unwrap().contains("private address"));
+}
+
+// 配信ログに保存するレスポンスボディは上限のバイト数までしか読み取らないことを確認する
+#[sqlx::test]
+async fn test_webhook_response_body_limit(pool: PgPool) {
+    let (_receiver, url) = Receiver::start().await;
+    let state = build_app_state(pool, local_config());
+    let webhook_service = state.webhook_service.clone();
+    let app = build_router(state);
+    let (app, token) = register_and_login(app).await;
+
+    let body = json!({"url": url.replace("/hook", "/large"), "events": ["todo.created"]});
+    let (_, webhook) = send(&app, Method::POST, URI_WEBHOOKS, &token, Some(&body)).await;
+    send(&app, Method::POST, URI_TODOS, &token, Some(&json!({"title": "Large"}))).await;
+    assert_eq!(webhook_service.process().await.unwrap(), 1);
+
+    let uri = format!("{}/{}/deliveries", URI_WEBHOOKS, webhook["id"].as_str().unwrap());
+    let (_, deliveries) = send(&app, Method::GET, &uri, &token, None).await;
+    assert_eq!(deliveries[0]["status"], "succeeded");
+    assert_eq!(deliveries[0]["responseBody"].as_str().unwrap().len(), 1000);
+}